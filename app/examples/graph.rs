@@ -43,7 +43,8 @@ fn main() {
             draw_nodes: true,
             node_radius: 0.2,
             node_segments: 50,
-            width: 1.0
+            width: 1.0,
+            ..default()
         })
         // .insert_resource(GraphGizmosConfig {
         //     line_color: palettes::tailwind::YELLOW_50.into(), 
@@ -10,6 +10,7 @@ use picking::PickingExtraPlugin;
 use styles::StylesPlugin;
 use widget_3d::Ui3dPlugin;
 use crate::{
+    circuit::{layout::CircuitLayoutPlugin, simulation::CircuitSimulationPlugin},
     event::{BildInEvent, BildOutEvent},
     view::{schematic_2d::Schematic2dPlugin},
 };
@@ -76,6 +77,8 @@ impl Plugin for AppPlugin {
                 PickingExtraPlugin,
                 Ui3dPlugin,
                 UiPlugin,
+                CircuitLayoutPlugin,
+                CircuitSimulationPlugin,
                 //views
                 // Layout3dViewPlugin,
                 Schematic2dPlugin {},
@@ -6,6 +6,55 @@ use geometry::representation::polyline::prelude::*;
 
 use super::circuit_graph::{EdgeFrom, EdgeTo, EdgeColor, CircuitEdgeQuery, CircuitNodeQuery, CircuitNode};
 
+/// Maps a normalised scalar in `[0, 1]` to a colour, e.g. for voltage colour-coding.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Colormap {
+    /// Perceptually uniform dark-blue → green → yellow.
+    Viridis,
+    /// Diverging blue → white → red, useful for voltages around a midpoint.
+    CoolWarm,
+    Grayscale,
+    /// Evenly spaced colour stops.
+    Custom(Vec<Color>),
+}
+
+impl Colormap {
+    const VIRIDIS: [(f32, f32, f32); 5] = [
+        (0.267, 0.005, 0.329),
+        (0.229, 0.322, 0.546),
+        (0.128, 0.567, 0.551),
+        (0.369, 0.789, 0.383),
+        (0.993, 0.906, 0.144),
+    ];
+    const COOL_WARM: [(f32, f32, f32); 3] = [(0.230, 0.299, 0.754), (0.865, 0.865, 0.865), (0.706, 0.016, 0.150)];
+
+    /// Sample the colormap, interpolating stops in linear RGB; `t` is clamped to `[0, 1]`.
+    pub fn sample(&self, t: f32) -> Color {
+        let stops: Vec<Color> = match self {
+            Colormap::Viridis => Self::VIRIDIS.iter().map(|&(r, g, b)| Color::srgb(r, g, b)).collect(),
+            Colormap::CoolWarm => Self::COOL_WARM.iter().map(|&(r, g, b)| Color::srgb(r, g, b)).collect(),
+            Colormap::Grayscale => vec![Color::BLACK, Color::WHITE],
+            Colormap::Custom(stops) => stops.clone(),
+        };
+        match stops.len() {
+            0 => Color::WHITE,
+            1 => stops[0],
+            n => {
+                let scaled = t.clamp(0.0, 1.0) * (n - 1) as f32;
+                let i = (scaled.floor() as usize).min(n - 2);
+                stops[i].to_linear().mix(&stops[i + 1].to_linear(), scaled - i as f32).into()
+            }
+        }
+    }
+
+    /// Sample for `value` within `range`; a degenerate range maps to the midpoint.
+    pub fn sample_range(&self, value: f32, range: (f32, f32)) -> Color {
+        let span = range.1 - range.0;
+        let t = if span.abs() <= f32::EPSILON { 0.5 } else { (value - range.0) / span };
+        self.sample(t)
+    }
+}
+
 #[derive(Resource, Clone)]
 pub struct GraphRenderConfig {
     pub default_color: Color,
@@ -14,6 +63,10 @@ pub struct GraphRenderConfig {
     pub node_radius: f32,
     pub node_color: Color,
     pub node_segments: usize,
+    /// Colormap used to colour-code edges by the voltage of their net.
+    pub voltage_colormap: Colormap,
+    /// Voltage range mapped onto the colormap; `None` fits the current solution.
+    pub voltage_range: Option<(f32, f32)>,
 }
 
 impl Default for GraphRenderConfig {
@@ -25,6 +78,8 @@ impl Default for GraphRenderConfig {
             node_radius: 0.12,
             node_color: Color::srgb(1.0, 0.6, 0.2),
             node_segments: 16,
+            voltage_colormap: Colormap::Viridis,
+            voltage_range: None,
         }
     }
 }
@@ -0,0 +1,83 @@
+use bevy::prelude::*;
+use block3d_core::block::Block3DLike;
+
+use crate::circuit::part::{LayoutPlacement, Part};
+
+/// A layout result to apply to part entities, e.g. from the WFC solver or the
+/// block tool: the minimum grid cell of each part's block.
+#[derive(Event, BufferedEvent, Debug, Clone, Default)]
+pub struct ApplyLayout(pub Vec<(Entity, IVec3)>);
+
+/// Keeps every part's `LayoutPlacement` up to date so `SolveThermal` sees the
+/// whole circuit.
+pub struct CircuitLayoutPlugin;
+
+impl Plugin for CircuitLayoutPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ApplyLayout>()
+            .add_systems(Update, (apply_layout, pack_unplaced_parts).chain());
+    }
+}
+
+/// The grid cell holding the minimum corner of a unit block centred at `center`.
+pub fn layout_cell(center: Vec3) -> IVec3 {
+    (center - Vec3::splat(0.5)).round().as_ivec3()
+}
+
+fn apply_layout(
+    mut commands: Commands,
+    mut layouts: EventReader<ApplyLayout>,
+    parts: Query<(), With<Part>>,
+) {
+    for ApplyLayout(placements) in layouts.read() {
+        for &(entity, cell) in placements {
+            if parts.contains(entity) {
+                commands.entity(entity).insert(LayoutPlacement(cell));
+            } else {
+                warn!("Layout places {:?}, which is not a part", entity);
+            }
+        }
+    }
+}
+
+/// Parts nobody has placed yet go in a row along +X past everything already placed.
+fn pack_unplaced_parts(
+    mut commands: Commands,
+    unplaced: Query<(Entity, &Part), Without<LayoutPlacement>>,
+    placed: Query<(&Part, &LayoutPlacement)>,
+) {
+    let mut next_x = placed
+        .iter()
+        .map(|(part, placement)| placement.0.x + part.size().0 as i32)
+        .max()
+        .unwrap_or(0);
+    for (entity, part) in &unplaced {
+        commands.entity(entity).insert(LayoutPlacement(IVec3::new(next_x, 0, 0)));
+        next_x += part.size().0 as i32;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuit::simulation::thermal_network::ThermalNetworkConfig;
+    use crate::circuit::simulation::{BlockTemperatures, CircuitSimulationPlugin, SolveThermal};
+
+    #[test]
+    fn thermal_solve_sees_placed_parts() {
+        let mut app = App::new();
+        app.add_plugins((CircuitLayoutPlugin, CircuitSimulationPlugin));
+        let placed = app.world_mut().spawn(Part::default()).id();
+        let packed = app.world_mut().spawn(Part::default()).id();
+        app.world_mut().write_event(ApplyLayout(vec![(placed, IVec3::new(0, 0, 2))]));
+        app.update();
+
+        assert_eq!(app.world().get::<LayoutPlacement>(placed), Some(&LayoutPlacement(IVec3::new(0, 0, 2))));
+        assert!(app.world().get::<LayoutPlacement>(packed).is_some_and(|placement| placement.0.x >= 1));
+
+        app.world_mut().write_event(SolveThermal::SteadyState(ThermalNetworkConfig::default()));
+        app.update();
+        let temperatures = &app.world().resource::<BlockTemperatures>().current;
+        assert!(temperatures.contains_key(&placed) && temperatures.contains_key(&packed));
+    }
+}
@@ -1,7 +1,11 @@
+pub mod layout;
 pub mod net;
 pub mod part;
 pub mod pin;
+pub mod simulation;
+pub mod symbol;
 pub mod trace;
+pub mod value;
 
 pub mod circuit_graph;
 pub mod circuit_graph_render;
@@ -26,4 +26,10 @@ impl Net {
     pub fn new(net_type: NetKind) -> Self {
         Self { net_type }
     }
-}
\ No newline at end of file
+}
+
+/// Ideal supply holding a net at a fixed voltage relative to ground.
+#[derive(Component, Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct NetSupply {
+    pub volts: f64,
+}
@@ -20,6 +20,22 @@ use circuit_physics_core::physical::{
 };
 
 use circuit_physics_core::material_properties::MaterialProperties;
+use circuit_physics_core::units::celsius;
+use circuit_physics_core::thermal::{TemperatureDependent, ThermalBehavior};
+use circuit_physics_core::lifetime::{AgingMechanisms, WearOut};
+use circuit_physics_core::mechanical::{MechanicalStress, SolderJointReliability};
+use circuit_physics_core::electromagnetic::ElectromagneticCompatibility;
+use circuit_physics_core::radiation::RadiationHardness;
+use circuit_physics_core::acoustic::{resonant_gain, AcousticBehavior};
+use circuit_physics_core::environment_degradation::{ChemicalMaterial, CorrosionResistance, EnvironmentalDegradation};
+use uom::si::{
+    area::{square_centimeter, square_meter, square_millimeter}, electric_field::volt_per_meter,
+    electrical_resistivity::ohm_meter, energy::electronvolt, length::meter,
+    mass_density::kilogram_per_cubic_meter, pressure::pascal, ratio::percent,
+    specific_heat_capacity::joule_per_kilogram_kelvin, temperature_coefficient::ppm_per_kelvin,
+    thermal_conductivity::watt_per_meter_kelvin, time::year,
+};
+
 
 
 
@@ -83,10 +99,171 @@ impl FrequencyDependent for Capacitor {
 }
 
 impl MaterialProperties for Capacitor {
-    fn thermal_conductivity(&self) -> f32 { 0.5 }
-    fn electrical_resistivity(&self) -> f32 { 1e14 } // High for dielectric
-    fn youngs_modulus(&self) -> f32 { 5e9 }
-    fn poisson_ratio(&self) -> f32 { 0.3 }
-    fn density(&self) -> f32 { 3000.0 }
-    fn specific_heat(&self) -> f32 { 800.0 }
-}
\ No newline at end of file
+    fn thermal_conductivity(&self) -> ThermalConductivity { ThermalConductivity::new::<watt_per_meter_kelvin>(0.5) }
+    fn electrical_resistivity(&self) -> ElectricalResistivity { ElectricalResistivity::new::<ohm_meter>(1e14) } // High for dielectric
+    fn youngs_modulus(&self) -> Pressure { Pressure::new::<pascal>(5e9) }
+    fn poisson_ratio(&self) -> f64 { 0.3 }
+    fn density(&self) -> MassDensity { MassDensity::new::<kilogram_per_cubic_meter>(3000.0) }
+    fn specific_heat(&self) -> SpecificHeatCapacity { SpecificHeatCapacity::new::<joule_per_kilogram_kelvin>(800.0) }
+}
+
+impl ThermalBehavior for Capacitor {
+    fn thermal_resistance_jc(&self) -> ThermalResistance { self.package.typical_thermal_resistance_jc() }
+    fn thermal_resistance_ca(&self) -> ThermalResistance { self.package.typical_thermal_resistance_ca() }
+    fn thermal_capacitance(&self) -> HeatCapacity { self.package.typical_thermal_capacitance() }
+    fn max_junction_temperature(&self) -> ThermodynamicTemperature {
+        let limit = match self.dielectric {
+            DielectricType::Ceramic | DielectricType::Tantalum => 125.0,
+            DielectricType::Film | DielectricType::Electrolytic => 105.0,
+            DielectricType::Mica => 150.0,
+            DielectricType::Paper => 85.0,
+        };
+        celsius(limit)
+    }
+}
+
+impl TemperatureDependent for Capacitor {
+    /// Typical capacitance drift per dielectric
+    fn temperature_coefficient(&self) -> TemperatureCoefficient {
+        let ppm = match self.dielectric {
+            DielectricType::Ceramic => -750.0,
+            DielectricType::Film => -200.0,
+            DielectricType::Electrolytic => 2500.0,
+            DielectricType::Tantalum => 500.0,
+            DielectricType::Mica => 50.0,
+            DielectricType::Paper => 400.0,
+        };
+        TemperatureCoefficient::new::<ppm_per_kelvin>(ppm)
+    }
+}
+
+impl AgingMechanisms for Capacitor {
+    fn activation_energy(&self) -> Energy {
+        let ev = match self.dielectric {
+            DielectricType::Ceramic => 1.2,
+            DielectricType::Electrolytic => 0.94,
+            DielectricType::Tantalum => 1.1,
+            DielectricType::Film | DielectricType::Paper => 0.6,
+            DielectricType::Mica => 0.8,
+        };
+        Energy::new::<electronvolt>(ev)
+    }
+    /// Voltage acceleration (Prokopowicz-Vaskas for ceramics)
+    fn stress_acceleration_factor(&self, stress_level: f64, reference_level: f64) -> f64 {
+        let exponent = match self.dielectric {
+            DielectricType::Ceramic => 3.0,
+            DielectricType::Electrolytic | DielectricType::Tantalum => 5.0,
+            DielectricType::Film | DielectricType::Mica | DielectricType::Paper => 7.0,
+        };
+        (stress_level / reference_level).powf(exponent)
+    }
+}
+
+impl WearOut for Capacitor {
+    fn weibull_parameters(&self) -> (f64, Time) {
+        match self.dielectric {
+            // Electrolyte dry-out is a clear wear-out mechanism
+            DielectricType::Electrolytic => (2.5, Time::new::<year>(10.0)),
+            DielectricType::Tantalum => (1.2, Time::new::<year>(40.0)),
+            DielectricType::Ceramic | DielectricType::Film | DielectricType::Mica | DielectricType::Paper => (1.3, Time::new::<year>(80.0)),
+        }
+    }
+}
+
+impl MechanicalStress for Capacitor {
+    fn max_acceleration(&self) -> Acceleration { self.package.typical_max_acceleration() }
+    fn resonant_frequency(&self) -> Option<Frequency> { Some(self.package.typical_resonant_frequency()) }
+    fn mechanical_q_factor(&self) -> f64 { self.package.typical_mechanical_q_factor() }
+    fn shock_resistance(&self) -> (Acceleration, Time) { self.package.typical_shock_resistance() }
+}
+
+impl SolderJointReliability for Capacitor {
+    fn cte_mismatch(&self) -> TemperatureCoefficient { self.package.typical_cte_mismatch() }
+    fn joint_geometry_factor(&self) -> f64 { self.package.typical_joint_geometry_factor() }
+    fn neutral_point_distance(&self) -> Length { self.package.typical_neutral_point_distance() }
+    fn joint_height(&self) -> Length { self.package.typical_joint_height() }
+}
+
+impl ElectromagneticCompatibility for Capacitor {
+    fn emission_level(&self, _frequency: Frequency) -> f64 { 0.0 }
+    fn susceptibility_threshold(&self, _frequency: Frequency) -> ElectricField {
+        ElectricField::new::<volt_per_meter>(200.0)
+    }
+    fn shielding_effectiveness(&self) -> f64 { 0.0 }
+}
+
+impl RadiationHardness for Capacitor {
+    /// Polymer and liquid dielectrics degrade first (rad)
+    fn total_dose_tolerance(&self) -> f64 {
+        match self.dielectric {
+            DielectricType::Ceramic => 1e7,
+            DielectricType::Mica => 1e8,
+            DielectricType::Tantalum => 1e6,
+            DielectricType::Electrolytic | DielectricType::Film => 1e5,
+            DielectricType::Paper => 1e4,
+        }
+    }
+    fn seu_cross_section(&self) -> Area { Area::new::<square_centimeter>(0.0) }
+    fn displacement_damage_threshold(&self) -> f64 { 1e14 }
+}
+
+impl AcousticBehavior for Capacitor {
+    /// Class II ceramic (barium titanate) layers each move ~100 pm/V and bigger values
+    /// stack more of them; other dielectrics aren't piezoelectric
+    fn piezoelectric_coefficient(&self) -> f64 {
+        match self.dielectric {
+            DielectricType::Ceramic => {
+                let layers = ((self.capacitance.get::<farad>() * 1e9).sqrt() * 20.0).clamp(10.0, 400.0);
+                100e-12 * layers
+            }
+            DielectricType::Film
+            | DielectricType::Electrolytic
+            | DielectricType::Tantalum
+            | DielectricType::Mica
+            | DielectricType::Paper => 0.0,
+        }
+    }
+    /// Body footprint: 0805 chip or radial disc
+    fn radiating_area(&self) -> Area {
+        let mm2 = match self.package {
+            PackageType::ThroughHole => 20.0,
+            PackageType::SurfaceMount => 2.5,
+            PackageType::ChipOnBoard | PackageType::BareChip => 1.0,
+        };
+        Area::new::<square_millimeter>(mm2)
+    }
+    /// Amplified near the part's own mechanical resonance
+    fn vibration_amplitude(&self, ripple: ElectricPotential, frequency: Frequency) -> Length {
+        let gain = self
+            .resonant_frequency()
+            .map_or(1.0, |resonance| resonant_gain(frequency, resonance, self.mechanical_q_factor()));
+        Length::new::<meter>(self.piezoelectric_coefficient() * ripple.get::<volt>().abs() * gain)
+    }
+    /// Charge `d·p·A` on the part's own capacitance
+    fn microphonic_sensitivity(&self) -> f64 {
+        self.piezoelectric_coefficient() * self.radiating_area().get::<square_meter>() / self.capacitance.get::<farad>()
+    }
+}
+
+impl EnvironmentalDegradation for Capacitor {
+    fn moisture_sensitivity_level(&self) -> u8 {
+        match self.dielectric {
+            DielectricType::Ceramic | DielectricType::Mica => 1,
+            DielectricType::Film | DielectricType::Electrolytic | DielectricType::Tantalum | DielectricType::Paper => {
+                self.package.typical_moisture_sensitivity_level()
+            }
+        }
+    }
+    fn corrosion_resistance(&self) -> CorrosionResistance { self.package.typical_corrosion_resistance() }
+    fn outgassing_properties(&self) -> (Ratio, Ratio) {
+        match self.dielectric {
+            DielectricType::Electrolytic => (Ratio::new::<percent>(1.0), Ratio::new::<percent>(0.1)),
+            _ => self.package.typical_outgassing(),
+        }
+    }
+    /// Halogenated and alcohol cleaners attack electrolytic seals
+    fn chemical_compatibility(&self, material: ChemicalMaterial) -> bool {
+        let seal_attacked = self.dielectric == DielectricType::Electrolytic && material == ChemicalMaterial::Alcohol;
+        !seal_attacked && self.package.typical_chemical_compatibility(material)
+    }
+}
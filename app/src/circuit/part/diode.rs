@@ -19,6 +19,18 @@ use circuit_physics_core::physical::{
     Capacitive, FrequencyDependent, Inductive, NoiseGenerating, PackageType, PowerRated, Resistive, Semiconductor 
 };
 use circuit_physics_core::material_properties::MaterialProperties;
+use circuit_physics_core::thermal::{TemperatureDependent, ThermalBehavior};
+use circuit_physics_core::lifetime::{AgingMechanisms, WearOut};
+use circuit_physics_core::mechanical::{MechanicalStress, SolderJointReliability};
+use circuit_physics_core::electromagnetic::ElectromagneticCompatibility;
+use circuit_physics_core::radiation::RadiationHardness;
+use circuit_physics_core::environment_degradation::{ChemicalMaterial, CorrosionResistance, EnvironmentalDegradation};
+use uom::si::{
+    area::square_centimeter, electric_field::volt_per_meter, electrical_resistivity::ohm_meter, energy::electronvolt,
+    mass_density::kilogram_per_cubic_meter, pressure::pascal, specific_heat_capacity::joule_per_kilogram_kelvin,
+    temperature_coefficient::per_kelvin, thermal_conductivity::watt_per_meter_kelvin, time::year,
+};
+
 
 
 
@@ -83,10 +95,84 @@ impl PowerRated for Diode {
 }
 
 impl MaterialProperties for Diode {
-    fn thermal_conductivity(&self) -> f32 { 148.0 } // Silicon
-    fn electrical_resistivity(&self) -> f32 { 1e5 }
-    fn youngs_modulus(&self) -> f32 { 130e9 }
-    fn poisson_ratio(&self) -> f32 { 0.27 }
-    fn density(&self) -> f32 { 2329.0 }
-    fn specific_heat(&self) -> f32 { 712.0 }
+    fn thermal_conductivity(&self) -> ThermalConductivity { ThermalConductivity::new::<watt_per_meter_kelvin>(148.0) } // Silicon
+    fn electrical_resistivity(&self) -> ElectricalResistivity { ElectricalResistivity::new::<ohm_meter>(1e5) }
+    fn youngs_modulus(&self) -> Pressure { Pressure::new::<pascal>(130e9) }
+    fn poisson_ratio(&self) -> f64 { 0.27 }
+    fn density(&self) -> MassDensity { MassDensity::new::<kilogram_per_cubic_meter>(2329.0) }
+    fn specific_heat(&self) -> SpecificHeatCapacity { SpecificHeatCapacity::new::<joule_per_kilogram_kelvin>(712.0) }
+}
+
+impl ThermalBehavior for Diode {
+    fn thermal_resistance_jc(&self) -> ThermalResistance { self.package.typical_thermal_resistance_jc() }
+    fn thermal_resistance_ca(&self) -> ThermalResistance { self.package.typical_thermal_resistance_ca() }
+    fn thermal_capacitance(&self) -> HeatCapacity { self.package.typical_thermal_capacitance() }
+    fn max_junction_temperature(&self) -> ThermodynamicTemperature {
+        ThermodynamicTemperature::new::<kelvin>(423.15) // 150°C silicon
+    }
+}
+
+impl TemperatureDependent for Diode {
+    /// Forward-voltage drift of a silicon junction, about -2 mV/°C relative to `forward_voltage`
+    fn temperature_coefficient(&self) -> TemperatureCoefficient {
+        TemperatureCoefficient::new::<per_kelvin>(-2e-3 / self.forward_voltage.get::<volt>())
+    }
+}
+
+impl AgingMechanisms for Diode {
+    /// Silicon junction degradation (eV)
+    fn activation_energy(&self) -> Energy { Energy::new::<electronvolt>(0.7) }
+    /// Current-density driven electromigration (Black's equation, n = 2)
+    fn stress_acceleration_factor(&self, stress_level: f64, reference_level: f64) -> f64 {
+        (stress_level / reference_level).powf(2.0)
+    }
+}
+
+impl WearOut for Diode {
+    fn weibull_parameters(&self) -> (f64, Time) { (1.3, Time::new::<year>(40.0)) }
+}
+
+impl MechanicalStress for Diode {
+    fn max_acceleration(&self) -> Acceleration { self.package.typical_max_acceleration() }
+    fn resonant_frequency(&self) -> Option<Frequency> { Some(self.package.typical_resonant_frequency()) }
+    fn mechanical_q_factor(&self) -> f64 { self.package.typical_mechanical_q_factor() }
+    fn shock_resistance(&self) -> (Acceleration, Time) { self.package.typical_shock_resistance() }
+}
+
+impl SolderJointReliability for Diode {
+    fn cte_mismatch(&self) -> TemperatureCoefficient { self.package.typical_cte_mismatch() }
+    fn joint_geometry_factor(&self) -> f64 { self.package.typical_joint_geometry_factor() }
+    fn neutral_point_distance(&self) -> Length { self.package.typical_neutral_point_distance() }
+    fn joint_height(&self) -> Length { self.package.typical_joint_height() }
+}
+
+impl ElectromagneticCompatibility for Diode {
+    /// Reverse-recovery switching noise, rising 20 dB/decade above 1 MHz (dBµV/m)
+    fn emission_level(&self, frequency: Frequency) -> f64 {
+        let recovery = 10.0 * (self.reverse_recovery_time.max(1.0) / 10.0).log10();
+        let decades = (frequency.get::<hertz>() / 1e6).max(1e-3).log10();
+        20.0 + recovery + 20.0 * decades
+    }
+    /// Junctions rectify RF, so the threshold is low (V/m)
+    fn susceptibility_threshold(&self, _frequency: Frequency) -> ElectricField {
+        ElectricField::new::<volt_per_meter>(30.0)
+    }
+    fn shielding_effectiveness(&self) -> f64 { 0.0 }
+}
+
+impl RadiationHardness for Diode {
+    /// Silicon junction leakage grows past ~300 krad
+    fn total_dose_tolerance(&self) -> f64 { 3e5 }
+    /// Single-event burnout under reverse bias
+    fn seu_cross_section(&self) -> Area { Area::new::<square_centimeter>(1e-10) }
+    fn displacement_damage_threshold(&self) -> f64 { 1e11 }
+    /// Burnout needs heavy ions (MeV·cm²/mg)
+    fn seu_threshold_let(&self) -> f64 { 20.0 }
+}
+
+impl EnvironmentalDegradation for Diode {
+    fn moisture_sensitivity_level(&self) -> u8 { self.package.typical_moisture_sensitivity_level() }
+    fn corrosion_resistance(&self) -> CorrosionResistance { self.package.typical_corrosion_resistance() }
+    fn outgassing_properties(&self) -> (Ratio, Ratio) { self.package.typical_outgassing() }
+    fn chemical_compatibility(&self, material: ChemicalMaterial) -> bool { self.package.typical_chemical_compatibility(material) }
 }
@@ -19,6 +19,18 @@ use circuit_physics_core::physical::{
 };
 
 use circuit_physics_core::material_properties::MaterialProperties;
+use circuit_physics_core::thermal::{TemperatureDependent, ThermalBehavior};
+use circuit_physics_core::lifetime::{AgingMechanisms, WearOut};
+use circuit_physics_core::mechanical::{MechanicalStress, SolderJointReliability};
+use circuit_physics_core::electromagnetic::ElectromagneticCompatibility;
+use circuit_physics_core::radiation::RadiationHardness;
+use circuit_physics_core::environment_degradation::{ChemicalMaterial, CorrosionResistance, EnvironmentalDegradation};
+use uom::si::{
+    area::square_centimeter, electric_field::volt_per_meter, electrical_resistivity::ohm_meter, energy::electronvolt,
+    mass_density::kilogram_per_cubic_meter, pressure::pascal, specific_heat_capacity::joule_per_kilogram_kelvin,
+    temperature_coefficient::ppm_per_kelvin, thermal_conductivity::watt_per_meter_kelvin, time::year,
+};
+
 
 
 
@@ -80,10 +92,95 @@ impl FrequencyDependent for Inductor {
 }
 
 impl MaterialProperties for Inductor {
-    fn thermal_conductivity(&self) -> f32 { 50.0 } // Copper wire + ferrite
-    fn electrical_resistivity(&self) -> f32 { self.dc_resistance.get::<ohm>() as f32 }
-    fn youngs_modulus(&self) -> f32 { 100e9 }
-    fn poisson_ratio(&self) -> f32 { 0.35 }
-    fn density(&self) -> f32 { 5000.0 }
-    fn specific_heat(&self) -> f32 { 500.0 }
+    fn thermal_conductivity(&self) -> ThermalConductivity { ThermalConductivity::new::<watt_per_meter_kelvin>(50.0) } // Copper wire + ferrite
+    fn electrical_resistivity(&self) -> ElectricalResistivity { ElectricalResistivity::new::<ohm_meter>(1.68e-8) } // Copper winding
+    fn youngs_modulus(&self) -> Pressure { Pressure::new::<pascal>(100e9) }
+    fn poisson_ratio(&self) -> f64 { 0.35 }
+    fn density(&self) -> MassDensity { MassDensity::new::<kilogram_per_cubic_meter>(5000.0) }
+    fn specific_heat(&self) -> SpecificHeatCapacity { SpecificHeatCapacity::new::<joule_per_kilogram_kelvin>(500.0) }
+}
+
+impl ThermalBehavior for Inductor {
+    fn thermal_resistance_jc(&self) -> ThermalResistance { self.package.typical_thermal_resistance_jc() }
+    fn thermal_resistance_ca(&self) -> ThermalResistance { self.package.typical_thermal_resistance_ca() }
+    fn thermal_capacitance(&self) -> HeatCapacity { self.package.typical_thermal_capacitance() }
+    fn max_junction_temperature(&self) -> ThermodynamicTemperature {
+        ThermodynamicTemperature::new::<kelvin>(398.15) // 125°C
+    }
+}
+
+impl TemperatureDependent for Inductor {
+    /// Copper winding resistance drift
+    fn temperature_coefficient(&self) -> TemperatureCoefficient { TemperatureCoefficient::new::<ppm_per_kelvin>(3930.0) }
+}
+
+impl AgingMechanisms for Inductor {
+    /// Winding insulation breakdown (eV)
+    fn activation_energy(&self) -> Energy { Energy::new::<electronvolt>(0.7) }
+    /// Resistive heating grows with the square of current
+    fn stress_acceleration_factor(&self, stress_level: f64, reference_level: f64) -> f64 {
+        (stress_level / reference_level).powf(2.0)
+    }
+}
+
+impl WearOut for Inductor {
+    fn weibull_parameters(&self) -> (f64, Time) { (2.0, Time::new::<year>(60.0)) }
+}
+
+impl MechanicalStress for Inductor {
+    fn max_acceleration(&self) -> Acceleration { self.package.typical_max_acceleration() * 0.5 }
+    fn resonant_frequency(&self) -> Option<Frequency> { Some(self.package.typical_resonant_frequency() * 0.5) }
+    fn mechanical_q_factor(&self) -> f64 { self.package.typical_mechanical_q_factor() }
+    fn shock_resistance(&self) -> (Acceleration, Time) { self.package.typical_shock_resistance() }
+}
+
+impl SolderJointReliability for Inductor {
+    fn cte_mismatch(&self) -> TemperatureCoefficient { self.package.typical_cte_mismatch() }
+    fn joint_geometry_factor(&self) -> f64 { self.package.typical_joint_geometry_factor() }
+    fn neutral_point_distance(&self) -> Length { self.package.typical_neutral_point_distance() }
+    fn joint_height(&self) -> Length { self.package.typical_joint_height() }
+}
+
+impl ElectromagneticCompatibility for Inductor {
+    /// Stray field of the winding at 1 MHz, rising 20 dB/decade, less shielding (dBµV/m)
+    fn emission_level(&self, frequency: Frequency) -> f64 {
+        let at_1mhz = match self.core_material {
+            CoreMaterial::Air => 40.0,
+            CoreMaterial::Iron | CoreMaterial::Laminated => 35.0,
+            CoreMaterial::Ferrite => 30.0,
+            CoreMaterial::Powdered => 25.0,
+        };
+        let decades = (frequency.get::<hertz>() / 1e6).max(1e-3).log10();
+        at_1mhz + 20.0 * decades - self.shielding_effectiveness()
+    }
+    fn susceptibility_threshold(&self, _frequency: Frequency) -> ElectricField {
+        ElectricField::new::<volt_per_meter>(100.0)
+    }
+    /// Closed magnetic paths keep the field in the core (dB)
+    fn shielding_effectiveness(&self) -> f64 {
+        match self.core_material {
+            CoreMaterial::Ferrite => 20.0,
+            CoreMaterial::Powdered => 15.0,
+            CoreMaterial::Air | CoreMaterial::Iron | CoreMaterial::Laminated => 0.0,
+        }
+    }
+}
+
+impl RadiationHardness for Inductor {
+    fn total_dose_tolerance(&self) -> f64 { 1e7 }
+    fn seu_cross_section(&self) -> Area { Area::new::<square_centimeter>(0.0) }
+    fn displacement_damage_threshold(&self) -> f64 { 1e13 }
+}
+
+impl EnvironmentalDegradation for Inductor {
+    fn moisture_sensitivity_level(&self) -> u8 { 1 }
+    /// Bare iron cores rust
+    fn corrosion_resistance(&self) -> CorrosionResistance {
+        match self.core_material {
+            CoreMaterial::Iron | CoreMaterial::Laminated => CorrosionResistance::Fair,
+            CoreMaterial::Air | CoreMaterial::Ferrite | CoreMaterial::Powdered => self.package.typical_corrosion_resistance(),
+        }
+    }
+    fn outgassing_properties(&self) -> (Ratio, Ratio) { self.package.typical_outgassing() }
+    fn chemical_compatibility(&self, material: ChemicalMaterial) -> bool { self.package.typical_chemical_compatibility(material) }
 }
@@ -8,7 +8,9 @@ pub mod capacitor;
 
 use block3d_core::block::Block3DLike;
 use block3d_core::face::Face;
-use circuit_physics_core::physical::PackageType;
+use block3d_core::orientation::Orientation;
+use block3d_core::symmetry::SymmetryClass;
+use std::collections::HashSet;
 use serde::{Serialize, Deserialize};
 use bevy::prelude::*;
 use strum::EnumIter;
@@ -19,15 +21,24 @@ use uom::si::{
     electrical_resistance::ohm,
     capacitance::farad,
     inductance::henry,
-    frequency::hertz,
     power::watt,
     thermodynamic_temperature::kelvin,
+    area::square_meter,
+    length::meter,
 };
 use circuit_physics_core::{
     physical::{
-        Resistive, Capacitive, Inductive, FrequencyDependent, PowerRated, NoiseGenerating, Semiconductor 
+        Resistive, Capacitive, Inductive, FrequencyDependent, PowerRated, NoiseGenerating, Semiconductor,
     },
-    material_properties::MaterialProperties
+    material_properties::MaterialProperties,
+    thermal::{TemperatureDependent, ThermalBehavior},
+    lifetime::{AgingMechanisms, WearOut},
+    mechanical::{MechanicalStress, SolderJointReliability},
+    electromagnetic::ElectromagneticCompatibility,
+    radiation::RadiationHardness,
+    environment_degradation::{ChemicalMaterial, CorrosionResistance, EnvironmentalDegradation},
+    acoustic::AcousticBehavior,
+    overrides::{Overridable, ParameterOverrides, WithOverrides},
 };
 use interaction::drag::two_d::Draggable2d;
 
@@ -51,6 +62,14 @@ use resistor::Resistor;
 
 
 
+/// Datasheet or measured values that replace a part's physics defaults.
+#[derive(Component, Debug, Clone, Default, PartialEq, Serialize, Deserialize, Deref, DerefMut)]
+pub struct PartOverrides(pub ParameterOverrides);
+
+/// Where a part sits in the 3D layout: the minimum grid cell of its block.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct LayoutPlacement(pub IVec3);
+
 // ============================================================================
 // UNIFIED COMPONENT ENUM FOR WFC SOLVER
 // ============================================================================
@@ -109,10 +128,24 @@ impl Block3DLike for Part {
             Part::Diode(_) => 0.8,
         }
     }
+
+    /// The rotations that map the part's faces onto themselves, so polarity is
+    /// whatever the faces say it is
+    fn symmetry(&self) -> SymmetryClass {
+        let size = self.size();
+        let faces: HashSet<Face> = self.faces().collect();
+        let symmetric: Vec<Orientation> = Orientation::rotations()
+            .filter(|&rotation| {
+                rotation.apply_size(size) == size
+                    && self.faces().all(|face| faces.contains(&face.oriented(rotation, size)))
+            })
+            .collect();
+        SymmetryClass::generated_by(&symmetric)
+    }
 }
 
 impl MaterialProperties for Part {
-    fn thermal_conductivity(&self) -> f32 {
+    fn thermal_conductivity(&self) -> ThermalConductivity {
         match self {
             Part::Resistor(r) => r.thermal_conductivity(),
             Part::Capacitor(c) => c.thermal_conductivity(),
@@ -121,7 +154,7 @@ impl MaterialProperties for Part {
         }
     }
     
-    fn electrical_resistivity(&self) -> f32 {
+    fn electrical_resistivity(&self) -> ElectricalResistivity {
         match self {
             Part::Resistor(r) => r.electrical_resistivity(),
             Part::Capacitor(c) => c.electrical_resistivity(),
@@ -130,7 +163,7 @@ impl MaterialProperties for Part {
         }
     }
     
-    fn youngs_modulus(&self) -> f32 {
+    fn youngs_modulus(&self) -> Pressure {
         match self {
             Part::Resistor(r) => r.youngs_modulus(),
             Part::Capacitor(c) => c.youngs_modulus(),
@@ -139,7 +172,7 @@ impl MaterialProperties for Part {
         }
     }
     
-    fn poisson_ratio(&self) -> f32 {
+    fn poisson_ratio(&self) -> f64 {
         match self {
             Part::Resistor(r) => r.poisson_ratio(),
             Part::Capacitor(c) => c.poisson_ratio(),
@@ -148,7 +181,7 @@ impl MaterialProperties for Part {
         }
     }
     
-    fn density(&self) -> f32 {
+    fn density(&self) -> MassDensity {
         match self {
             Part::Resistor(r) => r.density(),
             Part::Capacitor(c) => c.density(),
@@ -157,7 +190,7 @@ impl MaterialProperties for Part {
         }
     }
     
-    fn specific_heat(&self) -> f32 {
+    fn specific_heat(&self) -> SpecificHeatCapacity {
         match self {
             Part::Resistor(r) => r.specific_heat(),
             Part::Capacitor(c) => c.specific_heat(),
@@ -167,6 +200,298 @@ impl MaterialProperties for Part {
     }
 }
 
+impl ThermalBehavior for Part {
+    fn thermal_resistance_jc(&self) -> ThermalResistance {
+        match self {
+            Part::Resistor(r) => r.thermal_resistance_jc(),
+            Part::Capacitor(c) => c.thermal_resistance_jc(),
+            Part::Inductor(l) => l.thermal_resistance_jc(),
+            Part::Diode(d) => d.thermal_resistance_jc(),
+        }
+    }
+
+    fn thermal_resistance_ca(&self) -> ThermalResistance {
+        match self {
+            Part::Resistor(r) => r.thermal_resistance_ca(),
+            Part::Capacitor(c) => c.thermal_resistance_ca(),
+            Part::Inductor(l) => l.thermal_resistance_ca(),
+            Part::Diode(d) => d.thermal_resistance_ca(),
+        }
+    }
+
+    fn thermal_capacitance(&self) -> HeatCapacity {
+        match self {
+            Part::Resistor(r) => r.thermal_capacitance(),
+            Part::Capacitor(c) => c.thermal_capacitance(),
+            Part::Inductor(l) => l.thermal_capacitance(),
+            Part::Diode(d) => d.thermal_capacitance(),
+        }
+    }
+
+    fn max_junction_temperature(&self) -> ThermodynamicTemperature {
+        match self {
+            Part::Resistor(r) => r.max_junction_temperature(),
+            Part::Capacitor(c) => c.max_junction_temperature(),
+            Part::Inductor(l) => l.max_junction_temperature(),
+            Part::Diode(d) => d.max_junction_temperature(),
+        }
+    }
+}
+
+impl TemperatureDependent for Part {
+    fn temperature_coefficient(&self) -> TemperatureCoefficient {
+        match self {
+            Part::Resistor(r) => TemperatureDependent::temperature_coefficient(r),
+            Part::Capacitor(c) => c.temperature_coefficient(),
+            Part::Inductor(l) => l.temperature_coefficient(),
+            Part::Diode(d) => d.temperature_coefficient(),
+        }
+    }
+}
+
+impl AgingMechanisms for Part {
+    fn activation_energy(&self) -> Energy {
+        match self {
+            Part::Resistor(r) => r.activation_energy(),
+            Part::Capacitor(c) => c.activation_energy(),
+            Part::Inductor(l) => l.activation_energy(),
+            Part::Diode(d) => d.activation_energy(),
+        }
+    }
+
+    fn stress_acceleration_factor(&self, stress_level: f64, reference_level: f64) -> f64 {
+        match self {
+            Part::Resistor(r) => r.stress_acceleration_factor(stress_level, reference_level),
+            Part::Capacitor(c) => c.stress_acceleration_factor(stress_level, reference_level),
+            Part::Inductor(l) => l.stress_acceleration_factor(stress_level, reference_level),
+            Part::Diode(d) => d.stress_acceleration_factor(stress_level, reference_level),
+        }
+    }
+}
+
+impl WearOut for Part {
+    fn weibull_parameters(&self) -> (f64, Time) {
+        match self {
+            Part::Resistor(r) => r.weibull_parameters(),
+            Part::Capacitor(c) => c.weibull_parameters(),
+            Part::Inductor(l) => l.weibull_parameters(),
+            Part::Diode(d) => d.weibull_parameters(),
+        }
+    }
+}
+
+impl MechanicalStress for Part {
+    fn max_acceleration(&self) -> Acceleration {
+        match self {
+            Part::Resistor(r) => r.max_acceleration(),
+            Part::Capacitor(c) => c.max_acceleration(),
+            Part::Inductor(l) => l.max_acceleration(),
+            Part::Diode(d) => d.max_acceleration(),
+        }
+    }
+
+    fn resonant_frequency(&self) -> Option<Frequency> {
+        match self {
+            Part::Resistor(r) => r.resonant_frequency(),
+            Part::Capacitor(c) => c.resonant_frequency(),
+            Part::Inductor(l) => l.resonant_frequency(),
+            Part::Diode(d) => d.resonant_frequency(),
+        }
+    }
+
+    fn mechanical_q_factor(&self) -> f64 {
+        match self {
+            Part::Resistor(r) => r.mechanical_q_factor(),
+            Part::Capacitor(c) => c.mechanical_q_factor(),
+            Part::Inductor(l) => l.mechanical_q_factor(),
+            Part::Diode(d) => d.mechanical_q_factor(),
+        }
+    }
+
+    fn shock_resistance(&self) -> (Acceleration, Time) {
+        match self {
+            Part::Resistor(r) => r.shock_resistance(),
+            Part::Capacitor(c) => c.shock_resistance(),
+            Part::Inductor(l) => l.shock_resistance(),
+            Part::Diode(d) => d.shock_resistance(),
+        }
+    }
+}
+
+impl SolderJointReliability for Part {
+    fn cte_mismatch(&self) -> TemperatureCoefficient {
+        match self {
+            Part::Resistor(r) => r.cte_mismatch(),
+            Part::Capacitor(c) => c.cte_mismatch(),
+            Part::Inductor(l) => l.cte_mismatch(),
+            Part::Diode(d) => d.cte_mismatch(),
+        }
+    }
+
+    fn joint_geometry_factor(&self) -> f64 {
+        match self {
+            Part::Resistor(r) => r.joint_geometry_factor(),
+            Part::Capacitor(c) => c.joint_geometry_factor(),
+            Part::Inductor(l) => l.joint_geometry_factor(),
+            Part::Diode(d) => d.joint_geometry_factor(),
+        }
+    }
+
+    fn neutral_point_distance(&self) -> Length {
+        match self {
+            Part::Resistor(r) => r.neutral_point_distance(),
+            Part::Capacitor(c) => c.neutral_point_distance(),
+            Part::Inductor(l) => l.neutral_point_distance(),
+            Part::Diode(d) => d.neutral_point_distance(),
+        }
+    }
+
+    fn joint_height(&self) -> Length {
+        match self {
+            Part::Resistor(r) => r.joint_height(),
+            Part::Capacitor(c) => c.joint_height(),
+            Part::Inductor(l) => l.joint_height(),
+            Part::Diode(d) => d.joint_height(),
+        }
+    }
+}
+
+impl ElectromagneticCompatibility for Part {
+    fn emission_level(&self, frequency: Frequency) -> f64 {
+        match self {
+            Part::Resistor(r) => r.emission_level(frequency),
+            Part::Capacitor(c) => c.emission_level(frequency),
+            Part::Inductor(l) => l.emission_level(frequency),
+            Part::Diode(d) => d.emission_level(frequency),
+        }
+    }
+
+    fn susceptibility_threshold(&self, frequency: Frequency) -> ElectricField {
+        match self {
+            Part::Resistor(r) => r.susceptibility_threshold(frequency),
+            Part::Capacitor(c) => c.susceptibility_threshold(frequency),
+            Part::Inductor(l) => l.susceptibility_threshold(frequency),
+            Part::Diode(d) => d.susceptibility_threshold(frequency),
+        }
+    }
+
+    fn shielding_effectiveness(&self) -> f64 {
+        match self {
+            Part::Resistor(r) => r.shielding_effectiveness(),
+            Part::Capacitor(c) => c.shielding_effectiveness(),
+            Part::Inductor(l) => l.shielding_effectiveness(),
+            Part::Diode(d) => d.shielding_effectiveness(),
+        }
+    }
+}
+
+impl RadiationHardness for Part {
+    fn total_dose_tolerance(&self) -> f64 {
+        match self {
+            Part::Resistor(r) => r.total_dose_tolerance(),
+            Part::Capacitor(c) => c.total_dose_tolerance(),
+            Part::Inductor(l) => l.total_dose_tolerance(),
+            Part::Diode(d) => d.total_dose_tolerance(),
+        }
+    }
+
+    fn seu_cross_section(&self) -> Area {
+        match self {
+            Part::Resistor(r) => r.seu_cross_section(),
+            Part::Capacitor(c) => c.seu_cross_section(),
+            Part::Inductor(l) => l.seu_cross_section(),
+            Part::Diode(d) => d.seu_cross_section(),
+        }
+    }
+
+    fn displacement_damage_threshold(&self) -> f64 {
+        match self {
+            Part::Resistor(r) => r.displacement_damage_threshold(),
+            Part::Capacitor(c) => c.displacement_damage_threshold(),
+            Part::Inductor(l) => l.displacement_damage_threshold(),
+            Part::Diode(d) => d.displacement_damage_threshold(),
+        }
+    }
+
+    fn seu_threshold_let(&self) -> f64 {
+        match self {
+            Part::Resistor(r) => r.seu_threshold_let(),
+            Part::Capacitor(c) => c.seu_threshold_let(),
+            Part::Inductor(l) => l.seu_threshold_let(),
+            Part::Diode(d) => d.seu_threshold_let(),
+        }
+    }
+}
+
+impl EnvironmentalDegradation for Part {
+    fn moisture_sensitivity_level(&self) -> u8 {
+        match self {
+            Part::Resistor(r) => r.moisture_sensitivity_level(),
+            Part::Capacitor(c) => c.moisture_sensitivity_level(),
+            Part::Inductor(l) => l.moisture_sensitivity_level(),
+            Part::Diode(d) => d.moisture_sensitivity_level(),
+        }
+    }
+
+    fn corrosion_resistance(&self) -> CorrosionResistance {
+        match self {
+            Part::Resistor(r) => r.corrosion_resistance(),
+            Part::Capacitor(c) => c.corrosion_resistance(),
+            Part::Inductor(l) => l.corrosion_resistance(),
+            Part::Diode(d) => d.corrosion_resistance(),
+        }
+    }
+
+    fn outgassing_properties(&self) -> (Ratio, Ratio) {
+        match self {
+            Part::Resistor(r) => r.outgassing_properties(),
+            Part::Capacitor(c) => c.outgassing_properties(),
+            Part::Inductor(l) => l.outgassing_properties(),
+            Part::Diode(d) => d.outgassing_properties(),
+        }
+    }
+
+    fn chemical_compatibility(&self, material: ChemicalMaterial) -> bool {
+        match self {
+            Part::Resistor(r) => r.chemical_compatibility(material),
+            Part::Capacitor(c) => c.chemical_compatibility(material),
+            Part::Inductor(l) => l.chemical_compatibility(material),
+            Part::Diode(d) => d.chemical_compatibility(material),
+        }
+    }
+}
+
+/// Only capacitors are modelled acoustically; the other parts are inert.
+impl AcousticBehavior for Part {
+    fn piezoelectric_coefficient(&self) -> f64 {
+        match self {
+            Part::Capacitor(c) => c.piezoelectric_coefficient(),
+            Part::Resistor(_) | Part::Inductor(_) | Part::Diode(_) => 0.0,
+        }
+    }
+
+    fn radiating_area(&self) -> Area {
+        match self {
+            Part::Capacitor(c) => c.radiating_area(),
+            Part::Resistor(_) | Part::Inductor(_) | Part::Diode(_) => Area::new::<square_meter>(0.0),
+        }
+    }
+
+    fn vibration_amplitude(&self, ripple: ElectricPotential, frequency: Frequency) -> Length {
+        match self {
+            Part::Capacitor(c) => c.vibration_amplitude(ripple, frequency),
+            Part::Resistor(_) | Part::Inductor(_) | Part::Diode(_) => Length::new::<meter>(0.0),
+        }
+    }
+
+    fn microphonic_sensitivity(&self) -> f64 {
+        match self {
+            Part::Capacitor(c) => c.microphonic_sensitivity(),
+            Part::Resistor(_) | Part::Inductor(_) | Part::Diode(_) => 0.0,
+        }
+    }
+}
+
 // Convenience constructors for the enum
 impl Part {
     pub fn resistor() -> Self {
@@ -186,6 +511,30 @@ impl Part {
     pub fn diode() -> Self {
         Self::Diode(Diode::default())
     }
+
+    /// This part as seen through `overrides`, for physics queries.
+    pub fn physics<'a>(&'a self, overrides: &'a ParameterOverrides) -> WithOverrides<'a, Part> {
+        self.with_overrides(overrides)
+    }
+
+    /// Copy of this part with its temperature-sensitive parameter drifted to `temperature`
+    /// (resistance, winding resistance, capacitance or forward voltage).
+    pub fn at_temperature(&self, temperature: ThermodynamicTemperature) -> Self {
+        self.with_drift(self.parameter_drift(temperature))
+    }
+
+    /// Copy of this part with its temperature-sensitive parameter scaled by `1 + drift`.
+    pub fn with_drift(&self, drift: f64) -> Self {
+        let scale = 1.0 + drift;
+        let mut part = self.clone();
+        match &mut part {
+            Part::Resistor(r) => r.resistance *= scale,
+            Part::Capacitor(c) => c.capacitance *= scale,
+            Part::Inductor(l) => l.dc_resistance *= scale,
+            Part::Diode(d) => d.forward_voltage *= scale,
+        }
+        part
+    }
 }
 
 // Manual Hash implementations for all component structs, ignoring floating point fields
@@ -226,11 +575,51 @@ impl std::hash::Hash for Part {
             Part::Diode(d) => d.hash(state),
         }
     }
-} 
-
-
-
+}
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use block3d_core::connection::{ConnectorInterface, OrientedInterface};
+    use block3d_core::face::Direction;
+    use block3d_core::orientation::Axis;
+    use uom::si::thermal_resistance::kelvin_per_watt;
 
+    fn end(direction: Direction, interface: ConnectorInterface, orientation: Orientation) -> Face {
+        Face::new(direction, OrientedInterface { interface, orientation })
+    }
 
+    #[test]
+    fn symmetry_follows_the_faces() {
+        let half_turn = Orientation::about(Axis::Y, 2);
+        let ends = vec![
+            end(Direction::PosX, ConnectorInterface::MetalPad, Orientation::IDENTITY),
+            end(Direction::NegX, ConnectorInterface::MetalPad, half_turn),
+        ];
+        let resistor = Part::Resistor(Resistor { faces: ends.clone(), ..Resistor::default() });
+        assert_eq!(resistor.symmetry(), SymmetryClass::about(Axis::Y, 2));
+
+        let mut polarised = ends;
+        polarised[1] = end(Direction::NegX, ConnectorInterface::ThermalPad, half_turn);
+        let capacitor = Part::Capacitor(Capacitor { faces: polarised, ..Capacitor::default() });
+        assert_eq!(capacitor.symmetry(), SymmetryClass::NONE);
+    }
 
+    #[test]
+    fn overrides_replace_defaults_until_cleared() {
+        let part = Part::resistor();
+        let mut overrides = ParameterOverrides {
+            thermal_resistance_jc: Some(ThermalResistance::new::<kelvin_per_watt>(12.5)),
+            ..ParameterOverrides::default()
+        };
+
+        let physics = part.physics(&overrides);
+        assert_eq!(physics.thermal_resistance_jc().get::<kelvin_per_watt>(), 12.5);
+        // Unset fields fall through to the part
+        assert_eq!(physics.max_junction_temperature(), part.max_junction_temperature());
+
+        overrides.thermal_resistance_jc = None;
+        assert!(overrides.is_empty());
+        assert_eq!(part.physics(&overrides).thermal_resistance_jc(), part.thermal_resistance_jc());
+    }
+}
@@ -18,6 +18,18 @@ use circuit_physics_core::physical::{
     Capacitive, FrequencyDependent, Inductive, NoiseGenerating, PackageType, PowerRated, Resistive, Semiconductor 
 };
 use circuit_physics_core::material_properties::MaterialProperties;
+use circuit_physics_core::thermal::{TemperatureDependent, ThermalBehavior};
+use circuit_physics_core::lifetime::{AgingMechanisms, WearOut};
+use circuit_physics_core::mechanical::{MechanicalStress, SolderJointReliability};
+use circuit_physics_core::electromagnetic::ElectromagneticCompatibility;
+use circuit_physics_core::radiation::RadiationHardness;
+use circuit_physics_core::environment_degradation::{ChemicalMaterial, CorrosionResistance, EnvironmentalDegradation};
+use uom::si::{
+    area::square_centimeter, electric_field::volt_per_meter, electrical_resistivity::ohm_meter, energy::electronvolt,
+    mass_density::kilogram_per_cubic_meter, pressure::pascal, specific_heat_capacity::joule_per_kilogram_kelvin,
+    temperature_coefficient::ppm_per_kelvin, thermal_conductivity::watt_per_meter_kelvin, time::year,
+};
+
 
 
 
@@ -101,12 +113,78 @@ impl NoiseGenerating for Resistor {
 }
 
 impl MaterialProperties for Resistor {
-    fn thermal_conductivity(&self) -> f32 { 1.0 } // Generic resistor
-    fn electrical_resistivity(&self) -> f32 { self.resistance.get::<ohm>() as f32 }
-    fn youngs_modulus(&self) -> f32 { 1e9 }
-    fn poisson_ratio(&self) -> f32 { 0.3 }
-    fn density(&self) -> f32 { 2000.0 }
-    fn specific_heat(&self) -> f32 { 1000.0 }
+    fn thermal_conductivity(&self) -> ThermalConductivity { ThermalConductivity::new::<watt_per_meter_kelvin>(1.0) } // Generic resistor
+    fn electrical_resistivity(&self) -> ElectricalResistivity { ElectricalResistivity::new::<ohm_meter>(1.1e-6) } // Nichrome film
+    fn youngs_modulus(&self) -> Pressure { Pressure::new::<pascal>(1e9) }
+    fn poisson_ratio(&self) -> f64 { 0.3 }
+    fn density(&self) -> MassDensity { MassDensity::new::<kilogram_per_cubic_meter>(2000.0) }
+    fn specific_heat(&self) -> SpecificHeatCapacity { SpecificHeatCapacity::new::<joule_per_kilogram_kelvin>(1000.0) }
+}
+
+impl ThermalBehavior for Resistor {
+    fn thermal_resistance_jc(&self) -> ThermalResistance { self.package.typical_thermal_resistance_jc() }
+    fn thermal_resistance_ca(&self) -> ThermalResistance { self.package.typical_thermal_resistance_ca() }
+    fn thermal_capacitance(&self) -> HeatCapacity { self.package.typical_thermal_capacitance() }
+    fn max_junction_temperature(&self) -> ThermodynamicTemperature {
+        ThermodynamicTemperature::new::<kelvin>(428.15) // 155°C film resistor
+    }
+}
+
+impl TemperatureDependent for Resistor {
+    fn temperature_coefficient(&self) -> TemperatureCoefficient {
+        TemperatureCoefficient::new::<ppm_per_kelvin>(self.temperature_coefficient)
+    }
+}
+
+impl AgingMechanisms for Resistor {
+    /// Film resistor drift (eV)
+    fn activation_energy(&self) -> Energy { Energy::new::<electronvolt>(0.5) }
+    /// Power-law acceleration with dissipated power
+    fn stress_acceleration_factor(&self, stress_level: f64, reference_level: f64) -> f64 {
+        (stress_level / reference_level).powf(2.0)
+    }
+}
+
+impl WearOut for Resistor {
+    fn weibull_parameters(&self) -> (f64, Time) { (1.5, Time::new::<year>(50.0)) }
+}
+
+impl MechanicalStress for Resistor {
+    fn max_acceleration(&self) -> Acceleration { self.package.typical_max_acceleration() }
+    fn resonant_frequency(&self) -> Option<Frequency> { Some(self.package.typical_resonant_frequency()) }
+    fn mechanical_q_factor(&self) -> f64 { self.package.typical_mechanical_q_factor() }
+    fn shock_resistance(&self) -> (Acceleration, Time) { self.package.typical_shock_resistance() }
+}
+
+impl SolderJointReliability for Resistor {
+    fn cte_mismatch(&self) -> TemperatureCoefficient { self.package.typical_cte_mismatch() }
+    fn joint_geometry_factor(&self) -> f64 { self.package.typical_joint_geometry_factor() }
+    fn neutral_point_distance(&self) -> Length { self.package.typical_neutral_point_distance() }
+    fn joint_height(&self) -> Length { self.package.typical_joint_height() }
+}
+
+impl ElectromagneticCompatibility for Resistor {
+    /// Passive, non-switching: no meaningful emission (dBµV/m)
+    fn emission_level(&self, _frequency: Frequency) -> f64 { 0.0 }
+    fn susceptibility_threshold(&self, _frequency: Frequency) -> ElectricField {
+        ElectricField::new::<volt_per_meter>(200.0)
+    }
+    fn shielding_effectiveness(&self) -> f64 { 0.0 }
+}
+
+impl RadiationHardness for Resistor {
+    /// Film resistors are essentially immune to ionizing dose (rad)
+    fn total_dose_tolerance(&self) -> f64 { 1e6 }
+    fn seu_cross_section(&self) -> Area { Area::new::<square_centimeter>(0.0) }
+    fn displacement_damage_threshold(&self) -> f64 { 1e14 }
+}
+
+impl EnvironmentalDegradation for Resistor {
+    /// Ceramic body, not moisture sensitive
+    fn moisture_sensitivity_level(&self) -> u8 { 1 }
+    fn corrosion_resistance(&self) -> CorrosionResistance { self.package.typical_corrosion_resistance() }
+    fn outgassing_properties(&self) -> (Ratio, Ratio) { self.package.typical_outgassing() }
+    fn chemical_compatibility(&self, material: ChemicalMaterial) -> bool { self.package.typical_chemical_compatibility(material) }
 }
 
 // Manual Hash implementation ignoring floating point fields
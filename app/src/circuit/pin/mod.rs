@@ -44,7 +44,7 @@ impl Pin {
 // ============================================================================
 
 /// Electrical role of a pin. Useful for DRC and auto-wiring.
-#[derive(Component, Debug, Clone, Copy, Serialize, Deserialize, Reflect, Default)]
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Reflect, Default)]
 #[reflect(Component, Serialize, Deserialize)]
 pub enum PinRole {
     Input,
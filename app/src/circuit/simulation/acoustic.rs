@@ -0,0 +1,200 @@
+//! Singing-capacitor risk from simulated ripple (see `docs/09_acoustic_coupling.md`).
+//!
+//! The ripple across each capacitor is taken from the transient samples in
+//! `SimulationResults`: its peak amplitude and, from mean crossings, its
+//! frequency. Piezoelectric parts turn it into surface motion and sound through
+//! `AcousticBehavior`; the board they are soldered to radiates far more than the
+//! part alone, which `AcousticConfig::board_gain` accounts for.
+
+use bevy::prelude::Entity;
+
+use circuit_physics_core::acoustic::{a_weighting, sound_pressure_level, AcousticBehavior};
+use uom::si::f64::{ElectricPotential, Frequency, Length};
+use uom::si::{electric_potential::volt, frequency::hertz, length::meter};
+
+use crate::circuit::part::Part;
+
+use super::netlist::CircuitSnapshot;
+use super::OperatingPoint;
+
+#[derive(Debug, Clone)]
+pub struct AcousticConfig {
+    /// Listening distance from the board.
+    pub distance: Length,
+    /// Sound pressure multiplier for the board acting as a soundboard.
+    pub board_gain: f64,
+    /// A-weighted level (dB(A)) at which a part can be heard in a quiet room.
+    pub audible_level: f64,
+    /// A-weighted level (dB(A)) at which the noise is likely to draw complaints.
+    pub loud_level: f64,
+}
+
+impl Default for AcousticConfig {
+    fn default() -> Self {
+        Self { distance: Length::new::<meter>(0.1), board_gain: 30.0, audible_level: 20.0, loud_level: 35.0 }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SingingRisk {
+    None,
+    Audible,
+    Loud,
+}
+
+#[derive(Debug, Clone)]
+pub struct CapacitorAcoustics {
+    pub entity: Entity,
+    /// Peak ripple across the part.
+    pub ripple: ElectricPotential,
+    /// Ripple frequency, if the samples show a periodic ripple.
+    pub frequency: Option<Frequency>,
+    /// A-weighted sound level at `AcousticConfig::distance`; `-∞` when silent.
+    pub level: f64,
+    /// Open-circuit pickup (V/Pa) for microphonic noise in sensitive nodes.
+    pub microphonic_sensitivity: f64,
+    pub risk: SingingRisk,
+}
+
+#[derive(Debug, Clone)]
+pub struct AcousticReport {
+    /// Capacitors only, loudest first.
+    pub parts: Vec<CapacitorAcoustics>,
+}
+
+impl AcousticReport {
+    pub fn analyse(snapshot: &CircuitSnapshot, samples: &[(f64, OperatingPoint)], config: &AcousticConfig) -> Self {
+        let mut parts: Vec<_> = snapshot
+            .parts
+            .iter()
+            .filter_map(|terminals| {
+                let physics = terminals.physics();
+                let Part::Capacitor(_) = physics.component else { return None; };
+                let across: Vec<(f64, f64)> = samples
+                    .iter()
+                    .map(|(time, point)| {
+                        let [a, b] = terminals.nets.map(|net| net.and_then(|net| point.net_voltage(net)).unwrap_or(0.0));
+                        (*time, a - b)
+                    })
+                    .collect();
+                let (amplitude, frequency) = ripple(&across).map_or((0.0, None), |(amplitude, hz)| (amplitude, Some(hz)));
+                let ripple = ElectricPotential::new::<volt>(amplitude);
+                let frequency = frequency.map(Frequency::new::<hertz>);
+                let (level, risk) = frequency.map_or((f64::NEG_INFINITY, SingingRisk::None), |f| assess(&physics, ripple, f, config));
+                Some(CapacitorAcoustics {
+                    entity: terminals.entity,
+                    ripple,
+                    frequency,
+                    level,
+                    microphonic_sensitivity: physics.microphonic_sensitivity(),
+                    risk,
+                })
+            })
+            .collect();
+        parts.sort_by(|a, b| b.level.total_cmp(&a.level));
+        Self { parts }
+    }
+
+    /// Capacitors loud enough to hear.
+    pub fn singing(&self) -> impl Iterator<Item = &CapacitorAcoustics> {
+        self.parts.iter().filter(|p| p.risk > SingingRisk::None)
+    }
+}
+
+/// A-weighted level and risk of `capacitor` under a sinusoidal ripple.
+pub fn assess(capacitor: &impl AcousticBehavior, ripple: ElectricPotential, frequency: Frequency, config: &AcousticConfig) -> (f64, SingingRisk) {
+    if !capacitor.is_piezoelectric() || !(20.0..=20e3).contains(&frequency.get::<hertz>()) {
+        return (f64::NEG_INFINITY, SingingRisk::None);
+    }
+    let pressure = capacitor.acoustic_emission(ripple, frequency, config.distance) * config.board_gain;
+    let level = sound_pressure_level(pressure) + a_weighting(frequency);
+    let risk = if level >= config.loud_level {
+        SingingRisk::Loud
+    } else if level >= config.audible_level {
+        SingingRisk::Audible
+    } else {
+        SingingRisk::None
+    };
+    (level, risk)
+}
+
+/// Peak amplitude and frequency (Hz) of the ripple in `(time, value)` samples.
+/// Only the settled signal counts: the level is the mean of the second half of
+/// the samples, and both figures come from between the second and last
+/// crossings of it, so a power-up ramp arriving at the level is left out.
+/// `None` without a full ripple period.
+pub fn ripple(samples: &[(f64, f64)]) -> Option<(f64, f64)> {
+    let settled = &samples[samples.len() / 2..];
+    let level = settled.iter().map(|(_, v)| v).sum::<f64>() / settled.len().max(1) as f64;
+    let crossings: Vec<(usize, f64)> = samples
+        .windows(2)
+        .enumerate()
+        .filter_map(|(i, w)| {
+            let ((t0, v0), (t1, v1)) = (w[0], w[1]);
+            let (d0, d1) = (v0 - level, v1 - level);
+            (d0 < 0.0 && d1 >= 0.0 || d0 >= 0.0 && d1 < 0.0).then(|| (i + 1, t0 + (t1 - t0) * d0 / (d0 - d1)))
+        })
+        .collect();
+    // The first crossing may be the ramp; two crossings per period after it.
+    let &[_, (start, first), .., (end, last)] = crossings.as_slice() else { return None; };
+    if crossings.len() < 4 || last <= first {
+        return None;
+    }
+    let frequency = (crossings.len() - 2) as f64 / (2.0 * (last - first));
+    let (min, max) = samples[start..end]
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), (_, v)| (lo.min(*v), hi.max(*v)));
+    Some(((max - min) / 2.0, frequency))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuit::part::capacitor::Capacitor;
+    use circuit_physics_core::overrides::ParameterOverrides;
+    use circuit_physics_core::physical::DielectricType;
+    use std::f64::consts::PI;
+    use uom::si::capacitance::microfarad;
+
+    #[test]
+    fn ripple_recovers_amplitude_and_frequency() {
+        let samples: Vec<_> = (0..2000)
+            .map(|i| {
+                let t = f64::from(i) * 1e-6;
+                (t, 5.0 + 0.2 * (2.0 * PI * 3e3 * t).sin())
+            })
+            .collect();
+        let (amplitude, frequency) = ripple(&samples).unwrap();
+        assert!((amplitude - 0.2).abs() < 1e-3);
+        assert!((frequency - 3e3).abs() < 10.0, "{frequency}");
+        assert!(ripple(&[(0.0, 0.0), (1.0, 1.0)]).is_none());
+    }
+
+    #[test]
+    fn power_up_ramp_is_not_ripple() {
+        let samples: Vec<_> = (0..2000)
+            .map(|i| {
+                let t = f64::from(i) * 1e-6;
+                (t, 5.0 * (t / 2e-4).min(1.0) + 0.05 * (2.0 * PI * 3e3 * t).sin())
+            })
+            .collect();
+        let (amplitude, frequency) = ripple(&samples).unwrap();
+        assert!((amplitude - 0.05).abs() < 1e-3, "{amplitude}");
+        assert!((frequency - 3e3).abs() < 30.0, "{frequency}");
+    }
+
+    #[test]
+    fn only_ceramic_capacitors_sing() {
+        let config = AcousticConfig::default();
+        let ripple = ElectricPotential::new::<volt>(0.5);
+        let frequency = Frequency::new::<hertz>(2e3);
+        let ceramic = Capacitor { capacitance: uom::si::f64::Capacitance::new::<microfarad>(10.0), ..Capacitor::default() };
+        let (level, risk) = assess(&ceramic, ripple, frequency, &config);
+        assert!(risk >= SingingRisk::Audible, "{level} dB(A)");
+        let damped = ParameterOverrides { piezoelectric_coefficient: Some(0.0), ..ParameterOverrides::default() };
+        let part = Part::Capacitor(ceramic.clone());
+        assert_eq!(assess(&part.physics(&damped), ripple, frequency, &config).1, SingingRisk::None);
+        let film = Capacitor { dielectric: DielectricType::Film, ..ceramic };
+        assert_eq!(assess(&film, ripple, frequency, &config).1, SingingRisk::None);
+    }
+}
@@ -0,0 +1,385 @@
+//! Trace parasitics and crosstalk coupling from closed-form approximations.
+//!
+//! Each trace is a chain of straight rectangular segments. Self inductance uses
+//! Ruehli's partial inductance of a rectangular bar, mutual inductance Grover's
+//! formula for parallel filaments (offset along the axis allowed) and mutual
+//! capacitance the two-wire line over the overlapping length. Only segment pairs
+//! that are close to parallel are coupled; perpendicular segments have no mutual
+//! inductance and little capacitance.
+//!
+//! `CouplingExtraction::inject` turns a trace into a series R-L between its two
+//! end nodes, with the couplings stamped between traces as `MutualInductance` and
+//! split capacitors (half at each end), for `Netlist::transient`.
+
+use std::collections::HashMap;
+use std::f64::consts::PI;
+use std::hash::Hash;
+
+use super::dc::{Element, Netlist};
+use super::transient::TransientConfig;
+
+/// Vacuum permeability (H/m).
+const MU_0: f64 = 4e-7 * PI;
+/// Vacuum permittivity (F/m).
+const EPSILON_0: f64 = 8.854_187_8e-12;
+
+#[derive(Debug, Clone)]
+pub struct CouplingConfig {
+    /// Edge length of one grid cell (metres), for trace paths given in grid units.
+    pub grid_unit: f64,
+    /// Dielectric between traces; 4.4 is FR-4.
+    pub relative_permittivity: f64,
+    /// Conductor resistivity (Ω·m); copper by default.
+    pub resistivity: f64,
+    /// Minimum `|cos θ|` between two segments for them to be treated as parallel.
+    pub parallel_tolerance: f64,
+    /// Segments further apart than this (metres) are not coupled.
+    pub max_distance: f64,
+    /// Trace pairs with a smaller inductive coupling coefficient are dropped.
+    pub min_coefficient: f64,
+}
+
+impl Default for CouplingConfig {
+    fn default() -> Self {
+        Self {
+            grid_unit: 1e-3,
+            relative_permittivity: 4.4,
+            resistivity: 1.68e-8,
+            parallel_tolerance: 0.98,
+            max_distance: 10e-3,
+            min_coefficient: 1e-4,
+        }
+    }
+}
+
+/// Crosstalk run: extract trace coupling, then ramp every supply from zero over
+/// `rise_time` seconds and integrate.
+#[derive(Debug, Clone)]
+pub struct CrosstalkConfig {
+    pub coupling: CouplingConfig,
+    pub transient: TransientConfig,
+    pub rise_time: f64,
+}
+
+impl Default for CrosstalkConfig {
+    fn default() -> Self {
+        Self { coupling: CouplingConfig::default(), transient: TransientConfig::default(), rise_time: 1e-9 }
+    }
+}
+
+/// Straight piece of a trace with a rectangular cross-section (metres).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TraceSegment {
+    pub start: [f64; 3],
+    pub end: [f64; 3],
+    pub width: f64,
+    pub thickness: f64,
+}
+
+impl TraceSegment {
+    pub fn length(&self) -> f64 {
+        norm(sub(self.end, self.start))
+    }
+
+    /// Unit vector from `start` to `end`.
+    pub fn direction(&self) -> [f64; 3] {
+        scale(sub(self.end, self.start), 1.0 / self.length().max(f64::MIN_POSITIVE))
+    }
+
+    /// Partial self inductance (henries) of a rectangular bar.
+    pub fn self_inductance(&self) -> f64 {
+        let l = self.length();
+        if l <= 0.0 {
+            return 0.0;
+        }
+        let perimeter = self.width + self.thickness;
+        MU_0 * l / (2.0 * PI) * ((2.0 * l / perimeter).ln() + 0.5 + 0.2235 * perimeter / l)
+    }
+
+    pub fn resistance(&self, resistivity: f64) -> f64 {
+        resistivity * self.length() / (self.width * self.thickness).max(f64::MIN_POSITIVE)
+    }
+
+    /// Geometric mean distance of the cross-section to itself, used as the closest
+    /// spacing two conductors can have.
+    fn mean_radius(&self) -> f64 {
+        0.2235 * (self.width + self.thickness)
+    }
+}
+
+/// Where `other` lies relative to `segment`'s axis.
+struct Alignment {
+    /// Sign of the dot product of the two directions.
+    sign: f64,
+    /// Axial coordinates of `other`'s ends along `segment`, ordered to run the same way.
+    span: (f64, f64),
+    /// Perpendicular distance between the two axes.
+    distance: f64,
+}
+
+fn align(segment: &TraceSegment, other: &TraceSegment, parallel_tolerance: f64) -> Option<Alignment> {
+    let axis = segment.direction();
+    let cos = dot(axis, other.direction());
+    if cos.abs() < parallel_tolerance {
+        return None;
+    }
+    let s = dot(sub(other.start, segment.start), axis);
+    let e = dot(sub(other.end, segment.start), axis);
+    let midpoint = scale(add(other.start, other.end), 0.5);
+    let offset = sub(midpoint, segment.start);
+    let distance = norm(sub(offset, scale(axis, dot(offset, axis))));
+    let (sign, span) = if cos > 0.0 { (1.0, (s, e)) } else { (-1.0, (e, s)) };
+    Some(Alignment { sign, span, distance })
+}
+
+/// Mutual inductance (henries) between two parallel segments; negative when they
+/// run in opposite directions, zero when they are not parallel.
+pub fn mutual_inductance(a: &TraceSegment, b: &TraceSegment, parallel_tolerance: f64) -> f64 {
+    let Some(Alignment { sign, span: (s, e), distance }) = align(a, b, parallel_tolerance) else {
+        return 0.0;
+    };
+    let d = distance.max(a.mean_radius()).max(b.mean_radius());
+    // Double antiderivative of 1/√(z² + d²).
+    let g = |z: f64| z * (z / d).asinh() - (z * z + d * d).sqrt();
+    let l = a.length();
+    sign * MU_0 / (4.0 * PI) * (g(l - s) - g(l - e) - g(-s) + g(-e))
+}
+
+/// Mutual capacitance (farads) between two parallel segments over the length where
+/// they overlap, treating each as a round wire of equal perimeter.
+pub fn mutual_capacitance(a: &TraceSegment, b: &TraceSegment, relative_permittivity: f64, parallel_tolerance: f64) -> f64 {
+    let Some(Alignment { span: (s, e), distance, .. }) = align(a, b, parallel_tolerance) else {
+        return 0.0;
+    };
+    let overlap = e.min(a.length()) - s.max(0.0);
+    if overlap <= 0.0 {
+        return 0.0;
+    }
+    let radius = (a.width + a.thickness + b.width + b.thickness) / 8.0;
+    // acosh is undefined once the wires touch; cap the ratio just above 1.
+    let ratio = (distance / (2.0 * radius)).max(1.0 + 1e-3);
+    PI * EPSILON_0 * relative_permittivity * overlap / ratio.acosh()
+}
+
+/// A trace as a chain of segments in metres.
+#[derive(Debug, Clone)]
+pub struct TraceGeometry<K> {
+    pub key: K,
+    pub segments: Vec<TraceSegment>,
+}
+
+impl<K> TraceGeometry<K> {
+    /// Segments between consecutive path points (metres); zero-length steps are skipped.
+    pub fn from_path(key: K, path: &[[f64; 3]], width: f64, thickness: f64) -> Self {
+        let segments = path
+            .windows(2)
+            .filter(|pair| pair[0] != pair[1])
+            .map(|pair| TraceSegment { start: pair[0], end: pair[1], width, thickness })
+            .collect();
+        Self { key, segments }
+    }
+
+    pub fn length(&self) -> f64 {
+        self.segments.iter().map(TraceSegment::length).sum()
+    }
+}
+
+/// Series parasitics of one trace.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TraceParasitics {
+    /// Henries, summed over segments (segment-to-segment mutuals within a trace are ignored).
+    pub inductance: f64,
+    pub resistance: f64,
+}
+
+/// Coupling between two traces.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TraceCoupling<K> {
+    pub a: K,
+    pub b: K,
+    /// Henries, signed by the relative direction of the two paths.
+    pub mutual_inductance: f64,
+    pub capacitance: f64,
+    /// `M / √(L_a L_b)`.
+    pub coefficient: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct CouplingExtraction<K> {
+    pub traces: HashMap<K, TraceParasitics>,
+    pub couplings: Vec<TraceCoupling<K>>,
+}
+
+impl<K> Default for CouplingExtraction<K> {
+    fn default() -> Self {
+        Self { traces: HashMap::new(), couplings: Vec::new() }
+    }
+}
+
+impl<K: Copy + Eq + Hash> CouplingExtraction<K> {
+    pub fn extract(traces: &[TraceGeometry<K>], config: &CouplingConfig) -> Self {
+        let parasitics: HashMap<K, TraceParasitics> = traces
+            .iter()
+            .map(|trace| {
+                let inductance = trace.segments.iter().map(TraceSegment::self_inductance).sum();
+                let resistance = trace.segments.iter().map(|s| s.resistance(config.resistivity)).sum();
+                (trace.key, TraceParasitics { inductance, resistance })
+            })
+            .collect();
+
+        let mut couplings = Vec::new();
+        for (i, first) in traces.iter().enumerate() {
+            for second in &traces[i + 1..] {
+                let (mut mutual, mut capacitance) = (0.0, 0.0);
+                for a in &first.segments {
+                    for b in &second.segments {
+                        if segment_distance(a, b) > config.max_distance {
+                            continue;
+                        }
+                        mutual += mutual_inductance(a, b, config.parallel_tolerance);
+                        capacitance += mutual_capacitance(a, b, config.relative_permittivity, config.parallel_tolerance);
+                    }
+                }
+                let self_product = parasitics[&first.key].inductance * parasitics[&second.key].inductance;
+                if self_product <= 0.0 {
+                    continue;
+                }
+                let coefficient = mutual / self_product.sqrt();
+                if coefficient.abs() >= config.min_coefficient {
+                    couplings.push(TraceCoupling { a: first.key, b: second.key, mutual_inductance: mutual, capacitance, coefficient });
+                }
+            }
+        }
+        Self { traces: parasitics, couplings }
+    }
+
+    /// Strongest couplings first, by `|coefficient|`.
+    pub fn strongest(&self, count: usize) -> Vec<&TraceCoupling<K>> {
+        let mut sorted: Vec<_> = self.couplings.iter().collect();
+        sorted.sort_by(|a, b| b.coefficient.abs().total_cmp(&a.coefficient.abs()));
+        sorted.truncate(count);
+        sorted
+    }
+
+    /// Add every trace in `ends` (near node, far node) to `netlist` as a series
+    /// resistor (tagged with the trace key) and inductor, and the couplings between them.
+    pub fn inject(&self, netlist: &mut Netlist<K>, ends: &HashMap<K, [usize; 2]>) {
+        let mut inductors = HashMap::new();
+        for (key, &[near, far]) in ends {
+            let Some(parasitics) = self.traces.get(key) else { continue; };
+            let mid = netlist.add_node();
+            netlist.add(Some(*key), Element::Resistor { a: near, b: mid, ohms: parasitics.resistance });
+            let inductor = netlist.push(None, Element::Inductor { a: mid, b: far, henries: parasitics.inductance });
+            inductors.insert(*key, inductor);
+        }
+        for coupling in &self.couplings {
+            let (Some(&[near_a, far_a]), Some(&[near_b, far_b])) = (ends.get(&coupling.a), ends.get(&coupling.b)) else {
+                continue;
+            };
+            let farads = coupling.capacitance / 2.0;
+            netlist
+                .add(None, Element::Capacitor { a: near_a, b: near_b, farads })
+                .add(None, Element::Capacitor { a: far_a, b: far_b, farads })
+                .add(
+                    None,
+                    Element::MutualInductance {
+                        first: inductors[&coupling.a],
+                        second: inductors[&coupling.b],
+                        henries: coupling.mutual_inductance,
+                    },
+                );
+        }
+    }
+}
+
+/// Distance between segment midpoints, a cheap proximity cull.
+fn segment_distance(a: &TraceSegment, b: &TraceSegment) -> f64 {
+    let mid_a = scale(add(a.start, a.end), 0.5);
+    let mid_b = scale(add(b.start, b.end), 0.5);
+    (norm(sub(mid_a, mid_b)) - (a.length() + b.length()) / 2.0).max(0.0)
+}
+
+fn add(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+fn sub(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn scale(a: [f64; 3], k: f64) -> [f64; 3] {
+    [a[0] * k, a[1] * k, a[2] * k]
+}
+
+fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn norm(a: [f64; 3]) -> f64 {
+    dot(a, a).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuit::simulation::transient::ramp;
+
+    fn segment(start: [f64; 3], end: [f64; 3]) -> TraceSegment {
+        TraceSegment { start, end, width: 0.2e-3, thickness: 35e-6 }
+    }
+
+    #[test]
+    fn aligned_mutual_inductance_matches_closed_form() {
+        let (l, d) = (20e-3, 1e-3);
+        let a = segment([0.0; 3], [l, 0.0, 0.0]);
+        let b = segment([0.0, d, 0.0], [l, d, 0.0]);
+        let expected = MU_0 * l / (2.0 * PI) * ((l / d).asinh() - (1.0 + (d / l).powi(2)).sqrt() + d / l);
+        let m = mutual_inductance(&a, &b, 0.98);
+        assert!((m - expected).abs() < 1e-6 * expected, "{m} vs {expected}");
+
+        let far = segment([0.0, 3.0 * d, 0.0], [l, 3.0 * d, 0.0]);
+        assert!(mutual_inductance(&a, &far, 0.98) < m);
+        let reversed = segment([l, d, 0.0], [0.0, d, 0.0]);
+        assert!((mutual_inductance(&a, &reversed, 0.98) + m).abs() < 1e-6 * expected);
+        let perpendicular = segment([0.0, d, 0.0], [0.0, d + l, 0.0]);
+        assert_eq!(mutual_inductance(&a, &perpendicular, 0.98), 0.0);
+    }
+
+    #[test]
+    fn parallel_traces_crosstalk() {
+        // 50 mm aggressor and victim 0.3 mm apart, both terminated in 50 Ω at each end.
+        let config = CouplingConfig::default();
+        let traces = [
+            TraceGeometry::from_path(10, &[[0.0; 3], [50e-3, 0.0, 0.0]], 0.2e-3, 35e-6),
+            TraceGeometry::from_path(11, &[[0.0, 0.3e-3, 0.0], [50e-3, 0.3e-3, 0.0]], 0.2e-3, 35e-6),
+        ];
+        let extraction = CouplingExtraction::extract(&traces, &config);
+        let coupling = extraction.couplings[0];
+        assert!(coupling.coefficient > 0.1 && coupling.coefficient < 1.0, "k = {}", coupling.coefficient);
+        assert!(coupling.capacitance > 0.0);
+
+        // Nodes: 1 source, 2/3 aggressor near/far, 4/5 victim near/far.
+        let mut netlist = Netlist::new(6);
+        netlist
+            .add(Some(0), Element::VoltageSource { pos: 1, neg: 0, volts: 1.0 })
+            .add(Some(1), Element::Resistor { a: 1, b: 2, ohms: 50.0 })
+            .add(Some(2), Element::Resistor { a: 3, b: 0, ohms: 50.0 })
+            .add(Some(3), Element::Resistor { a: 4, b: 0, ohms: 50.0 })
+            .add(Some(4), Element::Resistor { a: 5, b: 0, ohms: 50.0 });
+        let ends = HashMap::from([(10, [2, 3]), (11, [4, 5])]);
+        extraction.inject(&mut netlist, &ends);
+
+        let nominal = netlist.clone();
+        let rise_time = 1e-9;
+        let samples = netlist
+            .transient(&TransientConfig { dt: 1e-11, steps: 2000 }, |t, n| n.scale_voltage_sources(&nominal, ramp(t, rise_time)))
+            .unwrap();
+        let near_end_peak = samples.iter().map(|(_, s)| s.node_voltages[4].abs()).fold(0.0, f64::max);
+        let (_, settled) = samples.last().unwrap();
+        assert!(near_end_peak > 1e-3, "near-end crosstalk {near_end_peak}");
+        assert!(near_end_peak < 0.5);
+        // Once the edge has passed the victim returns to rest and the aggressor divides 1 V across 100 Ω.
+        assert!(settled.node_voltages[4].abs() < near_end_peak / 10.0);
+        assert!((settled.node_voltages[3] - 0.5).abs() < 1e-2);
+    }
+}
@@ -0,0 +1,412 @@
+//! DC operating-point solver using modified nodal analysis (MNA).
+//!
+//! Node 0 is ground. Nonlinear elements (diodes) are handled with Newton-Raphson
+//! and junction-voltage limiting. Capacitors are open and inductors are shorts at
+//! DC; `transient` gives them backward-Euler companion models.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use thiserror::Error;
+
+/// Thermal voltage kT/q at 300 K (volts).
+pub const THERMAL_VOLTAGE: f64 = 0.025_852;
+
+/// Conductance added from every node to ground so floating nodes stay solvable.
+const GMIN: f64 = 1e-12;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Element {
+    Resistor { a: usize, b: usize, ohms: f64 },
+    /// Shockley diode `I = Is (exp(V / (n Vt)) - 1)`.
+    Diode { anode: usize, cathode: usize, saturation_current: f64, emission_coefficient: f64 },
+    /// Ideal voltage source, `pos` held `volts` above `neg`.
+    VoltageSource { pos: usize, neg: usize, volts: f64 },
+    /// Ideal current source pushing `amps` from `from` to `to` through the source.
+    CurrentSource { from: usize, to: usize, amps: f64 },
+    Capacitor { a: usize, b: usize, farads: f64 },
+    /// Ideal inductor; its current (from `a` to `b`) is an MNA unknown.
+    Inductor { a: usize, b: usize, henries: f64 },
+    /// Mutual inductance between the inductors at element indices `first` and `second`.
+    MutualInductance { first: usize, second: usize, henries: f64 },
+}
+
+impl Element {
+    /// Diode parameters chosen so the diode drops `forward_voltage` at `forward_current`.
+    pub fn diode_from_rating(anode: usize, cathode: usize, forward_voltage: f64, forward_current: f64) -> Self {
+        let emission_coefficient = 1.8;
+        let saturation_current = forward_current / ((forward_voltage / (emission_coefficient * THERMAL_VOLTAGE)).exp() - 1.0);
+        Element::Diode { anode, cathode, saturation_current, emission_coefficient }
+    }
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum DcError {
+    #[error("circuit matrix is singular (check for voltage-source loops or floating sources)")]
+    Singular,
+    #[error("Newton iteration did not converge after {0} iterations")]
+    NotConverged(usize),
+    #[error("element references node {node} but the netlist only has {node_count} nodes")]
+    InvalidNode { node: usize, node_count: usize },
+    #[error("mutual inductance references element {element}, which is not an inductor")]
+    InvalidCoupling { element: usize },
+}
+
+/// Solved node voltages and per-element branch quantities.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DcSolution<K: Eq + Hash> {
+    /// Voltage of each node, index 0 (ground) included.
+    pub node_voltages: Vec<f64>,
+    /// Current through each tagged element, flowing from its first terminal to its second.
+    pub currents: HashMap<K, f64>,
+    /// Power absorbed by each tagged element (negative for sources delivering power).
+    pub power: HashMap<K, f64>,
+    pub iterations: usize,
+}
+
+/// A flat circuit description for the DC solver. `K` tags elements so results can be
+/// mapped back to parts.
+#[derive(Debug, Clone)]
+pub struct Netlist<K> {
+    /// Number of nodes including ground.
+    pub node_count: usize,
+    pub elements: Vec<(Option<K>, Element)>,
+    pub max_iterations: usize,
+    /// Convergence threshold on the largest node-voltage update (volts).
+    pub tolerance: f64,
+}
+
+impl<K> Default for Netlist<K> {
+    fn default() -> Self {
+        Self { node_count: 1, elements: Vec::new(), max_iterations: 200, tolerance: 1e-9 }
+    }
+}
+
+impl<K: Copy + Eq + Hash> Netlist<K> {
+    pub fn new(node_count: usize) -> Self {
+        Self { node_count: node_count.max(1), ..Default::default() }
+    }
+
+    pub fn add(&mut self, tag: Option<K>, element: Element) -> &mut Self {
+        self.elements.push((tag, element));
+        self
+    }
+
+    /// Add an element and return its index, for `Element::MutualInductance`.
+    pub fn push(&mut self, tag: Option<K>, element: Element) -> usize {
+        self.elements.push((tag, element));
+        self.elements.len() - 1
+    }
+
+    /// Allocate a new internal node.
+    pub fn add_node(&mut self) -> usize {
+        self.node_count += 1;
+        self.node_count - 1
+    }
+
+    pub(super) fn validate(&self) -> Result<(), DcError> {
+        for (_, element) in &self.elements {
+            let nodes = match *element {
+                Element::Resistor { a, b, .. } | Element::Capacitor { a, b, .. } | Element::Inductor { a, b, .. } => [a, b],
+                Element::Diode { anode, cathode, .. } => [anode, cathode],
+                Element::VoltageSource { pos, neg, .. } => [pos, neg],
+                Element::CurrentSource { from, to, .. } => [from, to],
+                Element::MutualInductance { first, second, .. } => {
+                    for element in [first, second] {
+                        if !matches!(self.elements.get(element), Some((_, Element::Inductor { .. }))) {
+                            return Err(DcError::InvalidCoupling { element });
+                        }
+                    }
+                    continue;
+                }
+            };
+            if let Some(&node) = nodes.iter().find(|&&n| n >= self.node_count) {
+                return Err(DcError::InvalidNode { node, node_count: self.node_count });
+            }
+        }
+        Ok(())
+    }
+
+    /// Solve for the DC operating point.
+    pub fn solve(&self) -> Result<DcSolution<K>, DcError> {
+        self.validate()?;
+        self.solve_step(None).map(|(solution, _)| solution)
+    }
+
+    /// Solve once, with capacitors and inductors replaced by their companion models
+    /// when `companion` is given. Also returns the current through every element, by index.
+    pub(super) fn solve_step(&self, companion: Option<&Companion>) -> Result<(DcSolution<K>, Vec<f64>), DcError> {
+        let n = self.node_count - 1;
+        // Voltage sources and inductors each add a branch-current unknown.
+        let mut size = n;
+        let branch_rows: Vec<Option<usize>> = self
+            .elements
+            .iter()
+            .map(|(_, e)| {
+                matches!(e, Element::VoltageSource { .. } | Element::Inductor { .. }).then(|| {
+                    size += 1;
+                    size - 1
+                })
+            })
+            .collect();
+        let has_diodes = self.elements.iter().any(|(_, e)| matches!(e, Element::Diode { .. }));
+
+        let mut voltages = vec![0.0; self.node_count];
+        let mut iterations = 0;
+
+        let unknowns = loop {
+            iterations += 1;
+            let mut matrix = vec![vec![0.0; size]; size];
+            let mut rhs = vec![0.0; size];
+
+            for node in 1..self.node_count {
+                matrix[node - 1][node - 1] += GMIN;
+            }
+
+            for (index, (_, element)) in self.elements.iter().enumerate() {
+                match *element {
+                    Element::Resistor { a, b, ohms } => {
+                        stamp_conductance(&mut matrix, a, b, 1.0 / ohms.max(f64::MIN_POSITIVE));
+                    }
+                    Element::Diode { anode, cathode, saturation_current, emission_coefficient } => {
+                        let vt = emission_coefficient * THERMAL_VOLTAGE;
+                        let vd = voltages[anode] - voltages[cathode];
+                        let exp = (vd / vt).exp();
+                        let id = saturation_current * (exp - 1.0);
+                        let gd = saturation_current * exp / vt + GMIN;
+                        let ieq = id - gd * vd;
+                        stamp_conductance(&mut matrix, anode, cathode, gd);
+                        stamp_current(&mut rhs, anode, cathode, ieq);
+                    }
+                    Element::VoltageSource { pos, neg, volts } => {
+                        let row = branch_rows[index].expect("voltage sources have a branch row");
+                        stamp_branch(&mut matrix, pos, neg, row);
+                        rhs[row] = volts;
+                    }
+                    Element::CurrentSource { from, to, amps } => {
+                        stamp_current(&mut rhs, from, to, amps);
+                    }
+                    Element::Capacitor { a, b, farads } => {
+                        if let Some(companion) = companion {
+                            let g = farads / companion.dt;
+                            stamp_conductance(&mut matrix, a, b, g);
+                            stamp_current(&mut rhs, b, a, g * companion.voltage_across(a, b));
+                        }
+                    }
+                    Element::Inductor { a, b, henries } => {
+                        let row = branch_rows[index].expect("inductors have a branch row");
+                        stamp_branch(&mut matrix, a, b, row);
+                        // v_a - v_b - (L/dt) i = -(L/dt) i_prev; a short at DC.
+                        if let Some(companion) = companion {
+                            let r = henries / companion.dt;
+                            matrix[row][row] -= r;
+                            rhs[row] -= r * companion.currents[index];
+                        }
+                    }
+                    Element::MutualInductance { first, second, henries } => {
+                        if let Some(companion) = companion {
+                            let (Some(row_first), Some(row_second)) = (branch_rows[first], branch_rows[second]) else {
+                                continue;
+                            };
+                            let r = henries / companion.dt;
+                            matrix[row_first][row_second] -= r;
+                            matrix[row_second][row_first] -= r;
+                            rhs[row_first] -= r * companion.currents[second];
+                            rhs[row_second] -= r * companion.currents[first];
+                        }
+                    }
+                }
+            }
+
+            let unknowns = solve_linear(matrix, rhs).ok_or(DcError::Singular)?;
+
+            let mut max_delta: f64 = 0.0;
+            let mut next = vec![0.0; self.node_count];
+            next[1..].copy_from_slice(&unknowns[..n]);
+            for (_, element) in &self.elements {
+                if let Element::Diode { anode, cathode, emission_coefficient, saturation_current } = *element {
+                    let vt = emission_coefficient * THERMAL_VOLTAGE;
+                    let old = voltages[anode] - voltages[cathode];
+                    let new = next[anode] - next[cathode];
+                    let limited = limit_junction_voltage(new, old, vt, saturation_current);
+                    if limited != new {
+                        // Pull the anode back so the junction voltage respects the limit.
+                        if anode > 0 { next[anode] = next[cathode] + limited; }
+                        else { next[cathode] = next[anode] - limited; }
+                    }
+                }
+            }
+            for (old, new) in voltages.iter().zip(&next) {
+                max_delta = max_delta.max((old - new).abs());
+            }
+            voltages = next;
+
+            if !has_diodes || max_delta < self.tolerance {
+                break unknowns;
+            }
+            if iterations >= self.max_iterations {
+                return Err(DcError::NotConverged(iterations));
+            }
+        };
+
+        let mut currents = HashMap::new();
+        let mut power = HashMap::new();
+        let mut element_currents = Vec::with_capacity(self.elements.len());
+        for (index, (tag, element)) in self.elements.iter().enumerate() {
+            let (current, drop) = match *element {
+                Element::Resistor { a, b, ohms } => {
+                    let v = voltages[a] - voltages[b];
+                    (v / ohms.max(f64::MIN_POSITIVE), v)
+                }
+                Element::Diode { anode, cathode, saturation_current, emission_coefficient } => {
+                    let v = voltages[anode] - voltages[cathode];
+                    (saturation_current * ((v / (emission_coefficient * THERMAL_VOLTAGE)).exp() - 1.0), v)
+                }
+                // MNA solves for the current entering `pos` (or `a`) from the circuit.
+                Element::VoltageSource { pos: a, neg: b, .. } | Element::Inductor { a, b, .. } => {
+                    (unknowns[branch_rows[index].expect("branch element has a row")], voltages[a] - voltages[b])
+                }
+                Element::CurrentSource { from, to, amps } => (amps, voltages[from] - voltages[to]),
+                Element::Capacitor { a, b, farads } => {
+                    let v = voltages[a] - voltages[b];
+                    let current = companion.map_or(0.0, |c| farads / c.dt * (v - c.voltage_across(a, b)));
+                    (current, v)
+                }
+                Element::MutualInductance { .. } => (0.0, 0.0),
+            };
+            element_currents.push(current);
+            if let Some(tag) = tag {
+                currents.insert(*tag, current);
+                power.insert(*tag, current * drop);
+            }
+        }
+
+        Ok((DcSolution { node_voltages: voltages, currents, power, iterations }, element_currents))
+    }
+}
+
+/// Backward-Euler state from the previous time step.
+pub(super) struct Companion<'a> {
+    pub dt: f64,
+    pub voltages: &'a [f64],
+    /// Current through each element, by element index.
+    pub currents: &'a [f64],
+}
+
+impl Companion<'_> {
+    fn voltage_across(&self, a: usize, b: usize) -> f64 {
+        self.voltages[a] - self.voltages[b]
+    }
+}
+
+/// Stamp the incidence of a branch-current unknown flowing from `pos` to `neg`.
+fn stamp_branch(matrix: &mut [Vec<f64>], pos: usize, neg: usize, row: usize) {
+    if pos > 0 {
+        matrix[pos - 1][row] += 1.0;
+        matrix[row][pos - 1] += 1.0;
+    }
+    if neg > 0 {
+        matrix[neg - 1][row] -= 1.0;
+        matrix[row][neg - 1] -= 1.0;
+    }
+}
+
+fn stamp_conductance(matrix: &mut [Vec<f64>], a: usize, b: usize, g: f64) {
+    if a > 0 { matrix[a - 1][a - 1] += g; }
+    if b > 0 { matrix[b - 1][b - 1] += g; }
+    if a > 0 && b > 0 {
+        matrix[a - 1][b - 1] -= g;
+        matrix[b - 1][a - 1] -= g;
+    }
+}
+
+/// Stamp a current `amps` flowing out of node `from` and into node `to`.
+fn stamp_current(rhs: &mut [f64], from: usize, to: usize, amps: f64) {
+    if from > 0 { rhs[from - 1] -= amps; }
+    if to > 0 { rhs[to - 1] += amps; }
+}
+
+/// SPICE-style `pnjlim`: cap the growth of a forward-biased junction voltage per iteration.
+fn limit_junction_voltage(new: f64, old: f64, vt: f64, saturation_current: f64) -> f64 {
+    let critical = vt * (vt / (std::f64::consts::SQRT_2 * saturation_current)).ln();
+    if new > critical && (new - old).abs() > 2.0 * vt {
+        if old > 0.0 {
+            let arg = 1.0 + (new - old) / vt;
+            if arg > 0.0 { old + vt * arg.ln() } else { critical }
+        } else {
+            vt * (new / vt).ln()
+        }
+    } else {
+        new
+    }
+}
+
+/// Gaussian elimination with partial pivoting. Returns `None` for singular systems.
+fn solve_linear(mut a: Vec<Vec<f64>>, mut b: Vec<f64>) -> Option<Vec<f64>> {
+    let n = b.len();
+    for col in 0..n {
+        let pivot = (col..n).max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))?;
+        if a[pivot][col].abs() < 1e-18 {
+            return None;
+        }
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+        for row in col + 1..n {
+            let factor = a[row][col] / a[col][col];
+            if factor == 0.0 { continue; }
+            let (upper, lower) = a.split_at_mut(row);
+            for (x, pivot_value) in lower[0][col..].iter_mut().zip(&upper[col][col..]) {
+                *x -= factor * pivot_value;
+            }
+            b[row] -= factor * b[col];
+        }
+    }
+    let mut x = vec![0.0; n];
+    for row in (0..n).rev() {
+        let sum: f64 = (row + 1..n).map(|k| a[row][k] * x[k]).sum();
+        x[row] = (b[row] - sum) / a[row][row];
+    }
+    Some(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn voltage_divider() {
+        let mut netlist = Netlist::new(3);
+        netlist
+            .add(Some(0), Element::VoltageSource { pos: 1, neg: 0, volts: 10.0 })
+            .add(Some(1), Element::Resistor { a: 1, b: 2, ohms: 1000.0 })
+            .add(Some(2), Element::Resistor { a: 2, b: 0, ohms: 3000.0 });
+        let solution = netlist.solve().unwrap();
+
+        assert!((solution.node_voltages[2] - 7.5).abs() < 1e-6);
+        assert!((solution.currents[&1] - 2.5e-3).abs() < 1e-9);
+        assert!((solution.power[&2] - 7.5 * 2.5e-3).abs() < 1e-9);
+        // The source delivers the power the resistors absorb.
+        assert!((solution.power[&0] + 10.0 * 2.5e-3).abs() < 1e-9);
+    }
+
+    #[test]
+    fn forward_biased_diode() {
+        let mut netlist = Netlist::new(3);
+        netlist
+            .add(None, Element::VoltageSource { pos: 1, neg: 0, volts: 5.0 })
+            .add(Some("R"), Element::Resistor { a: 1, b: 2, ohms: 430.0 })
+            .add(Some("D"), Element::diode_from_rating(2, 0, 0.7, 0.01));
+        let solution = netlist.solve().unwrap();
+
+        // ~4.3 V across 430 Ω gives ~10 mA, where the diode was rated to drop 0.7 V.
+        let vd = solution.node_voltages[2];
+        assert!((vd - 0.7).abs() < 0.02, "diode drop {vd}");
+        assert!((solution.currents[&"R"] - solution.currents[&"D"]).abs() < 1e-9);
+    }
+
+    #[test]
+    fn rejects_out_of_range_nodes() {
+        let mut netlist = Netlist::<u8>::new(2);
+        netlist.add(None, Element::Resistor { a: 1, b: 5, ohms: 1.0 });
+        assert_eq!(netlist.solve(), Err(DcError::InvalidNode { node: 5, node_count: 2 }));
+    }
+}
@@ -0,0 +1,261 @@
+//! Electro-thermal co-simulation (see `docs/05_thermal_coupling.md`).
+//!
+//! Each iteration solves the DC operating point with every part drifted to its
+//! current junction temperature, converts dissipated power into a new junction
+//! temperature through `ThermalBehavior`, and repeats until temperatures settle.
+
+use bevy::prelude::Entity;
+use std::collections::HashMap;
+use thiserror::Error;
+
+use circuit_physics_core::thermal::{TemperatureDependent, ThermalBehavior};
+use uom::si::f64::{Power, ThermodynamicTemperature};
+use uom::si::{power::watt, thermodynamic_temperature::kelvin};
+
+use super::dc::DcError;
+use super::netlist::CircuitSnapshot;
+use super::OperatingPoint;
+
+#[derive(Debug, Clone)]
+pub struct ElectroThermalConfig {
+    pub ambient: ThermodynamicTemperature,
+    pub max_iterations: usize,
+    /// Converged once no junction temperature moves by more than this (kelvin).
+    pub tolerance: f64,
+    /// Under-relaxation factor in `(0, 1]` applied to each temperature update.
+    pub relaxation: f64,
+    /// Junction temperature treated as runaway regardless of convergence (kelvin).
+    pub runaway_temperature: f64,
+    /// Consecutive iterations of growing residual tolerated before declaring runaway.
+    pub divergence_window: usize,
+}
+
+impl Default for ElectroThermalConfig {
+    fn default() -> Self {
+        Self {
+            ambient: ThermodynamicTemperature::new::<kelvin>(298.15),
+            max_iterations: 100,
+            tolerance: 0.01,
+            relaxation: 0.7,
+            runaway_temperature: 1000.0,
+            divergence_window: 5,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ElectroThermalSolution {
+    /// Electrical solution at the converged temperatures.
+    pub operating_point: OperatingPoint,
+    pub junction_temperatures: HashMap<Entity, ThermodynamicTemperature>,
+    pub iterations: usize,
+    /// Largest junction temperature change per iteration (kelvin).
+    pub residuals: Vec<f64>,
+    /// Parts that converged above their `max_junction_temperature`.
+    pub over_temperature: Vec<Entity>,
+}
+
+#[derive(Debug, Error)]
+pub enum ElectroThermalError {
+    #[error(transparent)]
+    Dc(#[from] DcError),
+    #[error("thermal runaway in {} part(s) after {iteration} iterations", parts.len())]
+    ThermalRunaway { parts: Vec<Entity>, iteration: usize },
+    #[error("electro-thermal loop did not converge in {iterations} iterations (residual {residual} K)")]
+    NotConverged { iterations: usize, residual: f64 },
+}
+
+/// Solve the electrical operating point with no self-heating.
+pub fn solve_dc(snapshot: &CircuitSnapshot) -> Result<OperatingPoint, DcError> {
+    let (netlist, nodes) = snapshot.netlist(|terminals| terminals.part.clone());
+    let solution = netlist.solve()?;
+    Ok(snapshot.operating_point(&solution, &nodes))
+}
+
+/// Iterate electrical and thermal solves until junction temperatures converge.
+pub fn solve_electro_thermal(
+    snapshot: &CircuitSnapshot,
+    config: &ElectroThermalConfig,
+) -> Result<ElectroThermalSolution, ElectroThermalError> {
+    let ambient = config.ambient.get::<kelvin>();
+    let mut temperatures: HashMap<Entity, f64> =
+        snapshot.parts.iter().map(|t| (t.entity, ambient)).collect();
+    let mut residuals = Vec::new();
+    let mut growing = 0;
+
+    for iteration in 1..=config.max_iterations {
+        let operating_point = solve_drifted(snapshot, &temperatures)?;
+
+        let mut residual: f64 = 0.0;
+        let mut deltas = Vec::new();
+        for terminals in &snapshot.parts {
+            let watts = operating_point.part_power(terminals.entity).unwrap_or(0.0).max(0.0);
+            let target = terminals
+                .physics()
+                .junction_temperature(Power::new::<watt>(watts), config.ambient)
+                .get::<kelvin>();
+            let current = temperatures.get_mut(&terminals.entity).expect("every part has a temperature");
+            let delta = config.relaxation * (target - *current);
+            *current += delta;
+            residual = residual.max(delta.abs());
+            deltas.push((terminals.entity, delta, *current));
+        }
+
+        growing = match residuals.last() {
+            Some(&previous) if residual > previous => growing + 1,
+            _ => 0,
+        };
+        residuals.push(residual);
+
+        let mut heating: Vec<_> = deltas.iter().filter(|&&(_, delta, _)| delta > config.tolerance).collect();
+        let runaway = heating.iter().any(|&&(_, _, t)| t > config.runaway_temperature)
+            || growing >= config.divergence_window;
+        if runaway {
+            heating.sort_by(|a, b| b.1.total_cmp(&a.1));
+            let mut parts: Vec<_> = heating.into_iter().map(|&(entity, _, _)| entity).collect();
+            if parts.is_empty() {
+                // Diverging without heating up (e.g. oscillating): blame the largest swing.
+                parts = deltas
+                    .iter()
+                    .filter(|&&(_, delta, _)| delta.abs() == residual)
+                    .map(|&(entity, _, _)| entity)
+                    .collect();
+            }
+            return Err(ElectroThermalError::ThermalRunaway { parts, iteration });
+        }
+
+        if residual < config.tolerance {
+            // The point above was solved before the last update; redo it at the final temperatures.
+            let operating_point = solve_drifted(snapshot, &temperatures)?;
+            let over_temperature = snapshot
+                .parts
+                .iter()
+                .filter(|t| temperatures[&t.entity] > t.physics().max_junction_temperature().get::<kelvin>())
+                .map(|t| t.entity)
+                .collect();
+            let junction_temperatures = temperatures
+                .into_iter()
+                .map(|(entity, t)| (entity, ThermodynamicTemperature::new::<kelvin>(t)))
+                .collect();
+            return Ok(ElectroThermalSolution {
+                operating_point,
+                junction_temperatures,
+                iterations: iteration,
+                residuals,
+                over_temperature,
+            });
+        }
+    }
+
+    Err(ElectroThermalError::NotConverged {
+        iterations: config.max_iterations,
+        residual: residuals.last().copied().unwrap_or(f64::INFINITY),
+    })
+}
+
+/// DC operating point with every part drifted to its temperature (kelvin).
+fn solve_drifted(snapshot: &CircuitSnapshot, temperatures: &HashMap<Entity, f64>) -> Result<OperatingPoint, DcError> {
+    let (netlist, nodes) = snapshot.netlist(|terminals| {
+        let temperature = ThermodynamicTemperature::new::<kelvin>(temperatures[&terminals.entity]);
+        terminals.part.with_drift(terminals.physics().parameter_drift(temperature))
+    });
+    let solution = netlist.solve()?;
+    Ok(snapshot.operating_point(&solution, &nodes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    use circuit_physics_core::overrides::ParameterOverrides;
+    use uom::si::f64::{ElectricalResistance, TemperatureCoefficient, ThermalResistance};
+    use uom::si::{electrical_resistance::ohm, temperature_coefficient::per_kelvin, thermal_resistance::kelvin_per_watt};
+
+    use crate::circuit::part::resistor::Resistor;
+    use crate::circuit::part::Part;
+    use crate::circuit::simulation::netlist::PartTerminals;
+
+    fn entity(index: u32) -> Entity {
+        Entity::from_raw_u32(index).unwrap()
+    }
+
+    /// Resistor between two nets with tempco `alpha` (1/K) and `theta` (K/W) to ambient.
+    fn resistor(index: u32, nets: [u32; 2], ohms: f64, alpha: f64, theta: f64) -> PartTerminals {
+        PartTerminals {
+            entity: entity(index),
+            part: Part::Resistor(Resistor { resistance: ElectricalResistance::new::<ohm>(ohms), ..Resistor::default() }),
+            nets: nets.map(|net| Some(entity(net))),
+            pins: [None, None],
+            overrides: ParameterOverrides {
+                temperature_coefficient: Some(TemperatureCoefficient::new::<per_kelvin>(alpha)),
+                thermal_resistance_jc: Some(ThermalResistance::new::<kelvin_per_watt>(0.0)),
+                thermal_resistance_ca: Some(ThermalResistance::new::<kelvin_per_watt>(theta)),
+                ..ParameterOverrides::default()
+            },
+        }
+    }
+
+    /// Net 0 is ground and net 1 is held at `volts`.
+    fn circuit(volts: f64, parts: Vec<PartTerminals>) -> CircuitSnapshot {
+        CircuitSnapshot {
+            parts,
+            ground_nets: HashSet::from([entity(0)]),
+            supplies: vec![(entity(1), volts)],
+            ..CircuitSnapshot::default()
+        }
+    }
+
+    fn rise(solution: &ElectroThermalSolution, part: u32) -> f64 {
+        solution.junction_temperatures[&entity(part)].get::<kelvin>() - 298.15
+    }
+
+    #[test]
+    fn self_heated_resistor_settles_at_its_fixed_point() {
+        // 1 W at 25 °C through 50 K/W: rise = 50 / (1 + 1e-3·rise).
+        let snapshot = circuit(10.0, vec![resistor(10, [1, 0], 100.0, 1e-3, 50.0)]);
+        let solution = solve_electro_thermal(&snapshot, &ElectroThermalConfig::default()).unwrap();
+        let expected = (1.2f64.sqrt() - 1.0) / 2e-3;
+        assert!((rise(&solution, 10) - expected).abs() < 0.05, "{}", rise(&solution, 10));
+        // The operating point belongs to the reported temperature, not the one before it.
+        let ohms = 100.0 * (1.0 + 1e-3 * rise(&solution, 10));
+        assert!((solution.operating_point.part_power(entity(10)).unwrap() - 100.0 / ohms).abs() < 1e-9);
+        assert!(solution.over_temperature.is_empty());
+    }
+
+    #[test]
+    fn reports_parts_above_their_rating() {
+        let mut hot = resistor(10, [1, 0], 100.0, 0.0, 50.0);
+        hot.overrides.max_junction_temperature = Some(ThermodynamicTemperature::new::<kelvin>(330.0));
+        let cool = resistor(11, [1, 0], 100.0, 0.0, 1.0);
+        let solution = solve_electro_thermal(&circuit(10.0, vec![hot, cool]), &ElectroThermalConfig::default()).unwrap();
+        assert_eq!(solution.over_temperature, [entity(10)]);
+    }
+
+    #[test]
+    fn positive_feedback_runs_away() {
+        // A hot 10 Ω sense resistor fed through a 1 kΩ ballast: the hotter it gets,
+        // the more of the supply it drops.
+        let snapshot = circuit(200.0, vec![
+            resistor(10, [1, 2], 1000.0, 0.0, 0.0),
+            resistor(11, [2, 0], 10.0, 1e-2, 1000.0),
+        ]);
+        let Err(ElectroThermalError::ThermalRunaway { parts, .. }) =
+            solve_electro_thermal(&snapshot, &ElectroThermalConfig::default())
+        else {
+            panic!("expected runaway")
+        };
+        assert_eq!(parts, [entity(11)]);
+    }
+
+    #[test]
+    fn oscillating_loop_blames_the_largest_swing() {
+        // Over-relaxed, each update overshoots further; the diverging step cools.
+        let config = ElectroThermalConfig { relaxation: 2.5, divergence_window: 1, ..ElectroThermalConfig::default() };
+        let snapshot = circuit(10.0, vec![resistor(10, [1, 0], 100.0, 0.0, 50.0), resistor(11, [1, 0], 1e6, 0.0, 1.0)]);
+        let Err(ElectroThermalError::ThermalRunaway { parts, iteration }) = solve_electro_thermal(&snapshot, &config) else {
+            panic!("expected runaway")
+        };
+        assert_eq!((parts, iteration), (vec![entity(10)], 2));
+    }
+}
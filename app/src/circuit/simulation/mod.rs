@@ -0,0 +1,493 @@
+pub mod acoustic;
+pub mod coupling;
+pub mod dc;
+pub mod electro_thermal;
+pub mod netlist;
+pub mod radiation;
+pub mod reliability;
+pub mod solder_fatigue;
+pub mod thermal_network;
+pub mod transient;
+pub mod vibration;
+pub mod voxel_thermal;
+
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+use uom::si::f64::ThermodynamicTemperature;
+use uom::si::{acceleration::standard_gravity, electric_potential::volt, frequency::hertz, time::year};
+
+use crate::circuit::net::{Net, NetSupply};
+use crate::circuit::part::{LayoutPlacement, Part, PartOverrides};
+use crate::circuit::pin::Pin;
+use crate::circuit::relations::{OnNet, Pins};
+use crate::circuit::trace::Trace;
+use acoustic::{AcousticConfig, AcousticReport, SingingRisk};
+use coupling::{CouplingExtraction, CrosstalkConfig};
+use electro_thermal::{solve_dc, solve_electro_thermal, ElectroThermalConfig};
+use netlist::CircuitSnapshot;
+use radiation::{RadiationConfig, RadiationReport};
+use reliability::{ReliabilityConfig, ReliabilityReport};
+use solder_fatigue::{SolderFatigueConfig, SolderFatigueReport};
+use thermal_network::{ThermalBlock, ThermalNetwork, ThermalNetworkConfig};
+use transient::ramp;
+use vibration::{VibrationConfig, VibrationReport};
+use voxel_thermal::{TemperatureField, VoxelModel, VoxelThermalConfig};
+
+/// Electrical state of the circuit at one instant.
+///
+/// Part currents use the passive sign convention: positive current enters the
+/// part at pin 1 and leaves at pin 2.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OperatingPoint {
+    /// Net entity to node voltage (volts, relative to ground).
+    pub net_voltages: HashMap<Entity, f64>,
+    /// Part entity to current through the part (amperes). Simulated traces are
+    /// included, with current flowing from their driven end.
+    pub part_currents: HashMap<Entity, f64>,
+    /// Part entity to dissipated power (watts).
+    pub part_power: HashMap<Entity, f64>,
+}
+
+impl OperatingPoint {
+    pub fn net_voltage(&self, net: Entity) -> Option<f64> {
+        self.net_voltages.get(&net).copied()
+    }
+
+    pub fn part_current(&self, part: Entity) -> Option<f64> {
+        self.part_currents.get(&part).copied()
+    }
+
+    pub fn part_power(&self, part: Entity) -> Option<f64> {
+        self.part_power.get(&part).copied()
+    }
+
+    /// Current flowing *into* a part through the given pin (1-based index).
+    pub fn pin_current(&self, part: Entity, pin_index: u8) -> Option<f64> {
+        let current = self.part_current(part)?;
+        Some(if pin_index == 1 { current } else { -current })
+    }
+
+    /// Minimum and maximum net voltage, if any nets were solved.
+    pub fn voltage_range(&self) -> Option<(f64, f64)> {
+        self.net_voltages.values().fold(None, |range, &v| match range {
+            None => Some((v, v)),
+            Some((lo, hi)) => Some((lo.min(v), hi.max(v))),
+        })
+    }
+
+    /// Linear blend between two operating points; entries missing from `other` are kept from `self`.
+    pub fn lerp(&self, other: &Self, t: f64) -> Self {
+        fn blend(a: &HashMap<Entity, f64>, b: &HashMap<Entity, f64>, t: f64) -> HashMap<Entity, f64> {
+            a.iter()
+                .map(|(&k, &va)| (k, b.get(&k).map_or(va, |&vb| va + (vb - va) * t)))
+                .collect()
+        }
+        Self {
+            net_voltages: blend(&self.net_voltages, &other.net_voltages, t),
+            part_currents: blend(&self.part_currents, &other.part_currents, t),
+            part_power: blend(&self.part_power, &other.part_power, t),
+        }
+    }
+}
+
+/// Results of the most recent circuit solve.
+///
+/// A DC solve stores a single sample; a transient solve stores samples in
+/// ascending time order and is sampled at the current simulation time.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct SimulationResults {
+    samples: Vec<(f64, OperatingPoint)>,
+}
+
+impl SimulationResults {
+    pub fn dc(point: OperatingPoint) -> Self {
+        Self { samples: vec![(0.0, point)] }
+    }
+
+    /// Build from `(time_seconds, point)` samples; they are sorted by time.
+    pub fn transient(mut samples: Vec<(f64, OperatingPoint)>) -> Self {
+        samples.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Self { samples }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+
+    pub fn samples(&self) -> &[(f64, OperatingPoint)] {
+        &self.samples
+    }
+
+    /// Operating point at `time` seconds, interpolated between samples and
+    /// clamped to the first/last sample outside the solved window.
+    pub fn at(&self, time: f64) -> Option<OperatingPoint> {
+        let (first, last) = (self.samples.first()?, self.samples.last()?);
+        if time <= first.0 { return Some(first.1.clone()); }
+        if time >= last.0 { return Some(last.1.clone()); }
+        let upper = self.samples.partition_point(|(t, _)| *t <= time);
+        let (t0, p0) = &self.samples[upper - 1];
+        let (t1, p1) = &self.samples[upper];
+        let span = t1 - t0;
+        if span <= 0.0 { return Some(p1.clone()); }
+        Some(p0.lerp(p1, (time - t0) / span))
+    }
+}
+
+/// Junction temperature of each part from the most recent electro-thermal solve.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct JunctionTemperatures(pub HashMap<Entity, ThermodynamicTemperature>);
+
+/// Request a circuit solve; results land in `SimulationResults`.
+#[derive(Event, BufferedEvent, Debug, Clone)]
+pub enum SolveCircuit {
+    /// DC operating point at nominal part values.
+    Dc,
+    /// DC operating point with self-heating iterated to convergence.
+    ElectroThermal(ElectroThermalConfig),
+    /// Transient power-up with trace parasitics and trace-to-trace coupling.
+    Crosstalk(CrosstalkConfig),
+}
+
+/// Trace parasitics and couplings from the most recent crosstalk solve.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct TraceCouplings(pub CouplingExtraction<Entity>);
+
+/// Request a thermal solve of parts placed in the layout, heated by the power in
+/// the latest `SimulationResults` sample.
+#[derive(Event, BufferedEvent, Debug, Clone)]
+pub enum SolveThermal {
+    SteadyState(ThermalNetworkConfig),
+    /// `steps` backward-Euler steps of `dt` seconds, starting from the current `BlockTemperatures`.
+    Transient { config: ThermalNetworkConfig, dt: f64, steps: usize },
+    /// Steady-state finite-volume solve; blocks report their hottest voxel.
+    Voxel(VoxelThermalConfig),
+}
+
+/// Block temperatures from the most recent thermal network solve.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct BlockTemperatures {
+    pub current: HashMap<Entity, ThermodynamicTemperature>,
+    /// `(time_seconds, temperatures)` of the last transient solve.
+    pub samples: Vec<(f64, HashMap<Entity, ThermodynamicTemperature>)>,
+    /// Per-voxel temperatures from the last `SolveThermal::Voxel` solve.
+    pub field: Option<TemperatureField<Entity>>,
+}
+
+/// Request a lifetime prediction from the latest results and temperatures.
+#[derive(Event, BufferedEvent, Debug, Clone)]
+pub struct AnalyseReliability(pub ReliabilityConfig);
+
+/// Most recent reliability analysis.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct Reliability(pub Option<ReliabilityReport>);
+
+/// Request a thermal cycling fatigue analysis of every part's solder joints, using
+/// the last `SolveThermal::Transient` history when there is one.
+#[derive(Event, BufferedEvent, Debug, Clone)]
+pub struct AnalyseSolderFatigue(pub SolderFatigueConfig);
+
+/// Most recent solder fatigue analysis.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct SolderFatigue(pub Option<SolderFatigueReport>);
+
+/// Request a mission dose and single-event upset analysis of every part.
+#[derive(Event, BufferedEvent, Debug, Clone)]
+pub struct AnalyseRadiation(pub RadiationConfig);
+
+/// Most recent radiation analysis.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct Radiation(pub Option<RadiationReport>);
+
+/// Request a singing-capacitor analysis of the ripple in the last transient solve.
+#[derive(Event, BufferedEvent, Debug, Clone)]
+pub struct AnalyseAcoustics(pub AcousticConfig);
+
+/// Most recent acoustic analysis.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct Acoustics(pub Option<AcousticReport>);
+
+/// Request a vibration and shock analysis of every part.
+#[derive(Event, BufferedEvent, Debug, Clone)]
+pub struct AnalyseVibration(pub VibrationConfig);
+
+/// Most recent vibration analysis.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct Vibration(pub Option<VibrationReport>);
+
+pub struct CircuitSimulationPlugin;
+
+impl Plugin for CircuitSimulationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SimulationResults>()
+            .init_resource::<JunctionTemperatures>()
+            .init_resource::<BlockTemperatures>()
+            .init_resource::<Reliability>()
+            .init_resource::<TraceCouplings>()
+            .init_resource::<Vibration>()
+            .init_resource::<SolderFatigue>()
+            .init_resource::<Radiation>()
+            .init_resource::<Acoustics>()
+            .add_event::<SolveCircuit>()
+            .add_event::<SolveThermal>()
+            .add_event::<AnalyseReliability>()
+            .add_event::<AnalyseVibration>()
+            .add_event::<AnalyseSolderFatigue>()
+            .add_event::<AnalyseRadiation>()
+            .add_event::<AnalyseAcoustics>()
+            .add_systems(Update, (solve_circuit, solve_thermal, analyse_reliability, analyse_solder_fatigue, analyse_acoustics).chain())
+            .add_systems(Update, (analyse_vibration, analyse_radiation));
+    }
+}
+
+fn solve_circuit(
+    mut requests: EventReader<SolveCircuit>,
+    mut results: ResMut<SimulationResults>,
+    mut temperatures: ResMut<JunctionTemperatures>,
+    mut couplings: ResMut<TraceCouplings>,
+    parts: Query<(Entity, &Part, Option<&Pins>, Option<&PartOverrides>)>,
+    pins: Query<(&Pin, Option<&OnNet>)>,
+    nets: Query<(Entity, &Net, Option<&NetSupply>)>,
+    traces: Query<(Entity, &Trace)>,
+    names: Query<&Name>,
+) {
+    // Only the latest request matters; earlier ones would be overwritten anyway.
+    let Some(request) = requests.read().last() else { return; };
+    let snapshot = CircuitSnapshot::collect(&parts, &pins, &nets);
+
+    match request {
+        SolveCircuit::Dc => match solve_dc(&snapshot) {
+            Ok(point) => {
+                *results = SimulationResults::dc(point);
+                temperatures.0.clear();
+            }
+            Err(err) => error!("DC solve failed: {err}"),
+        },
+        SolveCircuit::ElectroThermal(config) => match solve_electro_thermal(&snapshot, config) {
+            Ok(solution) => {
+                if !solution.over_temperature.is_empty() {
+                    warn!(
+                        "{} part(s) exceed their maximum junction temperature: {:?}",
+                        solution.over_temperature.len(),
+                        solution.over_temperature
+                    );
+                }
+                debug!("Electro-thermal solve converged in {} iterations", solution.iterations);
+                *results = SimulationResults::dc(solution.operating_point);
+                temperatures.0 = solution.junction_temperatures;
+            }
+            Err(err) => error!("Electro-thermal solve failed: {err}"),
+        },
+        SolveCircuit::Crosstalk(config) => {
+            let snapshot = snapshot.with_traces(&traces, &pins, &config.coupling);
+            let name = |entity: Entity| names.get(entity).map_or("?", Name::as_str);
+            for coupling in snapshot.coupling.strongest(3) {
+                debug!(
+                    "Trace coupling {} - {}: k = {:.3}, C = {:.2e} F",
+                    name(coupling.a),
+                    name(coupling.b),
+                    coupling.coefficient,
+                    coupling.capacitance
+                );
+            }
+            let (netlist, nodes) = snapshot.netlist(|terminals| terminals.part.clone());
+            let samples = netlist.transient(&config.transient, |time, working| {
+                working.scale_voltage_sources(&netlist, ramp(time, config.rise_time));
+            });
+            match samples {
+                Ok(samples) => {
+                    *results = SimulationResults::transient(
+                        samples.iter().map(|(time, solution)| (*time, snapshot.operating_point(solution, &nodes))).collect(),
+                    );
+                    temperatures.0.clear();
+                    couplings.0 = snapshot.coupling;
+                }
+                Err(err) => error!("Crosstalk solve failed: {err}"),
+            }
+        }
+    }
+}
+
+fn solve_thermal(
+    mut requests: EventReader<SolveThermal>,
+    results: Res<SimulationResults>,
+    mut temperatures: ResMut<BlockTemperatures>,
+    parts: Query<(Entity, &Part, &LayoutPlacement)>,
+) {
+    let Some(request) = requests.read().last() else { return; };
+    let power = results.samples().last().map(|(_, point)| point.part_power.clone()).unwrap_or_default();
+    let blocks: Vec<_> = parts
+        .iter()
+        .map(|(entity, part, placement)| ThermalBlock::new(entity, placement.0.into(), part))
+        .collect();
+
+    match request {
+        SolveThermal::SteadyState(config) => match ThermalNetwork::build(blocks, config).steady_state(&power) {
+            Ok(current) => *temperatures = BlockTemperatures { current, ..default() },
+            Err(err) => error!("Thermal network solve failed: {err}"),
+        },
+        SolveThermal::Transient { config, dt, steps } => {
+            match ThermalNetwork::build(blocks, config).transient(&power, &temperatures.current, *dt, *steps) {
+                Ok(samples) => {
+                    if let Some((_, last)) = samples.last() {
+                        temperatures.current = last.clone();
+                    }
+                    temperatures.samples = samples;
+                    temperatures.field = None;
+                }
+                Err(err) => error!("Thermal network solve failed: {err}"),
+            }
+        }
+        SolveThermal::Voxel(config) => match VoxelModel::build(&blocks, config).and_then(|model| model.steady_state(&power)) {
+            Ok(field) => {
+                let current = field.block_stats().into_iter().map(|(entity, stats)| (entity, stats.max)).collect();
+                *temperatures = BlockTemperatures { current, samples: Vec::new(), field: Some(field) };
+            }
+            Err(err) => error!("Voxel thermal solve failed: {err}"),
+        },
+    }
+}
+
+fn analyse_reliability(
+    mut requests: EventReader<AnalyseReliability>,
+    results: Res<SimulationResults>,
+    junctions: Res<JunctionTemperatures>,
+    blocks: Res<BlockTemperatures>,
+    mut reliability: ResMut<Reliability>,
+    parts: Query<(Entity, &Part, Option<&Pins>, Option<&PartOverrides>)>,
+    pins: Query<(&Pin, Option<&OnNet>)>,
+    nets: Query<(Entity, &Net, Option<&NetSupply>)>,
+    names: Query<&Name>,
+) {
+    let Some(AnalyseReliability(config)) = requests.read().last() else { return; };
+    let Some((_, point)) = results.samples().last() else {
+        warn!("Reliability analysis requested before the circuit was solved");
+        return;
+    };
+    // Junction temperatures from the electro-thermal loop win over layout block temperatures.
+    let mut temperatures = blocks.current.clone();
+    temperatures.extend(junctions.0.iter().map(|(&entity, &t)| (entity, t)));
+
+    let snapshot = CircuitSnapshot::collect(&parts, &pins, &nets);
+    let report = ReliabilityReport::analyse(&snapshot, point, &temperatures, config);
+    info!(
+        "System reliability over mission: {:.4}, MTTF {:.1} years",
+        report.system_reliability,
+        report.system_mttf.get::<year>()
+    );
+    for part in report.weakest(3) {
+        let name = names.get(part.entity).map_or("?", Name::as_str);
+        info!("  {name}: R = {:.4}, acceleration x{:.1}, stress {:.0}%", part.reliability, part.acceleration, part.stress * 100.0);
+    }
+    reliability.0 = Some(report);
+}
+
+fn analyse_solder_fatigue(
+    mut requests: EventReader<AnalyseSolderFatigue>,
+    blocks: Res<BlockTemperatures>,
+    mut fatigue: ResMut<SolderFatigue>,
+    parts: Query<(Entity, &Part, Option<&Pins>, Option<&PartOverrides>)>,
+    pins: Query<(&Pin, Option<&OnNet>)>,
+    nets: Query<(Entity, &Net, Option<&NetSupply>)>,
+    names: Query<&Name>,
+) {
+    let Some(AnalyseSolderFatigue(config)) = requests.read().last() else { return; };
+    let snapshot = CircuitSnapshot::collect(&parts, &pins, &nets);
+    let histories = solder_fatigue::histories(&blocks.samples);
+    let report = SolderFatigueReport::analyse(&snapshot, &histories, config);
+    for part in report.failures() {
+        let name = names.get(part.entity).map_or("?", Name::as_str);
+        warn!(
+            "{name} solder joints fail within the mission: damage {:.2}, worst cycle lasts {:.0} cycles",
+            part.damage, part.worst_cycles_to_failure
+        );
+    }
+    if let Some(worst) = report.parts.first() {
+        info!("Solder fatigue: worst joint survives {:.1} missions", worst.missions_to_failure());
+    }
+    fatigue.0 = Some(report);
+}
+
+fn analyse_vibration(
+    mut requests: EventReader<AnalyseVibration>,
+    mut vibration: ResMut<Vibration>,
+    parts: Query<(Entity, &Part, Option<&Pins>, Option<&PartOverrides>)>,
+    pins: Query<(&Pin, Option<&OnNet>)>,
+    nets: Query<(Entity, &Net, Option<&NetSupply>)>,
+    names: Query<&Name>,
+) {
+    let Some(AnalyseVibration(config)) = requests.read().last() else { return; };
+    let snapshot = CircuitSnapshot::collect(&parts, &pins, &nets);
+    let report = VibrationReport::analyse(&snapshot, config);
+    for part in report.failures() {
+        let name = names.get(part.entity).map_or("?", Name::as_str);
+        warn!(
+            "{name} exceeds its acceleration rating: response {:.1} g, shock {:.1} g of {:.1} g withstood",
+            part.response.get::<standard_gravity>(),
+            part.shock_response.map_or(0.0, |a| a.get::<standard_gravity>()),
+            part.shock_rating.map_or(0.0, |a| a.get::<standard_gravity>())
+        );
+    }
+    if let Some(weakest) = report.parts.first() {
+        info!("Vibration analysis: smallest margin x{:.2}", weakest.margin);
+    }
+    vibration.0 = Some(report);
+}
+
+fn analyse_radiation(
+    mut requests: EventReader<AnalyseRadiation>,
+    mut radiation: ResMut<Radiation>,
+    parts: Query<(Entity, &Part, Option<&Pins>, Option<&PartOverrides>)>,
+    pins: Query<(&Pin, Option<&OnNet>)>,
+    nets: Query<(Entity, &Net, Option<&NetSupply>)>,
+    names: Query<&Name>,
+) {
+    let Some(AnalyseRadiation(config)) = requests.read().last() else { return; };
+    let snapshot = CircuitSnapshot::collect(&parts, &pins, &nets);
+    let report = RadiationReport::analyse(&snapshot, config);
+    info!(
+        "Mission dose {:.0} rad(Si) over {:.1} years, {:.2e} upsets expected",
+        report.mission_dose,
+        report.mission_time.get::<year>(),
+        report.expected_upsets()
+    );
+    for part in report.dose_failures() {
+        let name = names.get(part.entity).map_or("?", Name::as_str);
+        let limit = part.time_to_limit.map_or(f64::INFINITY, |t| t.get::<year>());
+        warn!("{name} reaches its dose limit after {limit:.1} years (margin x{:.2})", part.dose_margin);
+    }
+    radiation.0 = Some(report);
+}
+
+fn analyse_acoustics(
+    mut requests: EventReader<AnalyseAcoustics>,
+    results: Res<SimulationResults>,
+    mut acoustics: ResMut<Acoustics>,
+    parts: Query<(Entity, &Part, Option<&Pins>, Option<&PartOverrides>)>,
+    pins: Query<(&Pin, Option<&OnNet>)>,
+    nets: Query<(Entity, &Net, Option<&NetSupply>)>,
+    names: Query<&Name>,
+) {
+    let Some(AnalyseAcoustics(config)) = requests.read().last() else { return; };
+    if results.samples().len() < 2 {
+        warn!("Acoustic analysis needs a transient solve to find the ripple");
+        return;
+    }
+    let snapshot = CircuitSnapshot::collect(&parts, &pins, &nets);
+    let report = AcousticReport::analyse(&snapshot, results.samples(), config);
+    for part in report.singing() {
+        let name = names.get(part.entity).map_or("?", Name::as_str);
+        let hz = part.frequency.map_or(0.0, |f| f.get::<hertz>());
+        let loudness = if part.risk == SingingRisk::Loud { "loudly" } else { "audibly" };
+        warn!(
+            "{name} sings {loudness}: {:.0} dB(A) from {:.2} V ripple at {hz:.0} Hz",
+            part.level,
+            part.ripple.get::<volt>()
+        );
+    }
+    acoustics.0 = Some(report);
+}
@@ -0,0 +1,252 @@
+use bevy::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+use circuit_physics_core::overrides::{ParameterOverrides, WithOverrides};
+use uom::si::{
+    capacitance::farad, electric_current::ampere, electric_potential::volt, electrical_resistance::ohm,
+    inductance::henry,
+};
+
+use crate::circuit::net::{Net, NetKind, NetSupply};
+use crate::circuit::part::{Part, PartOverrides};
+use crate::circuit::pin::Pin;
+use crate::circuit::relations::{OnNet, Pins};
+use crate::circuit::trace::Trace;
+
+use super::coupling::{CouplingConfig, CouplingExtraction, TraceGeometry};
+use super::dc::{DcSolution, Element, Netlist};
+use super::OperatingPoint;
+
+/// Smallest winding resistance used for inductors so they don't short two nodes outright.
+const MIN_DC_RESISTANCE: f64 = 1e-6;
+
+/// A two-terminal part and the nets its pins 1 and 2 sit on.
+#[derive(Debug, Clone)]
+pub struct PartTerminals {
+    pub entity: Entity,
+    pub part: Part,
+    pub nets: [Option<Entity>; 2],
+    /// Pin entities for pins 1 and 2.
+    pub pins: [Option<Entity>; 2],
+    pub overrides: ParameterOverrides,
+}
+
+impl PartTerminals {
+    /// The part with any `PartOverrides` applied, for physics queries.
+    pub fn physics(&self) -> WithOverrides<'_, Part> {
+        self.part.physics(&self.overrides)
+    }
+}
+
+/// ECS-independent copy of the circuit, taken once per analysis so solvers can
+/// iterate without touching the world.
+#[derive(Debug, Clone, Default)]
+pub struct CircuitSnapshot {
+    pub parts: Vec<PartTerminals>,
+    pub ground_nets: HashSet<Entity>,
+    pub supplies: Vec<(Entity, f64)>,
+    /// Traces whose driven end is on a net; empty unless `with_traces` was called.
+    pub traces: Vec<TraceTerminals>,
+    pub coupling: CouplingExtraction<Entity>,
+}
+
+/// A trace, the net at its driven end and the pin at its far end.
+#[derive(Debug, Clone)]
+pub struct TraceTerminals {
+    pub entity: Entity,
+    pub net: Entity,
+    pub far_pin: Entity,
+}
+
+impl CircuitSnapshot {
+    pub fn collect(
+        parts: &Query<(Entity, &Part, Option<&Pins>, Option<&PartOverrides>)>,
+        pins: &Query<(&Pin, Option<&OnNet>)>,
+        nets: &Query<(Entity, &Net, Option<&NetSupply>)>,
+    ) -> Self {
+        let parts = parts
+            .iter()
+            .map(|(entity, part, part_pins, overrides)| {
+                let mut terminals = [None, None];
+                let mut pin_entities = [None, None];
+                for pin_entity in part_pins.into_iter().flat_map(Pins::iter) {
+                    let Ok((pin, on_net)) = pins.get(pin_entity) else { continue; };
+                    let slot = usize::from(pin.index).wrapping_sub(1);
+                    if slot < 2 {
+                        terminals[slot] = on_net.map(|rel| rel.0);
+                        pin_entities[slot] = Some(pin_entity);
+                    }
+                }
+                PartTerminals {
+                    entity,
+                    part: part.clone(),
+                    nets: terminals,
+                    pins: pin_entities,
+                    overrides: overrides.map(|o| o.0.clone()).unwrap_or_default(),
+                }
+            })
+            .collect();
+
+        let ground_nets = nets
+            .iter()
+            .filter(|(_, net, _)| net.net_type == NetKind::Ground)
+            .map(|(entity, _, _)| entity)
+            .collect();
+        let supplies = nets
+            .iter()
+            .filter_map(|(entity, _, supply)| supply.map(|s| (entity, s.volts)))
+            .collect();
+
+        Self { parts, ground_nets, supplies, traces: Vec::new(), coupling: CouplingExtraction::default() }
+    }
+
+    /// Add trace parasitics and the coupling between traces. Traces whose driven
+    /// pin is not on a net are ignored.
+    pub fn with_traces(
+        mut self,
+        traces: &Query<(Entity, &Trace)>,
+        pins: &Query<(&Pin, Option<&OnNet>)>,
+        config: &CouplingConfig,
+    ) -> Self {
+        let mut geometry = Vec::new();
+        for (entity, trace) in traces {
+            let Some(net) = pins.get(trace.pins[0]).ok().and_then(|(_, on_net)| on_net).map(|rel| rel.0) else {
+                continue;
+            };
+            let unit = config.grid_unit;
+            let path: Vec<_> = trace.path.iter().map(|p| p.as_dvec3().to_array().map(|c| c * unit)).collect();
+            geometry.push(TraceGeometry::from_path(
+                entity,
+                &path,
+                f64::from(trace.width) * unit,
+                f64::from(trace.thickness) * unit,
+            ));
+            self.traces.push(TraceTerminals { entity, net, far_pin: trace.pins[1] });
+        }
+        self.coupling = CouplingExtraction::extract(&geometry, config);
+        self
+    }
+
+    /// Build a netlist, letting `part_for` substitute each part (e.g. drifted to its temperature).
+    /// Part pins at the far end of a trace get their own node, joined to the net through
+    /// the trace. Returns the netlist and the node index of every net.
+    pub fn netlist(&self, mut part_for: impl FnMut(&PartTerminals) -> Part) -> (Netlist<Entity>, HashMap<Entity, usize>) {
+        let mut net_nodes: HashMap<Entity, usize> = self.ground_nets.iter().map(|&net| (net, 0)).collect();
+        let mut node_count = 1;
+
+        let mut elements = Vec::new();
+        for (net, volts) in &self.supplies {
+            let pos = node_for(&mut net_nodes, &mut node_count, Some(*net));
+            elements.push((None, Element::VoltageSource { pos, neg: 0, volts: *volts }));
+        }
+        let mut trace_ends = HashMap::new();
+        let mut far_nodes = HashMap::new();
+        for trace in &self.traces {
+            let near = node_for(&mut net_nodes, &mut node_count, Some(trace.net));
+            let far = *far_nodes.entry(trace.far_pin).or_insert_with(|| node_for(&mut net_nodes, &mut node_count, None));
+            trace_ends.insert(trace.entity, [near, far]);
+        }
+        for terminals in &self.parts {
+            let [a, b] = [0, 1].map(|i| match terminals.pins[i].and_then(|pin| far_nodes.get(&pin)) {
+                Some(&far) => far,
+                None => node_for(&mut net_nodes, &mut node_count, terminals.nets[i]),
+            });
+            let element = match part_for(terminals) {
+                Part::Resistor(r) => Element::Resistor { a, b, ohms: r.resistance.get::<ohm>() },
+                Part::Inductor(l) => {
+                    // Winding resistance in series with the inductance; the resistor carries the part tag.
+                    let mid = node_for(&mut net_nodes, &mut node_count, None);
+                    elements.push((None, Element::Inductor { a: mid, b, henries: l.inductance.get::<henry>() }));
+                    Element::Resistor { a, b: mid, ohms: l.dc_resistance.get::<ohm>().max(MIN_DC_RESISTANCE) }
+                }
+                Part::Capacitor(c) => Element::Capacitor { a, b, farads: c.capacitance.get::<farad>() },
+                Part::Diode(d) => Element::diode_from_rating(
+                    a,
+                    b,
+                    d.forward_voltage.get::<volt>(),
+                    d.forward_current_rating.get::<ampere>(),
+                ),
+            };
+            elements.push((Some(terminals.entity), element));
+        }
+
+        let mut netlist = Netlist::new(node_count);
+        netlist.elements = elements;
+        self.coupling.inject(&mut netlist, &trace_ends);
+        (netlist, net_nodes)
+    }
+
+    /// Map a DC solution back onto net and part entities.
+    pub fn operating_point(&self, solution: &DcSolution<Entity>, net_nodes: &HashMap<Entity, usize>) -> OperatingPoint {
+        let net_voltages = net_nodes
+            .iter()
+            .map(|(&net, &node)| (net, solution.node_voltages[node]))
+            .collect();
+        let mut point = OperatingPoint {
+            net_voltages,
+            part_currents: solution.currents.clone(),
+            part_power: solution.power.clone(),
+        };
+        // Every part gets an entry, even if the solver reported nothing for it.
+        for terminals in &self.parts {
+            point.part_currents.entry(terminals.entity).or_insert(0.0);
+            point.part_power.entry(terminals.entity).or_insert(0.0);
+        }
+        point
+    }
+}
+
+/// Node index for a net, allocating one on first use. Unconnected pins get a private floating node.
+fn node_for(net_nodes: &mut HashMap<Entity, usize>, node_count: &mut usize, net: Option<Entity>) -> usize {
+    let mut allocate = || {
+        *node_count += 1;
+        *node_count - 1
+    };
+    match net {
+        Some(net) => *net_nodes.entry(net).or_insert_with(allocate),
+        None => allocate(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuit::part::resistor::Resistor;
+    use uom::si::f64::ElectricalResistance;
+
+    fn entity(index: u32) -> Entity {
+        Entity::from_raw_u32(index).unwrap()
+    }
+
+    fn resistor(index: u32, nets: [Option<u32>; 2], ohms: f64) -> PartTerminals {
+        PartTerminals {
+            entity: entity(index),
+            part: Part::Resistor(Resistor { resistance: ElectricalResistance::new::<ohm>(ohms), ..Resistor::default() }),
+            nets: nets.map(|net| net.map(entity)),
+            pins: [None, None],
+            overrides: ParameterOverrides::default(),
+        }
+    }
+
+    #[test]
+    fn divider_maps_back_onto_nets_and_parts() {
+        let snapshot = CircuitSnapshot {
+            parts: vec![
+                resistor(10, [Some(1), Some(2)], 1000.0),
+                resistor(11, [Some(2), Some(0)], 3000.0),
+                // Dangling: its pins get private nodes and it carries nothing.
+                resistor(12, [Some(2), None], 10.0),
+            ],
+            ground_nets: HashSet::from([entity(0)]),
+            supplies: vec![(entity(1), 10.0)],
+            ..CircuitSnapshot::default()
+        };
+        let (netlist, nodes) = snapshot.netlist(|terminals| terminals.part.clone());
+        let point = snapshot.operating_point(&netlist.solve().unwrap(), &nodes);
+
+        assert_eq!(point.net_voltage(entity(0)), Some(0.0));
+        assert!((point.net_voltage(entity(2)).unwrap() - 7.5).abs() < 1e-6);
+        assert!((point.part_current(entity(10)).unwrap() - 2.5e-3).abs() < 1e-9);
+        assert!(point.part_power(entity(12)).unwrap().abs() < 1e-12);
+    }
+}
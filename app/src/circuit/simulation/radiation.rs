@@ -0,0 +1,163 @@
+//! Total ionising dose and single-event upsets over a mission.
+//!
+//! The mission is a sequence of phases (launch, transfer orbit, operations, ...),
+//! each with a constant dose rate behind the assembly's shielding and an integral
+//! LET spectrum. Dose accumulates phase by phase; a part reaches its limit once
+//! the dose times the design margin exceeds its `total_dose_tolerance`. Upset
+//! rates come from `RadiationHardness::seu_rate` in each phase.
+
+use bevy::prelude::Entity;
+
+use circuit_physics_core::radiation::RadiationHardness;
+use uom::si::f64::{Frequency, Time};
+use uom::si::{frequency::hertz, time::{second, year}};
+
+use super::netlist::CircuitSnapshot;
+
+#[derive(Debug, Clone)]
+pub struct RadiationPhase {
+    pub duration: Time,
+    /// Ionising dose rate at the parts (rad(Si)/s).
+    pub dose_rate: f64,
+    /// `(LET in MeV·cm²/mg, ions at or above that LET per cm²·s)`, ascending in LET.
+    pub let_spectrum: Vec<(f64, f64)>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RadiationConfig {
+    pub phases: Vec<RadiationPhase>,
+    /// Radiation design margin: parts must tolerate this multiple of the dose they see.
+    pub design_margin: f64,
+}
+
+impl Default for RadiationConfig {
+    fn default() -> Self {
+        // Five years in low Earth orbit behind ~2.5 mm of aluminium: ~1 krad/year and
+        // a galactic cosmic ray spectrum attenuated by the geomagnetic field.
+        Self {
+            phases: vec![RadiationPhase {
+                duration: Time::new::<year>(5.0),
+                dose_rate: 1e3 / Time::new::<year>(1.0).get::<second>(),
+                let_spectrum: vec![(1.0, 1e-3), (10.0, 1e-5), (30.0, 1e-7), (100.0, 1e-9)],
+            }],
+            design_margin: 2.0,
+        }
+    }
+}
+
+impl RadiationConfig {
+    pub fn mission_time(&self) -> Time {
+        Time::new::<second>(self.phases.iter().map(|p| p.duration.get::<second>()).sum())
+    }
+
+    pub fn mission_dose(&self) -> f64 {
+        self.phases.iter().map(|p| p.dose_rate * p.duration.get::<second>()).sum()
+    }
+
+    /// When `limit` rad (already divided by the design margin) is reached. Beyond the
+    /// listed phases the mission is assumed to repeat; `None` if no dose is received.
+    fn time_to_dose(&self, limit: f64) -> Option<Time> {
+        let mission_dose = self.mission_dose();
+        if mission_dose <= 0.0 {
+            return None;
+        }
+        let repeats = ((limit / mission_dose).ceil() - 1.0).max(0.0);
+        let mut dose = repeats * mission_dose;
+        let mut time = repeats * self.mission_time().get::<second>();
+        for phase in &self.phases {
+            let seconds = phase.duration.get::<second>();
+            let phase_dose = phase.dose_rate * seconds;
+            if dose + phase_dose >= limit && phase.dose_rate > 0.0 {
+                return Some(Time::new::<second>(time + (limit - dose) / phase.dose_rate));
+            }
+            dose += phase_dose;
+            time += seconds;
+        }
+        Some(Time::new::<second>(time))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PartRadiation {
+    pub entity: Entity,
+    /// Total ionising dose over the mission (rad(Si)).
+    pub total_dose: f64,
+    /// `total_dose_tolerance / (total_dose · design_margin)`; below 1 the part is under-rated.
+    pub dose_margin: f64,
+    /// When the margined dose reaches the part's tolerance; `None` in a dose-free mission.
+    pub time_to_limit: Option<Time>,
+    /// Mission-average upset rate.
+    pub seu_rate: Frequency,
+    pub expected_upsets: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct RadiationReport {
+    pub mission_time: Time,
+    pub mission_dose: f64,
+    /// Sorted by dose margin, smallest first.
+    pub parts: Vec<PartRadiation>,
+}
+
+impl RadiationReport {
+    pub fn analyse(snapshot: &CircuitSnapshot, config: &RadiationConfig) -> Self {
+        let mission_time = config.mission_time();
+        let mission_dose = config.mission_dose();
+        let mut parts: Vec<_> = snapshot
+            .parts
+            .iter()
+            .map(|terminals| {
+                let part = terminals.physics();
+                let tolerance = part.total_dose_tolerance();
+                let expected_upsets: f64 = config
+                    .phases
+                    .iter()
+                    .map(|phase| (part.seu_rate(&phase.let_spectrum) * phase.duration).value)
+                    .sum();
+                let seconds = mission_time.get::<second>();
+                PartRadiation {
+                    entity: terminals.entity,
+                    total_dose: mission_dose,
+                    dose_margin: tolerance / (mission_dose * config.design_margin),
+                    time_to_limit: config.time_to_dose(tolerance / config.design_margin),
+                    seu_rate: Frequency::new::<hertz>(if seconds > 0.0 { expected_upsets / seconds } else { 0.0 }),
+                    expected_upsets,
+                }
+            })
+            .collect();
+        parts.sort_by(|a, b| a.dose_margin.total_cmp(&b.dose_margin));
+        Self { mission_time, mission_dose, parts }
+    }
+
+    /// Parts whose margined dose limit is reached before the mission ends.
+    pub fn dose_failures(&self) -> impl Iterator<Item = &PartRadiation> {
+        self.parts.iter().filter(|p| p.dose_margin < 1.0)
+    }
+
+    /// Upsets expected across the whole assembly over the mission.
+    pub fn expected_upsets(&self) -> f64 {
+        self.parts.iter().map(|p| p.expected_upsets).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn phase(seconds: f64, dose_rate: f64) -> RadiationPhase {
+        RadiationPhase { duration: Time::new::<second>(seconds), dose_rate, let_spectrum: Vec::new() }
+    }
+
+    #[test]
+    fn time_to_dose_walks_phases_and_repeats_the_mission() {
+        let config = RadiationConfig { phases: vec![phase(10.0, 1.0), phase(10.0, 0.0), phase(10.0, 2.0)], design_margin: 1.0 };
+        assert_eq!(config.mission_dose(), 30.0);
+        let at = |limit| config.time_to_dose(limit).unwrap().get::<second>();
+        assert_eq!(at(5.0), 5.0);
+        assert_eq!(at(15.0), 22.5);
+        // Second pass through the mission.
+        assert_eq!(at(35.0), 35.0);
+        let quiet = RadiationConfig { phases: vec![phase(10.0, 0.0)], design_margin: 1.0 };
+        assert!(quiet.time_to_dose(1.0).is_none());
+    }
+}
@@ -0,0 +1,211 @@
+//! Lifetime prediction from the simulated operating point.
+//!
+//! Each part's Weibull wear-out distribution (`WearOut`) is defined at the
+//! reference temperature and stress. Junction temperature and electrical stress
+//! shorten its scale by the Arrhenius/stress acceleration of `AgingMechanisms`,
+//! and the circuit is treated as a series system: it fails when any part fails.
+
+use bevy::prelude::Entity;
+use std::collections::HashMap;
+
+use circuit_physics_core::lifetime::{weibull_mean, AgingMechanisms, WearOut};
+use uom::si::f64::{ThermodynamicTemperature, Time};
+use uom::si::{
+    electric_current::ampere,
+    electric_potential::volt,
+    power::watt,
+    thermodynamic_temperature::kelvin,
+    time::{second, year},
+};
+
+use crate::circuit::part::Part;
+
+use super::netlist::{CircuitSnapshot, PartTerminals};
+use super::OperatingPoint;
+
+#[derive(Debug, Clone)]
+pub struct ReliabilityConfig {
+    pub mission_time: Time,
+    /// Used for parts without a simulated temperature.
+    pub ambient: ThermodynamicTemperature,
+    /// Conditions at which `WearOut::weibull_parameters` apply.
+    pub reference_temperature: ThermodynamicTemperature,
+    /// Fraction of rating at which `WearOut::weibull_parameters` apply.
+    pub reference_stress: f64,
+    /// Floor on the stress ratio so unloaded parts don't get an infinite lifetime.
+    pub min_stress: f64,
+}
+
+impl Default for ReliabilityConfig {
+    fn default() -> Self {
+        Self {
+            mission_time: Time::new::<year>(10.0),
+            ambient: ThermodynamicTemperature::new::<kelvin>(298.15),
+            reference_temperature: ThermodynamicTemperature::new::<kelvin>(298.15),
+            reference_stress: 0.5,
+            min_stress: 0.05,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PartReliability {
+    pub entity: Entity,
+    pub temperature: ThermodynamicTemperature,
+    /// Applied stress as a fraction of the part's rating.
+    pub stress: f64,
+    /// How much faster the part ages than at reference conditions.
+    pub acceleration: f64,
+    /// Weibull shape.
+    pub beta: f64,
+    /// Weibull scale after acceleration.
+    pub eta: Time,
+    pub mttf: Time,
+    /// Probability of surviving the mission.
+    pub reliability: f64,
+}
+
+impl PartReliability {
+    fn cumulative_hazard(&self, t: f64) -> f64 {
+        (t / self.eta.get::<second>()).powf(self.beta)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ReliabilityReport {
+    pub mission_time: Time,
+    /// Sorted weakest first (lowest mission reliability).
+    pub parts: Vec<PartReliability>,
+    /// Product of part reliabilities at mission time.
+    pub system_reliability: f64,
+    pub system_mttf: Time,
+}
+
+impl ReliabilityReport {
+    /// Analyse every part in `snapshot`; `temperatures` overrides `config.ambient` per part.
+    pub fn analyse(
+        snapshot: &CircuitSnapshot,
+        point: &OperatingPoint,
+        temperatures: &HashMap<Entity, ThermodynamicTemperature>,
+        config: &ReliabilityConfig,
+    ) -> Self {
+        let parts = snapshot
+            .parts
+            .iter()
+            .map(|terminals| {
+                let part = terminals.physics();
+                let temperature = temperatures.get(&terminals.entity).copied().unwrap_or(config.ambient);
+                let stress = electrical_stress(terminals, point).max(config.min_stress);
+                let acceleration = (part.time_to_failure_arrhenius(config.reference_temperature, config.reference_stress)
+                    / part.time_to_failure_arrhenius(temperature, stress))
+                .value;
+                let (beta, eta) = part.weibull_parameters();
+                let eta = eta / acceleration;
+                PartReliability {
+                    entity: terminals.entity,
+                    temperature,
+                    stress,
+                    acceleration,
+                    beta,
+                    eta,
+                    mttf: weibull_mean(beta, eta),
+                    reliability: (-(config.mission_time / eta).value.powf(beta)).exp(),
+                }
+            })
+            .collect();
+        Self::from_parts(parts, config.mission_time)
+    }
+
+    fn from_parts(mut parts: Vec<PartReliability>, mission_time: Time) -> Self {
+        parts.sort_by(|a, b| a.reliability.total_cmp(&b.reliability).then(a.mttf.value.total_cmp(&b.mttf.value)));
+        let system_reliability = parts.iter().map(|p| p.reliability).product();
+        let system_mttf = Time::new::<second>(series_mttf(&parts));
+        Self { mission_time, parts, system_reliability, system_mttf }
+    }
+
+    /// The `n` least reliable parts.
+    pub fn weakest(&self, n: usize) -> &[PartReliability] {
+        &self.parts[..n.min(self.parts.len())]
+    }
+
+    /// Probability that no part has failed by `time`.
+    pub fn system_reliability_at(&self, time: Time) -> f64 {
+        let t = time.get::<second>();
+        (-self.parts.iter().map(|p| p.cumulative_hazard(t)).sum::<f64>()).exp()
+    }
+}
+
+/// Applied load as a fraction of rating: power for resistors, voltage for
+/// capacitors, current for inductors, and the worse of forward current and
+/// reverse voltage for diodes.
+pub fn electrical_stress(terminals: &PartTerminals, point: &OperatingPoint) -> f64 {
+    let entity = terminals.entity;
+    let volts = {
+        let [a, b] = terminals.nets.map(|net| net.and_then(|net| point.net_voltage(net)).unwrap_or(0.0));
+        a - b
+    };
+    let amps = point.part_current(entity).unwrap_or(0.0);
+    let ratio = |value: f64, rating: f64| if rating > 0.0 { value / rating } else { 0.0 };
+    match &terminals.part {
+        Part::Resistor(r) => ratio(point.part_power(entity).unwrap_or(0.0).abs(), r.power_rating.get::<watt>()),
+        Part::Capacitor(c) => ratio(volts.abs(), c.voltage_rating.get::<volt>()),
+        Part::Inductor(l) => ratio(amps.abs(), l.current_rating.get::<ampere>()),
+        Part::Diode(d) => ratio(amps.max(0.0), d.forward_current_rating.get::<ampere>())
+            .max(ratio((-volts).max(0.0), d.reverse_breakdown_voltage.get::<volt>())),
+    }
+}
+
+/// `∫ R(t) dt` for a series system by Simpson's rule, truncated once R < 1e-12.
+fn series_mttf(parts: &[PartReliability]) -> f64 {
+    const INTERVALS: usize = 4096;
+    let horizon = parts
+        .iter()
+        .map(|p| p.eta.get::<second>() * 1e12_f64.ln().powf(1.0 / p.beta))
+        .fold(f64::INFINITY, f64::min);
+    if !horizon.is_finite() {
+        return f64::INFINITY;
+    }
+    let reliability = |t: f64| (-parts.iter().map(|p| p.cumulative_hazard(t)).sum::<f64>()).exp();
+    let h = horizon / INTERVALS as f64;
+    let interior: f64 = (1..INTERVALS)
+        .map(|i| (if i % 2 == 1 { 4.0 } else { 2.0 }) * reliability(i as f64 * h))
+        .sum();
+    h / 3.0 * (reliability(0.0) + interior + reliability(horizon))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn part(index: u32, beta: f64, eta: f64, mission: f64) -> PartReliability {
+        let eta = Time::new::<second>(eta);
+        PartReliability {
+            entity: Entity::from_raw_u32(index).unwrap(),
+            temperature: ThermodynamicTemperature::new::<kelvin>(298.15),
+            stress: 0.5,
+            acceleration: 1.0,
+            beta,
+            eta,
+            mttf: weibull_mean(beta, eta),
+            reliability: (-(mission / eta.get::<second>()).powf(beta)).exp(),
+        }
+    }
+
+    #[test]
+    fn exponential_series_mttf_is_reciprocal_of_total_rate() {
+        let report = ReliabilityReport::from_parts(
+            vec![part(1, 1.0, 1000.0, 100.0), part(2, 1.0, 250.0, 100.0)],
+            Time::new::<second>(100.0),
+        );
+        assert!((report.system_mttf.get::<second>() - 200.0).abs() < 0.1);
+        assert!((report.system_reliability - (-0.5_f64).exp()).abs() < 1e-9);
+        assert_eq!(report.weakest(1)[0].entity, Entity::from_raw_u32(2).unwrap());
+    }
+
+    #[test]
+    fn single_weibull_part_mttf_matches_gamma() {
+        let report = ReliabilityReport::from_parts(vec![part(1, 2.5, 1000.0, 10.0)], Time::new::<second>(10.0));
+        let expected = weibull_mean(2.5, Time::new::<second>(1000.0)).get::<second>();
+        assert!((report.system_mttf.get::<second>() - expected).abs() / expected < 1e-4);
+    }
+}
@@ -0,0 +1,206 @@
+//! Thermal cycling fatigue of solder joints.
+//!
+//! Every part sees the environmental cycles of `SolderFatigueConfig::profile`
+//! plus, when the thermal solver produced a transient, the cycles rainflow-counted
+//! from its own temperature history, repeated `transient_repeats` times. Each cycle
+//! costs `1 / Nf` of the joint's life with `Nf` from the Engelmaier model
+//! (`SolderJointReliability::thermal_cycles_to_failure`), and damage adds up by
+//! Miner's rule: the joint is expected to fail once it reaches 1.
+
+use bevy::prelude::Entity;
+use std::collections::HashMap;
+
+use circuit_physics_core::mechanical::SolderJointReliability;
+use circuit_physics_core::units::celsius;
+use uom::si::f64::{TemperatureInterval, ThermodynamicTemperature, Time};
+use uom::si::{temperature_interval, thermodynamic_temperature::kelvin, time::{day, second}};
+
+use super::netlist::CircuitSnapshot;
+
+/// `count` repetitions of a swing between `min` and `max` with period `period`.
+#[derive(Debug, Clone, Copy)]
+pub struct ThermalCycle {
+    pub min: ThermodynamicTemperature,
+    pub max: ThermodynamicTemperature,
+    pub period: Time,
+    pub count: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct SolderFatigueConfig {
+    /// Environmental cycles every part sees over the mission.
+    pub profile: Vec<ThermalCycle>,
+    /// How many times the simulated thermal transient (e.g. one power-up) repeats over the mission.
+    pub transient_repeats: f64,
+}
+
+impl Default for SolderFatigueConfig {
+    fn default() -> Self {
+        // Ten years of daily 20–45 °C swings, powered up once a day.
+        Self {
+            profile: vec![ThermalCycle {
+                min: celsius(20.0),
+                max: celsius(45.0),
+                period: Time::new::<day>(1.0),
+                count: 3650.0,
+            }],
+            transient_repeats: 3650.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct JointFatigue {
+    pub entity: Entity,
+    /// Miner's sum over the mission; failure is expected at 1.
+    pub damage: f64,
+    /// Cycles to failure of the most damaging cycle type.
+    pub worst_cycles_to_failure: f64,
+}
+
+impl JointFatigue {
+    /// How many missions the joint is expected to survive.
+    pub fn missions_to_failure(&self) -> f64 {
+        1.0 / self.damage
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SolderFatigueReport {
+    /// Sorted most damaged first.
+    pub parts: Vec<JointFatigue>,
+}
+
+impl SolderFatigueReport {
+    /// `histories` holds each part's simulated `(time_seconds, temperature)` samples, if any.
+    pub fn analyse(
+        snapshot: &CircuitSnapshot,
+        histories: &HashMap<Entity, Vec<(f64, ThermodynamicTemperature)>>,
+        config: &SolderFatigueConfig,
+    ) -> Self {
+        let mut parts: Vec<_> = snapshot
+            .parts
+            .iter()
+            .map(|terminals| {
+                let part = terminals.physics();
+                let mut damage = 0.0;
+                let mut worst: Option<(f64, f64)> = None;
+                let mut apply = |range: f64, mean: f64, period: f64, count: f64| {
+                    let cycles_to_failure = part.thermal_cycles_to_failure(
+                        TemperatureInterval::new::<temperature_interval::kelvin>(range),
+                        ThermodynamicTemperature::new::<kelvin>(mean),
+                        Time::new::<second>(period),
+                    );
+                    let cost = count / cycles_to_failure;
+                    damage += cost;
+                    if worst.is_none_or(|(worst_cost, _)| cost > worst_cost) {
+                        worst = Some((cost, cycles_to_failure));
+                    }
+                };
+
+                for cycle in &config.profile {
+                    let (min, max) = (cycle.min.get::<kelvin>(), cycle.max.get::<kelvin>());
+                    apply(max - min, (max + min) / 2.0, cycle.period.get::<second>(), cycle.count);
+                }
+                if let Some(history) = histories.get(&terminals.entity).filter(|h| h.len() > 1) {
+                    // The transient is one repeat, so each counted cycle spans it.
+                    let period = history[history.len() - 1].0 - history[0].0;
+                    let temperatures: Vec<f64> = history.iter().map(|(_, t)| t.get::<kelvin>()).collect();
+                    for (range, mean, count) in rainflow(&temperatures) {
+                        apply(range, mean, period, count * config.transient_repeats);
+                    }
+                }
+                JointFatigue {
+                    entity: terminals.entity,
+                    damage,
+                    worst_cycles_to_failure: worst.map_or(f64::INFINITY, |(_, n)| n),
+                }
+            })
+            .collect();
+        parts.sort_by(|a, b| b.damage.total_cmp(&a.damage));
+        Self { parts }
+    }
+
+    /// Parts whose joints are expected to fail within the mission.
+    pub fn failures(&self) -> impl Iterator<Item = &JointFatigue> {
+        self.parts.iter().filter(|p| p.damage >= 1.0)
+    }
+}
+
+/// Per-entity temperature histories from thermal transient samples.
+pub fn histories(
+    samples: &[(f64, HashMap<Entity, ThermodynamicTemperature>)],
+) -> HashMap<Entity, Vec<(f64, ThermodynamicTemperature)>> {
+    let mut histories: HashMap<Entity, Vec<_>> = HashMap::new();
+    for (time, temperatures) in samples {
+        for (&entity, &temperature) in temperatures {
+            histories.entry(entity).or_default().push((*time, temperature));
+        }
+    }
+    histories
+}
+
+/// ASTM E1049 rainflow count of `series` as `(range, mean, count)` with half
+/// cycles counted as 0.5.
+pub fn rainflow(series: &[f64]) -> Vec<(f64, f64, f64)> {
+    let mut cycles = Vec::new();
+    let mut stack: Vec<f64> = Vec::new();
+    for point in reversals(series) {
+        stack.push(point);
+        while stack.len() >= 3 {
+            let n = stack.len();
+            let x = (stack[n - 1] - stack[n - 2]).abs();
+            let y = (stack[n - 2] - stack[n - 3]).abs();
+            if x < y {
+                break;
+            }
+            let mean = (stack[n - 2] + stack[n - 3]) / 2.0;
+            if n == 3 {
+                // The range includes the starting point: half a cycle.
+                cycles.push((y, mean, 0.5));
+                stack.remove(0);
+            } else {
+                cycles.push((y, mean, 1.0));
+                stack.drain(n - 3..n - 1);
+            }
+        }
+    }
+    cycles.extend(stack.windows(2).map(|w| ((w[1] - w[0]).abs(), (w[0] + w[1]) / 2.0, 0.5)));
+    cycles
+}
+
+/// Peaks and valleys of `series`, including its end points.
+fn reversals(series: &[f64]) -> Vec<f64> {
+    let mut points: Vec<f64> = Vec::new();
+    for &value in series {
+        match points.as_slice() {
+            [.., a, b] if (b - a) * (value - b) >= 0.0 => *points.last_mut().unwrap() = value,
+            [.., b] if *b == value => {}
+            _ => points.push(value),
+        }
+    }
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rainflow_counts_astm_example() {
+        // ASTM E1049-85 figure 6.
+        let cycles = rainflow(&[-2.0, 1.0, -3.0, 5.0, -1.0, 3.0, -4.0, 4.0, -2.0]);
+        let count = |range: f64| cycles.iter().filter(|c| c.0 == range).map(|c| c.2).sum::<f64>();
+        assert_eq!(count(3.0), 0.5);
+        assert_eq!(count(4.0), 1.5);
+        assert_eq!(count(6.0), 0.5);
+        assert_eq!(count(8.0), 1.0);
+        assert_eq!(count(9.0), 0.5);
+        assert_eq!(cycles.iter().map(|c| c.2).sum::<f64>(), 4.0);
+    }
+
+    #[test]
+    fn reversals_drop_monotonic_points() {
+        assert_eq!(reversals(&[0.0, 1.0, 2.0, 2.0, 1.0, 3.0]), vec![0.0, 2.0, 1.0, 3.0]);
+    }
+}
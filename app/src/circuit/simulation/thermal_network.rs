@@ -0,0 +1,372 @@
+//! Compact thermal model of placed blocks (approach 1 in `docs/05_thermal_coupling.md`).
+//!
+//! Every block is a node with heat capacity `ρ·c·V`. Blocks that share a face
+//! are linked by the series resistance of their two half-lengths and the contact
+//! between them; exposed faces convect to ambient. The network is the thermal
+//! analogue of a resistor circuit (temperature ↔ voltage, heat flow ↔ current), so
+//! it is solved with the DC solver with ground held at ambient.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use block3d_core::block::Block3DLike;
+use block3d_core::connection::Connector;
+use block3d_core::face::Face;
+use circuit_physics_core::material_properties::MaterialProperties;
+use uom::si::f64::ThermodynamicTemperature;
+use uom::si::{
+    mass_density::kilogram_per_cubic_meter, specific_heat_capacity::joule_per_kilogram_kelvin,
+    thermal_conductivity::watt_per_meter_kelvin, thermodynamic_temperature::kelvin,
+};
+
+use super::dc::{DcError, Element, Netlist};
+
+/// Contact conductance per unit area (W/m²·K) across a shared face, by connector.
+#[derive(Debug, Clone)]
+pub struct ContactConductance {
+    /// Die attach and direct conduction paths.
+    pub bonded: f64,
+    pub thermal_interface: f64,
+    pub heat_sink: f64,
+    /// Solder and other electrical joints.
+    pub electrical: f64,
+    pub mechanical: f64,
+    /// Faces that touch without any compatible connector.
+    pub touching: f64,
+}
+
+impl Default for ContactConductance {
+    fn default() -> Self {
+        Self {
+            bonded: 5e4,
+            thermal_interface: 1e4,
+            heat_sink: 2e4,
+            electrical: 5e3,
+            mechanical: 2e3,
+            touching: 50.0,
+        }
+    }
+}
+
+impl ContactConductance {
+    pub fn for_connector(&self, connector: Option<Connector>) -> f64 {
+        match connector {
+            Some(Connector::ThermalConduction | Connector::DieAttach) => self.bonded,
+            Some(Connector::ThermalInterface) => self.thermal_interface,
+            Some(Connector::HeatSinkAttachment) => self.heat_sink,
+            Some(Connector::ElectricalConnection | Connector::PackageConnection) => self.electrical,
+            Some(Connector::MechanicalBond) => self.mechanical,
+            None => self.touching,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ThermalNetworkConfig {
+    pub ambient: ThermodynamicTemperature,
+    /// Edge length of one grid cell (metres).
+    pub grid_unit: f64,
+    /// Natural convection coefficient on exposed faces (W/m²·K).
+    pub convection_coefficient: f64,
+    pub contact: ContactConductance,
+}
+
+impl Default for ThermalNetworkConfig {
+    fn default() -> Self {
+        Self {
+            ambient: ThermodynamicTemperature::new::<kelvin>(298.15),
+            grid_unit: 1e-3,
+            convection_coefficient: 10.0,
+            contact: ContactConductance::default(),
+        }
+    }
+}
+
+/// A block as seen by the thermal network: its footprint and bulk material.
+#[derive(Debug, Clone)]
+pub struct ThermalBlock<K> {
+    pub key: K,
+    pub size: (u32, u32, u32),
+    /// Grid cells the block occupies, from `Block3DLike::occupied_positions`.
+    pub cells: Vec<(i32, i32, i32)>,
+    pub faces: Vec<Face>,
+    /// W/m·K
+    pub thermal_conductivity: f64,
+    /// kg/m³
+    pub density: f64,
+    /// J/kg·K
+    pub specific_heat: f64,
+}
+
+impl<K> ThermalBlock<K> {
+    pub fn new<B: Block3DLike + MaterialProperties>(key: K, position: (i32, i32, i32), block: &B) -> Self {
+        Self {
+            key,
+            size: block.size(),
+            cells: block.occupied_positions(position),
+            faces: block.faces().collect(),
+            thermal_conductivity: block.thermal_conductivity().get::<watt_per_meter_kelvin>(),
+            density: block.density().get::<kilogram_per_cubic_meter>(),
+            specific_heat: block.specific_heat().get::<joule_per_kilogram_kelvin>(),
+        }
+    }
+
+    fn extent(&self, axis: usize) -> u32 {
+        [self.size.0, self.size.1, self.size.2][axis]
+    }
+
+    fn cells(&self) -> impl Iterator<Item = [i32; 3]> + '_ {
+        self.cells.iter().map(|&(x, y, z)| [x, y, z])
+    }
+
+    /// World cell a face sits on, from the block's minimum corner.
+    fn face_cell(&self, face: &Face) -> [i32; 3] {
+        let [x, y, z] = self.cells().min().unwrap_or_default();
+        let (dx, dy, dz) = face.cell();
+        [x + dx as i32, y + dy as i32, z + dz as i32]
+    }
+
+    /// Most conductive connector formed by faces that actually meet: one of ours
+    /// and one of `other`'s on neighbouring cells, looking at each other.
+    fn connector_with(&self, other: &Self, contact: &ContactConductance) -> Option<Connector> {
+        self.faces
+            .iter()
+            .flat_map(|a| other.faces.iter().map(move |b| (a, b)))
+            .filter(|(a, b)| {
+                let ([x, y, z], normal) = (self.face_cell(a), a.direction().normal());
+                b.direction() == a.direction().opposite() && [x + normal.x, y + normal.y, z + normal.z] == other.face_cell(b)
+            })
+            .filter_map(|(a, b)| (a.oriented_interface() + b.oriented_interface())?.connector)
+            .max_by(|a, b| contact.for_connector(Some(*a)).total_cmp(&contact.for_connector(Some(*b))))
+    }
+}
+
+/// Conduction path between two blocks sharing one or more faces.
+#[derive(Debug, Clone)]
+pub struct ThermalLink {
+    pub a: usize,
+    pub b: usize,
+    pub connector: Option<Connector>,
+    /// Shared face area (m²).
+    pub area: f64,
+    /// W/K
+    pub conductance: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct ThermalNetwork<K> {
+    pub blocks: Vec<ThermalBlock<K>>,
+    pub links: Vec<ThermalLink>,
+    /// Conductance from each block to ambient (W/K).
+    pub ambient_conductance: Vec<f64>,
+    /// Heat capacity of each block (J/K).
+    pub heat_capacity: Vec<f64>,
+    pub ambient: ThermodynamicTemperature,
+}
+
+impl<K: Copy + Eq + Hash> ThermalNetwork<K> {
+    /// Link blocks whose cells touch face-to-face. Overlapping blocks are not detected.
+    pub fn build(blocks: Vec<ThermalBlock<K>>, config: &ThermalNetworkConfig) -> Self {
+        let unit = config.grid_unit;
+        let mut occupancy = HashMap::new();
+        for (index, block) in blocks.iter().enumerate() {
+            for cell in block.cells() {
+                occupancy.insert(cell, index);
+            }
+        }
+
+        // (a, b, axis) -> shared cell faces
+        let mut contacts: HashMap<(usize, usize, usize), u32> = HashMap::new();
+        for (index, block) in blocks.iter().enumerate() {
+            for cell in block.cells() {
+                for axis in 0..3 {
+                    let mut neighbour = cell;
+                    neighbour[axis] += 1;
+                    match occupancy.get(&neighbour) {
+                        Some(&other) if other != index => {
+                            *contacts.entry((index.min(other), index.max(other), axis)).or_default() += 1;
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        let mut contact_area = vec![0.0; blocks.len()];
+        let mut links: Vec<ThermalLink> = Vec::new();
+        let mut contacts: Vec<_> = contacts.into_iter().collect();
+        contacts.sort_unstable_by_key(|&(key, _)| key);
+        for ((a, b, axis), faces) in contacts {
+            let area = f64::from(faces) * unit * unit;
+            let (block_a, block_b) = (&blocks[a], &blocks[b]);
+            let connector = block_a.connector_with(block_b, &config.contact);
+            let half = |block: &ThermalBlock<K>| {
+                0.5 * f64::from(block.extent(axis)) * unit / (block.thermal_conductivity.max(f64::EPSILON) * area)
+            };
+            let resistance = half(block_a) + half(block_b) + 1.0 / (config.contact.for_connector(connector) * area);
+            contact_area[a] += area;
+            contact_area[b] += area;
+            links.push(ThermalLink { a, b, connector, area, conductance: 1.0 / resistance });
+        }
+
+        let ambient_conductance = blocks
+            .iter()
+            .zip(&contact_area)
+            .map(|(block, contact)| {
+                let (w, h, d) = (f64::from(block.size.0), f64::from(block.size.1), f64::from(block.size.2));
+                let surface = 2.0 * (w * h + h * d + w * d) * unit * unit;
+                config.convection_coefficient * (surface - contact).max(0.0)
+            })
+            .collect();
+        let heat_capacity = blocks
+            .iter()
+            .map(|block| {
+                let volume = f64::from(block.size.0 * block.size.1 * block.size.2) * unit.powi(3);
+                block.density * block.specific_heat * volume
+            })
+            .collect();
+
+        Self { blocks, links, ambient_conductance, heat_capacity, ambient: config.ambient }
+    }
+
+    /// Temperature of every block once heat flow has settled.
+    pub fn steady_state(&self, power: &HashMap<K, f64>) -> Result<HashMap<K, ThermodynamicTemperature>, DcError> {
+        let rise = self.solve_rise(power, None)?;
+        Ok(self.temperatures(&rise))
+    }
+
+    /// Backward-Euler transient from `initial` (ambient for missing blocks), returning
+    /// `(time_seconds, temperatures)` after each of `steps` steps of `dt` seconds.
+    pub fn transient(
+        &self,
+        power: &HashMap<K, f64>,
+        initial: &HashMap<K, ThermodynamicTemperature>,
+        dt: f64,
+        steps: usize,
+    ) -> Result<Vec<(f64, HashMap<K, ThermodynamicTemperature>)>, DcError> {
+        let ambient = self.ambient.get::<kelvin>();
+        let mut rise: Vec<f64> = self
+            .blocks
+            .iter()
+            .map(|block| initial.get(&block.key).map_or(0.0, |t| t.get::<kelvin>() - ambient))
+            .collect();
+        let mut samples = Vec::with_capacity(steps);
+        for step in 1..=steps {
+            rise = self.solve_rise(power, Some((dt, &rise)))?;
+            samples.push((step as f64 * dt, self.temperatures(&rise)));
+        }
+        Ok(samples)
+    }
+
+    /// Solve for temperature rise above ambient. With `previous`, each block's heat
+    /// capacity is added as a backward-Euler companion (conductance `C/dt` plus a
+    /// source carrying the stored heat).
+    fn solve_rise(&self, power: &HashMap<K, f64>, previous: Option<(f64, &[f64])>) -> Result<Vec<f64>, DcError> {
+        let mut netlist: Netlist<()> = Netlist::new(self.blocks.len() + 1);
+        for link in &self.links {
+            netlist.add(None, Element::Resistor { a: link.a + 1, b: link.b + 1, ohms: 1.0 / link.conductance });
+        }
+        for (index, block) in self.blocks.iter().enumerate() {
+            let node = index + 1;
+            if self.ambient_conductance[index] > 0.0 {
+                netlist.add(None, Element::Resistor { a: node, b: 0, ohms: 1.0 / self.ambient_conductance[index] });
+            }
+            if let Some(&watts) = power.get(&block.key) {
+                netlist.add(None, Element::CurrentSource { from: 0, to: node, amps: watts });
+            }
+            if let Some((dt, rise)) = previous {
+                let g = self.heat_capacity[index] / dt;
+                netlist.add(None, Element::Resistor { a: node, b: 0, ohms: 1.0 / g });
+                netlist.add(None, Element::CurrentSource { from: 0, to: node, amps: g * rise[index] });
+            }
+        }
+        let solution = netlist.solve()?;
+        Ok(solution.node_voltages[1..].to_vec())
+    }
+
+    fn temperatures(&self, rise: &[f64]) -> HashMap<K, ThermodynamicTemperature> {
+        let ambient = self.ambient.get::<kelvin>();
+        self.blocks
+            .iter()
+            .zip(rise)
+            .map(|(block, &dt)| (block.key, ThermodynamicTemperature::new::<kelvin>(ambient + dt)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use block3d_core::connection::{ConnectorInterface, OrientedInterface};
+    use block3d_core::face::Direction;
+    use block3d_core::orientation::Orientation;
+
+    fn block(key: u32, (x, y, z): (i32, i32, i32)) -> ThermalBlock<u32> {
+        ThermalBlock {
+            key,
+            size: (2, 1, 1),
+            cells: vec![(x, y, z), (x + 1, y, z)],
+            faces: Vec::new(),
+            thermal_conductivity: 10.0,
+            density: 2000.0,
+            specific_heat: 1000.0,
+        }
+    }
+
+    fn rise(temps: &HashMap<u32, ThermodynamicTemperature>, key: u32) -> f64 {
+        temps[&key].get::<kelvin>() - 298.15
+    }
+
+    #[test]
+    fn links_adjacent_blocks_only() {
+        let config = ThermalNetworkConfig::default();
+        let network = ThermalNetwork::build(vec![block(0, (0, 0, 0)), block(1, (2, 0, 0)), block(2, (10, 0, 0))], &config);
+        assert_eq!(network.links.len(), 1);
+        let link = &network.links[0];
+        assert_eq!((link.a, link.b, link.connector), (0, 1, None));
+        assert!((link.area - 1e-6).abs() < 1e-12);
+    }
+
+    #[test]
+    fn only_touching_faces_set_the_connector() {
+        let face = |direction, interface, cell| {
+            Face::new(direction, OrientedInterface { interface, orientation: Orientation::IDENTITY }).at_cell(cell)
+        };
+        let mut left = block(0, (0, 0, 0));
+        left.faces = vec![
+            face(Direction::PosX, ConnectorInterface::MetalPad, (1, 0, 0)),
+            face(Direction::NegX, ConnectorInterface::PassiveSurface, (0, 0, 0)),
+        ];
+        let mut right = block(1, (2, 0, 0));
+        right.faces = vec![
+            face(Direction::NegX, ConnectorInterface::SolderBall, (0, 0, 0)),
+            face(Direction::PosX, ConnectorInterface::ThermalPad, (1, 0, 0)),
+        ];
+        // The passive surface and thermal pad would bond, but they sit on the far ends.
+        let network = ThermalNetwork::build(vec![left, right], &ThermalNetworkConfig::default());
+        assert_eq!(network.links[0].connector, Some(Connector::ElectricalConnection));
+    }
+
+    #[test]
+    fn steady_state_matches_hand_calculation() {
+        let config = ThermalNetworkConfig::default();
+        let network = ThermalNetwork::build(vec![block(0, (0, 0, 0))], &config);
+        let temps = network.steady_state(&HashMap::from([(0, 0.01)])).unwrap();
+        // Surface 2·(2 + 1 + 2) mm² = 10 mm², G = 10 W/m²K · 1e-5 m² = 1e-4 W/K.
+        assert!((rise(&temps, 0) - 100.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn neighbour_heats_up_and_transient_approaches_steady_state() {
+        let config = ThermalNetworkConfig::default();
+        let network = ThermalNetwork::build(vec![block(0, (0, 0, 0)), block(1, (2, 0, 0))], &config);
+        let power = HashMap::from([(0, 0.01)]);
+        let steady = network.steady_state(&power).unwrap();
+        assert!(rise(&steady, 0) > rise(&steady, 1) && rise(&steady, 1) > 0.0);
+
+        let samples = network.transient(&power, &HashMap::new(), 10.0, 200).unwrap();
+        let (_, last) = samples.last().unwrap();
+        assert!(rise(&samples[0].1, 0) < rise(last, 0));
+        assert!((rise(last, 0) - rise(&steady, 0)).abs() < 0.1);
+    }
+}
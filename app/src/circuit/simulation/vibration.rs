@@ -0,0 +1,316 @@
+//! Vibration and shock response of parts on the assembly.
+//!
+//! The assembly is shaken at its mounts by a sine sweep or a random acceleration
+//! PSD. Each part is a single-degree-of-freedom resonator (`MechanicalStress`
+//! resonance and Q) sitting on an optional board resonance, so its response is
+//! the input filtered by the base-excitation transmissibility of both stages.
+//! Half-sine shocks are integrated through the same chain in the time domain.
+//! Parts without a resonance are treated as rigid.
+
+use bevy::prelude::Entity;
+use std::f64::consts::PI;
+
+use circuit_physics_core::mechanical::MechanicalStress;
+use uom::si::f64::{Acceleration, Frequency, Time};
+use uom::si::{acceleration::standard_gravity, frequency::hertz, time::second};
+
+use super::netlist::CircuitSnapshot;
+
+/// Base excitation applied to the assembly.
+#[derive(Debug, Clone)]
+pub enum VibrationProfile {
+    /// Sine sweep at constant peak acceleration between two frequencies.
+    SineSweep { start: Frequency, end: Frequency, peak: Acceleration },
+    /// Random vibration as `(frequency, g²/Hz)` breakpoints, interpolated log-log
+    /// and zero outside the first and last breakpoint.
+    Random { psd: Vec<(Frequency, f64)> },
+}
+
+impl VibrationProfile {
+    /// NAVMAT P-9492 random screening profile (6.06 g RMS).
+    pub fn navmat() -> Self {
+        let psd = [(20.0, 0.01), (80.0, 0.04), (350.0, 0.04), (2000.0, 0.007)];
+        Self::Random { psd: psd.map(|(hz, g2)| (Frequency::new::<hertz>(hz), g2)).to_vec() }
+    }
+
+    /// Input PSD (g²/Hz) at `hz`.
+    fn psd_at(psd: &[(Frequency, f64)], hz: f64) -> f64 {
+        let (Some((first, _)), Some((last, _))) = (psd.first(), psd.last()) else {
+            return 0.0;
+        };
+        if psd.len() < 2 || hz < first.get::<hertz>() || hz > last.get::<hertz>() {
+            return 0.0;
+        }
+        let upper = psd.partition_point(|(f, _)| f.get::<hertz>() < hz).clamp(1, psd.len() - 1);
+        let (f0, g0) = (psd[upper - 1].0.get::<hertz>(), psd[upper - 1].1);
+        let (f1, g1) = (psd[upper].0.get::<hertz>(), psd[upper].1);
+        if g0 <= 0.0 || g1 <= 0.0 {
+            return g0 + (g1 - g0) * (hz - f0) / (f1 - f0);
+        }
+        let slope = (g1 / g0).ln() / (f1 / f0).ln();
+        g0 * (hz / f0).powf(slope)
+    }
+}
+
+/// Half-sine shock pulse at the mounts.
+#[derive(Debug, Clone, Copy)]
+pub struct HalfSineShock {
+    pub peak: Acceleration,
+    pub duration: Time,
+}
+
+#[derive(Debug, Clone)]
+pub struct VibrationConfig {
+    pub profile: VibrationProfile,
+    pub shock: Option<HalfSineShock>,
+    /// First board mode `(frequency, Q)` between the mounts and the parts; `None` for a rigid board.
+    pub assembly: Option<(Frequency, f64)>,
+    /// Peak-to-RMS factor for random vibration (3σ by default).
+    pub peak_factor: f64,
+}
+
+impl Default for VibrationConfig {
+    fn default() -> Self {
+        Self {
+            profile: VibrationProfile::navmat(),
+            shock: Some(HalfSineShock {
+                peak: Acceleration::new::<standard_gravity>(50.0),
+                duration: Time::new::<second>(11e-3),
+            }),
+            assembly: None,
+            peak_factor: 3.0,
+        }
+    }
+}
+
+/// A base-excited single-degree-of-freedom oscillator.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Resonator {
+    /// Natural frequency (Hz).
+    pub frequency: f64,
+    pub q: f64,
+}
+
+impl Resonator {
+    /// Ratio of absolute response to base acceleration at `hz`.
+    pub fn transmissibility(&self, hz: f64) -> f64 {
+        let r = hz / self.frequency;
+        // 2ζr with ζ = 1 / 2Q.
+        let damping = r / self.q;
+        ((1.0 + damping * damping) / ((1.0 - r * r).powi(2) + damping * damping)).sqrt()
+    }
+}
+
+/// Peak sine response (g) of a resonator chain swept from `start` to `end` Hz at `peak` g.
+pub fn sine_response(chain: &[Resonator], start: f64, end: f64, peak: f64) -> f64 {
+    frequency_grid(chain, start, end)
+        .into_iter()
+        .map(|hz| chain.iter().map(|r| r.transmissibility(hz)).product::<f64>())
+        .fold(0.0, f64::max)
+        * peak
+}
+
+/// RMS response (g) of a resonator chain to a random input PSD.
+pub fn random_response(chain: &[Resonator], psd: &[(Frequency, f64)]) -> f64 {
+    let (Some((first, _)), Some((last, _))) = (psd.first(), psd.last()) else {
+        return 0.0;
+    };
+    let grid = frequency_grid(chain, first.get::<hertz>(), last.get::<hertz>());
+    let response = |hz: f64| {
+        let gain: f64 = chain.iter().map(|r| r.transmissibility(hz)).product();
+        VibrationProfile::psd_at(psd, hz) * gain * gain
+    };
+    let area: f64 = grid.windows(2).map(|w| (w[1] - w[0]) * (response(w[0]) + response(w[1])) / 2.0).sum();
+    area.sqrt()
+}
+
+/// Log-spaced frequencies over `[start, end]`, refined around each resonance.
+fn frequency_grid(chain: &[Resonator], start: f64, end: f64) -> Vec<f64> {
+    const POINTS: usize = 2000;
+    let (start, end) = (start.max(f64::MIN_POSITIVE), end.max(start));
+    let ratio = (end / start).powf(1.0 / POINTS as f64);
+    let mut grid: Vec<f64> = (0..=POINTS).map(|i| start * ratio.powi(i as i32)).collect();
+    for resonator in chain {
+        // Half-power bandwidth is f/Q; sample it finely.
+        let half_width = resonator.frequency / resonator.q;
+        grid.extend((-50..=50).map(|i| resonator.frequency + half_width * f64::from(i) / 25.0));
+    }
+    grid.retain(|hz| (start..=end).contains(hz));
+    grid.sort_by(f64::total_cmp);
+    grid.dedup();
+    grid
+}
+
+/// Peak absolute acceleration (g) at the end of a resonator chain for a half-sine
+/// base pulse, integrated with RK4 until well after the pulse.
+pub fn shock_response(chain: &[Resonator], peak: f64, duration: f64) -> f64 {
+    if chain.is_empty() {
+        return peak;
+    }
+    let omegas: Vec<(f64, f64)> = chain.iter().map(|r| (2.0 * PI * r.frequency, 1.0 / (2.0 * r.q))).collect();
+    let fastest = omegas.iter().map(|(w, _)| *w).fold(0.0, f64::max);
+    let slowest = omegas.iter().map(|(w, _)| *w).fold(f64::INFINITY, f64::min);
+    let dt = (duration / 200.0).min(2.0 * PI / fastest / 50.0);
+    let end = duration + 3.0 * 2.0 * PI / slowest;
+    let base = |t: f64| if t < duration { peak * (PI * t / duration).sin() } else { 0.0 };
+
+    // State: relative displacement and velocity of each stage to the stage below.
+    // Each stage's absolute acceleration is the base input of the next.
+    let derivative = |t: f64, state: &[f64]| {
+        let mut input = base(t);
+        let mut out = vec![0.0; state.len()];
+        for (stage, &(omega, zeta)) in omegas.iter().enumerate() {
+            let (z, v) = (state[2 * stage], state[2 * stage + 1]);
+            let restoring = 2.0 * zeta * omega * v + omega * omega * z;
+            out[2 * stage] = v;
+            out[2 * stage + 1] = -restoring - input;
+            input = -restoring;
+        }
+        (out, input)
+    };
+
+    let mut state = vec![0.0; 2 * chain.len()];
+    let mut max: f64 = 0.0;
+    let mut t = 0.0;
+    while t < end {
+        let (k1, response) = derivative(t, &state);
+        max = max.max(response.abs());
+        let step = |k: &[f64], h: f64| state.iter().zip(k).map(|(s, k)| s + h * k).collect::<Vec<_>>();
+        let (k2, _) = derivative(t + dt / 2.0, &step(&k1, dt / 2.0));
+        let (k3, _) = derivative(t + dt / 2.0, &step(&k2, dt / 2.0));
+        let (k4, _) = derivative(t + dt, &step(&k3, dt));
+        for (i, s) in state.iter_mut().enumerate() {
+            *s += dt / 6.0 * (k1[i] + 2.0 * k2[i] + 2.0 * k3[i] + k4[i]);
+        }
+        t += dt;
+    }
+    max
+}
+
+/// Peak response of a part to its rated shock pulse, applied straight to it.
+/// The rating holds for its own pulse length only: a pulse near the part's
+/// natural period loads it harder than its peak, a much shorter one less.
+pub fn withstood_shock(resonance: Option<Resonator>, (peak, duration): (Acceleration, Time)) -> Acceleration {
+    let g = shock_response(resonance.as_slice(), peak.get::<standard_gravity>(), duration.get::<second>());
+    Acceleration::new::<standard_gravity>(g)
+}
+
+#[derive(Debug, Clone)]
+pub struct PartVibration {
+    pub entity: Entity,
+    pub resonance: Option<Resonator>,
+    /// Peak response to the vibration profile (3σ for random).
+    pub response: Acceleration,
+    pub shock_response: Option<Acceleration>,
+    /// Peak response the part withstands in its rated shock test, see [`withstood_shock`].
+    pub shock_rating: Option<Acceleration>,
+    /// Worst of `max_acceleration / response` and `shock_rating / shock_response`.
+    pub margin: f64,
+    pub survives: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct VibrationReport {
+    /// Sorted by margin, smallest first.
+    pub parts: Vec<PartVibration>,
+}
+
+impl VibrationReport {
+    pub fn analyse(snapshot: &CircuitSnapshot, config: &VibrationConfig) -> Self {
+        let board = config.assembly.map(|(frequency, q)| Resonator { frequency: frequency.get::<hertz>(), q });
+        let mut parts: Vec<_> = snapshot
+            .parts
+            .iter()
+            .map(|terminals| {
+                let part = terminals.physics();
+                let resonance = part
+                    .resonant_frequency()
+                    .map(|f| Resonator { frequency: f.get::<hertz>(), q: part.mechanical_q_factor() });
+                let chain: Vec<_> = board.into_iter().chain(resonance).collect();
+
+                let g = match &config.profile {
+                    VibrationProfile::SineSweep { start, end, peak } => sine_response(
+                        &chain,
+                        start.get::<hertz>(),
+                        end.get::<hertz>(),
+                        peak.get::<standard_gravity>(),
+                    ),
+                    VibrationProfile::Random { psd } => config.peak_factor * random_response(&chain, psd),
+                };
+                let response = Acceleration::new::<standard_gravity>(g);
+                let mut margin = (part.max_acceleration() / response).value;
+                let mut survives = part.can_survive_acceleration(response);
+
+                let shock = config.shock.map(|shock| {
+                    let g = shock_response(&chain, shock.peak.get::<standard_gravity>(), shock.duration.get::<second>());
+                    (Acceleration::new::<standard_gravity>(g), withstood_shock(resonance, part.shock_resistance()))
+                });
+                if let Some((shock, withstood)) = shock {
+                    margin = margin.min((withstood / shock).value);
+                    survives &= shock <= withstood;
+                }
+                PartVibration {
+                    entity: terminals.entity,
+                    resonance,
+                    response,
+                    shock_response: shock.map(|(shock, _)| shock),
+                    shock_rating: shock.map(|(_, withstood)| withstood),
+                    margin,
+                    survives,
+                }
+            })
+            .collect();
+        parts.sort_by(|a, b| a.margin.total_cmp(&b.margin));
+        Self { parts }
+    }
+
+    /// Parts that exceed their acceleration or shock rating.
+    pub fn failures(&self) -> impl Iterator<Item = &PartVibration> {
+        self.parts.iter().filter(|p| !p.survives)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PART: Resonator = Resonator { frequency: 200.0, q: 10.0 };
+
+    #[test]
+    fn sine_sweep_through_resonance_amplifies_by_q() {
+        let response = sine_response(&[PART], 20.0, 2000.0, 1.0);
+        assert!((response - 10.05).abs() < 0.05, "{response}");
+        // Well below resonance the part follows the input.
+        assert!((sine_response(&[PART], 5.0, 10.0, 1.0) - 1.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn random_response_matches_miles() {
+        let psd = vec![(Frequency::new::<hertz>(10.0), 0.04), (Frequency::new::<hertz>(5000.0), 0.04)];
+        let grms = random_response(&[PART], &psd);
+        let miles = (PI / 2.0 * PART.frequency * PART.q * 0.04).sqrt();
+        assert!((grms - miles).abs() / miles < 0.05, "{grms} vs {miles}");
+    }
+
+    #[test]
+    fn shock_response_depends_on_pulse_length() {
+        // A pulse much longer than the natural period is quasi-static.
+        let slow = shock_response(&[PART], 1.0, 50e-3);
+        assert!((slow - 1.0).abs() < 0.15, "{slow}");
+        // A much shorter one barely excites the part.
+        let fast = shock_response(&[PART], 1.0, 0.05e-3);
+        assert!(fast < 0.1, "{fast}");
+        // Near the natural period the response overshoots.
+        assert!(shock_response(&[PART], 1.0, 1.0 / PART.frequency) > 1.3);
+    }
+
+    #[test]
+    fn shock_ratings_hold_for_their_own_pulse_length() {
+        let g = Acceleration::new::<standard_gravity>;
+        // 100 g over 0.1 ms barely moves a 200 Hz part, so a long 50 g pulse is worse.
+        let short = withstood_shock(Some(PART), (g(100.0), Time::new::<second>(0.1e-3)));
+        assert!(short < g(50.0), "{:?}", short);
+        // A rigid part feels the pulse peak whatever its length.
+        assert_eq!(withstood_shock(None, (g(100.0), Time::new::<second>(0.1e-3))), g(100.0));
+    }
+}
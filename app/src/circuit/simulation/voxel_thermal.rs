@@ -0,0 +1,524 @@
+//! Finite-volume heat conduction on a voxel grid (approach 2 in `docs/05_thermal_coupling.md`).
+//!
+//! Each grid cell occupied by a block is split into `subdivisions³` voxels that
+//! take the block's bulk material. Neighbouring voxels exchange heat through the
+//! harmonic-mean conductance of their half-widths; faces with no solid neighbour
+//! see the exterior boundary condition. The conductance matrix is symmetric
+//! positive definite, so the system is solved with Jacobi-preconditioned
+//! conjugate gradients; the matrix-vector products run on `rayon`.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use rayon::prelude::*;
+use thiserror::Error;
+use uom::si::f64::ThermodynamicTemperature;
+use uom::si::thermodynamic_temperature::kelvin;
+
+use super::thermal_network::ThermalBlock;
+
+#[derive(Debug, Clone, Copy)]
+pub enum BoundaryCondition {
+    /// Face held at a fixed temperature.
+    Dirichlet(ThermodynamicTemperature),
+    /// Convection to a fluid at `ambient` with film coefficient `coefficient` (W/m²·K).
+    Convective { coefficient: f64, ambient: ThermodynamicTemperature },
+}
+
+impl BoundaryCondition {
+    fn temperature(self) -> f64 {
+        match self {
+            BoundaryCondition::Dirichlet(t) | BoundaryCondition::Convective { ambient: t, .. } => t.get::<kelvin>(),
+        }
+    }
+
+    /// Conductance from a voxel centre, through `half_width` of material, to the boundary.
+    fn conductance(self, conductivity: f64, half_width: f64, area: f64) -> f64 {
+        let conduction = half_width / conductivity;
+        match self {
+            BoundaryCondition::Dirichlet(_) => area / conduction,
+            BoundaryCondition::Convective { coefficient, .. } => area / (conduction + 1.0 / coefficient),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct VoxelThermalConfig {
+    /// Edge length of one grid cell (metres).
+    pub grid_unit: f64,
+    /// Voxels per grid cell along each axis.
+    pub subdivisions: u32,
+    /// Applied to every voxel face that doesn't touch another voxel.
+    pub exterior: BoundaryCondition,
+    /// Conjugate-gradient iterations allowed per solve.
+    pub max_iterations: usize,
+    /// Stop once the heat imbalance `‖b − A·T‖` is this fraction of the heat
+    /// injected `‖b‖`, measured on the true residual.
+    pub tolerance: f64,
+}
+
+impl Default for VoxelThermalConfig {
+    fn default() -> Self {
+        Self {
+            grid_unit: 1e-3,
+            subdivisions: 2,
+            exterior: BoundaryCondition::Convective {
+                coefficient: 10.0,
+                ambient: ThermodynamicTemperature::new::<kelvin>(298.15),
+            },
+            max_iterations: 10_000,
+            tolerance: 1e-10,
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum VoxelThermalError {
+    #[error("no blocks to mesh")]
+    Empty,
+    #[error("voxel solve did not converge in {iterations} iterations (relative residual {residual})")]
+    NotConverged { iterations: usize, residual: f64 },
+}
+
+#[derive(Debug, Clone)]
+struct Voxel {
+    /// Index of the owning block.
+    owner: usize,
+    /// Dense grid index.
+    cell: usize,
+    neighbours: Vec<(usize, f64)>,
+    boundary_conductance: f64,
+    heat_capacity: f64,
+}
+
+/// Voxel mesh of a set of blocks, ready to solve.
+#[derive(Debug, Clone)]
+pub struct VoxelModel<K> {
+    keys: Vec<K>,
+    origin: [i32; 3],
+    dims: [usize; 3],
+    voxel_size: f64,
+    voxels: Vec<Voxel>,
+    /// Voxel count of each block, for spreading its power.
+    block_volume: Vec<usize>,
+    /// Voxels held at a fixed temperature (kelvin).
+    fixed: Vec<Option<f64>>,
+    boundary_temperature: f64,
+    max_iterations: usize,
+    tolerance: f64,
+}
+
+impl<K: Copy + Eq + Hash + Send + Sync> VoxelModel<K> {
+    pub fn build(blocks: &[ThermalBlock<K>], config: &VoxelThermalConfig) -> Result<Self, VoxelThermalError> {
+        let sub = config.subdivisions.max(1) as i32;
+        let voxel_size = config.grid_unit / f64::from(sub);
+        let area = voxel_size * voxel_size;
+        let volume = area * voxel_size;
+
+        let cells = || blocks.iter().enumerate().flat_map(|(owner, block)| block.cells.iter().map(move |&cell| (owner, cell)));
+        let (mut min, mut max) = ([i32::MAX; 3], [i32::MIN; 3]);
+        for (_, (x, y, z)) in cells() {
+            for (axis, value) in [x, y, z].into_iter().enumerate() {
+                min[axis] = min[axis].min(value);
+                max[axis] = max[axis].max(value);
+            }
+        }
+        if min[0] > max[0] {
+            return Err(VoxelThermalError::Empty);
+        }
+        let origin = min.map(|v| v * sub);
+        let dims: [usize; 3] = std::array::from_fn(|axis| ((max[axis] - min[axis] + 1) * sub) as usize);
+        let flat = |v: [i32; 3]| -> Option<usize> {
+            let local: [i32; 3] = std::array::from_fn(|axis| v[axis] - origin[axis]);
+            (0..3).all(|axis| (0..dims[axis] as i32).contains(&local[axis]))
+                .then(|| local[0] as usize + dims[0] * (local[1] as usize + dims[1] * local[2] as usize))
+        };
+
+        // Dense grid -> (owner, voxel index)
+        let mut grid: Vec<Option<(usize, usize)>> = vec![None; dims.iter().product()];
+        let mut voxels = Vec::new();
+        let mut block_volume = vec![0; blocks.len()];
+        for (owner, (x, y, z)) in cells() {
+            for d in 0..sub * sub * sub {
+                let v = [x * sub + d % sub, y * sub + (d / sub) % sub, z * sub + d / (sub * sub)];
+                let cell = flat(v).expect("voxel inside bounds");
+                if grid[cell].is_some() {
+                    continue;
+                }
+                grid[cell] = Some((owner, voxels.len()));
+                block_volume[owner] += 1;
+                let block = &blocks[owner];
+                voxels.push(Voxel {
+                    owner,
+                    cell,
+                    neighbours: Vec::new(),
+                    boundary_conductance: 0.0,
+                    heat_capacity: block.density * block.specific_heat * volume,
+                });
+            }
+        }
+
+        let half = 0.5 * voxel_size;
+        let conductivity = |owner: usize| blocks[owner].thermal_conductivity.max(f64::EPSILON);
+        let index_of = |cell: usize| -> [i32; 3] {
+            [(cell % dims[0]) as i32, ((cell / dims[0]) % dims[1]) as i32, (cell / (dims[0] * dims[1])) as i32]
+        };
+        let links: Vec<(Vec<(usize, f64)>, f64)> = voxels
+            .par_iter()
+            .map(|voxel| {
+                let k = conductivity(voxel.owner);
+                let local = index_of(voxel.cell);
+                let mut neighbours = Vec::with_capacity(6);
+                let mut boundary = 0.0;
+                for axis in 0..3 {
+                    for step in [-1, 1] {
+                        let mut v: [i32; 3] = std::array::from_fn(|a| local[a] + origin[a]);
+                        v[axis] += step;
+                        match flat(v).and_then(|cell| grid[cell]) {
+                            Some((owner, index)) => {
+                                let g = area / (half / k + half / conductivity(owner));
+                                neighbours.push((index, g));
+                            }
+                            None => boundary += config.exterior.conductance(k, half, area),
+                        }
+                    }
+                }
+                (neighbours, boundary)
+            })
+            .collect();
+        for (voxel, (neighbours, boundary)) in voxels.iter_mut().zip(links) {
+            voxel.neighbours = neighbours;
+            voxel.boundary_conductance = boundary;
+        }
+
+        Ok(Self {
+            keys: blocks.iter().map(|block| block.key).collect(),
+            origin,
+            dims,
+            voxel_size,
+            fixed: vec![None; voxels.len()],
+            voxels,
+            block_volume,
+            boundary_temperature: config.exterior.temperature(),
+            max_iterations: config.max_iterations,
+            tolerance: config.tolerance,
+        })
+    }
+
+    /// Hold every voxel of `key` at `temperature` (e.g. a heat sink or cold plate).
+    pub fn fix_block(&mut self, key: K, temperature: ThermodynamicTemperature) -> &mut Self {
+        if let Some(owner) = self.keys.iter().position(|k| *k == key) {
+            for (voxel, fixed) in self.voxels.iter().zip(&mut self.fixed) {
+                if voxel.owner == owner {
+                    *fixed = Some(temperature.get::<kelvin>());
+                }
+            }
+        }
+        self
+    }
+
+    pub fn voxel_count(&self) -> usize {
+        self.voxels.len()
+    }
+
+    /// Steady-state temperature field for the given power per block (watts).
+    pub fn steady_state(&self, power: &HashMap<K, f64>) -> Result<TemperatureField<K>, VoxelThermalError> {
+        let temperatures = self.solve(power, vec![0.0; self.voxels.len()], None)?;
+        Ok(self.field(&temperatures))
+    }
+
+    /// Advance `field` by one backward-Euler step of `dt` seconds.
+    pub fn step(&self, field: &TemperatureField<K>, power: &HashMap<K, f64>, dt: f64) -> Result<TemperatureField<K>, VoxelThermalError> {
+        let previous: Vec<f64> = self
+            .voxels
+            .iter()
+            .map(|voxel| field.values[voxel.cell].unwrap_or(self.boundary_temperature))
+            .collect();
+        let initial = previous.iter().map(|t| t - self.boundary_temperature).collect();
+        let temperatures = self.solve(power, initial, Some((dt, &previous)))?;
+        Ok(self.field(&temperatures))
+    }
+
+    /// Solves `Σ G (Tj − Ti) + Gb (Tb − Ti) + P = C/dt (Ti − Ti_prev)` for the free
+    /// voxels, in rise above the boundary temperature `Tb` so small rises keep their
+    /// precision. `rise` is the starting guess; the transient term is dropped
+    /// without `previous`.
+    fn solve(&self, power: &HashMap<K, f64>, mut rise: Vec<f64>, previous: Option<(f64, &[f64])>) -> Result<Vec<f64>, VoxelThermalError> {
+        let boundary = self.boundary_temperature;
+        let source: Vec<f64> = self
+            .keys
+            .iter()
+            .zip(&self.block_volume)
+            .map(|(key, &count)| power.get(key).map_or(0.0, |watts| watts / count.max(1) as f64))
+            .collect();
+        let capacity = |i: usize| previous.map_or(0.0, |(dt, _)| self.voxels[i].heat_capacity / dt);
+
+        // Fixed voxels are not unknowns: their rows are zero and their heat flow
+        // into free neighbours moves to the right-hand side.
+        let diagonal: Vec<f64> = self
+            .voxels
+            .par_iter()
+            .enumerate()
+            .map(|(i, voxel)| voxel.boundary_conductance + voxel.neighbours.iter().map(|&(_, g)| g).sum::<f64>() + capacity(i))
+            .collect();
+        let rhs: Vec<f64> = self
+            .voxels
+            .par_iter()
+            .enumerate()
+            .map(|(i, voxel)| {
+                if self.fixed[i].is_some() {
+                    return 0.0;
+                }
+                let stored = previous.map_or(0.0, |(_, previous)| capacity(i) * (previous[i] - boundary));
+                let held: f64 = voxel
+                    .neighbours
+                    .iter()
+                    .filter_map(|&(j, g)| self.fixed[j].map(|t| g * (t - boundary)))
+                    .sum();
+                source[voxel.owner] + stored + held
+            })
+            .collect();
+        for (i, fixed) in self.fixed.iter().enumerate() {
+            if fixed.is_some() {
+                rise[i] = 0.0;
+            }
+        }
+
+        let apply = |x: &[f64], out: &mut [f64]| {
+            out.par_iter_mut().enumerate().for_each(|(i, out)| {
+                *out = if self.fixed[i].is_some() {
+                    0.0
+                } else {
+                    let coupled: f64 = self.voxels[i]
+                        .neighbours
+                        .iter()
+                        .filter(|&&(j, _)| self.fixed[j].is_none())
+                        .map(|&(j, g)| g * x[j])
+                        .sum();
+                    diagonal[i] * x[i] - coupled
+                };
+            });
+        };
+        let precondition = |r: &[f64]| -> Vec<f64> {
+            r.par_iter().zip(&diagonal).map(|(r, d)| if *d > 0.0 { r / d } else { *r }).collect()
+        };
+        let residual_of = |x: &[f64], out: &mut Vec<f64>| {
+            apply(x, out);
+            out.par_iter_mut().zip(&rhs).for_each(|(ax, b)| *ax = b - *ax);
+        };
+
+        let target = self.tolerance * dot(&rhs, &rhs).sqrt();
+        let mut r = vec![0.0; rise.len()];
+        residual_of(&rise, &mut r);
+        let mut z = precondition(&r);
+        let mut p = z.clone();
+        let mut rz = dot(&r, &z);
+        let mut q = vec![0.0; rise.len()];
+
+        for _ in 0..self.max_iterations {
+            if dot(&r, &r).sqrt() <= target {
+                // The recurrence drifts from the true residual; only stop on the latter
+                residual_of(&rise, &mut r);
+                if dot(&r, &r).sqrt() <= target {
+                    return Ok(self.temperatures(&rise));
+                }
+                z = precondition(&r);
+                p.clone_from(&z);
+                rz = dot(&r, &z);
+            }
+            apply(&p, &mut q);
+            let curvature = dot(&p, &q);
+            if curvature <= 0.0 {
+                break;
+            }
+            let alpha = rz / curvature;
+            rise.par_iter_mut().zip(&p).for_each(|(x, p)| *x += alpha * p);
+            r.par_iter_mut().zip(&q).for_each(|(r, q)| *r -= alpha * q);
+            z = precondition(&r);
+            let next = dot(&r, &z);
+            let beta = next / rz;
+            rz = next;
+            p.par_iter_mut().zip(&z).for_each(|(p, z)| *p = z + beta * *p);
+        }
+
+        residual_of(&rise, &mut r);
+        let residual = dot(&r, &r).sqrt();
+        if residual <= target {
+            return Ok(self.temperatures(&rise));
+        }
+        let injected = dot(&rhs, &rhs).sqrt();
+        Err(VoxelThermalError::NotConverged {
+            iterations: self.max_iterations,
+            residual: if injected > 0.0 { residual / injected } else { residual },
+        })
+    }
+
+    /// Absolute voxel temperatures (kelvin) from rises above the boundary.
+    fn temperatures(&self, rise: &[f64]) -> Vec<f64> {
+        self.fixed
+            .iter()
+            .zip(rise)
+            .map(|(fixed, rise)| fixed.unwrap_or(self.boundary_temperature + rise))
+            .collect()
+    }
+
+    fn field(&self, temperatures: &[f64]) -> TemperatureField<K> {
+        let mut values = vec![None; self.dims.iter().product()];
+        let mut owners = vec![None; values.len()];
+        for (voxel, &t) in self.voxels.iter().zip(temperatures) {
+            values[voxel.cell] = Some(t);
+            owners[voxel.cell] = Some(voxel.owner);
+        }
+        TemperatureField {
+            keys: self.keys.clone(),
+            origin: self.origin,
+            dims: self.dims,
+            voxel_size: self.voxel_size,
+            values,
+            owners,
+        }
+    }
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.par_iter().zip(b).map(|(a, b)| a * b).sum()
+}
+
+/// Min, mean and max voxel temperature of one block.
+#[derive(Debug, Clone, Copy)]
+pub struct BlockTemperatureStats {
+    pub min: ThermodynamicTemperature,
+    pub mean: ThermodynamicTemperature,
+    pub max: ThermodynamicTemperature,
+}
+
+/// Solved temperature per voxel (kelvin), `None` where there is no material.
+#[derive(Debug, Clone)]
+pub struct TemperatureField<K> {
+    keys: Vec<K>,
+    /// Voxel coordinates of the grid's minimum corner.
+    pub origin: [i32; 3],
+    pub dims: [usize; 3],
+    /// Voxel edge length (metres).
+    pub voxel_size: f64,
+    values: Vec<Option<f64>>,
+    owners: Vec<Option<usize>>,
+}
+
+impl<K: Copy + Eq + Hash> TemperatureField<K> {
+    /// Temperature of the voxel at `voxel` (in voxel coordinates, see `origin`).
+    pub fn at(&self, voxel: [i32; 3]) -> Option<ThermodynamicTemperature> {
+        let local: [i32; 3] = std::array::from_fn(|axis| voxel[axis] - self.origin[axis]);
+        if !(0..3).all(|axis| (0..self.dims[axis] as i32).contains(&local[axis])) {
+            return None;
+        }
+        let index = local[0] as usize + self.dims[0] * (local[1] as usize + self.dims[1] * local[2] as usize);
+        self.values[index].map(ThermodynamicTemperature::new::<kelvin>)
+    }
+
+    /// Coldest and hottest voxel (kelvin).
+    pub fn range(&self) -> Option<(f64, f64)> {
+        self.values.iter().flatten().fold(None, |range, &t| match range {
+            None => Some((t, t)),
+            Some((lo, hi)) => Some((lo.min(t), hi.max(t))),
+        })
+    }
+
+    /// Temperature statistics per block, for colouring blocks by their hot spot or average.
+    pub fn block_stats(&self) -> HashMap<K, BlockTemperatureStats> {
+        let mut acc = vec![(f64::INFINITY, 0.0, f64::NEG_INFINITY, 0_usize); self.keys.len()];
+        for (value, owner) in self.values.iter().zip(&self.owners) {
+            let (Some(t), Some(owner)) = (value, owner) else { continue; };
+            let (min, sum, max, count) = &mut acc[*owner];
+            *min = min.min(*t);
+            *max = max.max(*t);
+            *sum += *t;
+            *count += 1;
+        }
+        self.keys
+            .iter()
+            .zip(acc)
+            .filter(|(_, (_, _, _, count))| *count > 0)
+            .map(|(&key, (min, sum, max, count))| {
+                (key, BlockTemperatureStats {
+                    min: ThermodynamicTemperature::new::<kelvin>(min),
+                    mean: ThermodynamicTemperature::new::<kelvin>(sum / count as f64),
+                    max: ThermodynamicTemperature::new::<kelvin>(max),
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bar(key: u32, cells: Vec<(i32, i32, i32)>) -> ThermalBlock<u32> {
+        ThermalBlock {
+            key,
+            size: (cells.len() as u32, 1, 1),
+            cells,
+            faces: Vec::new(),
+            thermal_conductivity: 100.0,
+            density: 2000.0,
+            specific_heat: 1000.0,
+        }
+    }
+
+    fn kelvin_of(t: ThermodynamicTemperature) -> f64 {
+        t.get::<kelvin>()
+    }
+
+    #[test]
+    fn uniform_block_without_power_stays_at_ambient() {
+        let model = VoxelModel::build(&[bar(0, vec![(0, 0, 0), (1, 0, 0)])], &VoxelThermalConfig::default()).unwrap();
+        assert_eq!(model.voxel_count(), 16);
+        let field = model.steady_state(&HashMap::new()).unwrap();
+        let (lo, hi) = field.range().unwrap();
+        assert!((lo - 298.15).abs() < 1e-3 && (hi - 298.15).abs() < 1e-3);
+    }
+
+    #[test]
+    fn energy_balance_matches_convective_loss() {
+        let config = VoxelThermalConfig { subdivisions: 1, ..VoxelThermalConfig::default() };
+        let model = VoxelModel::build(&[bar(0, vec![(0, 0, 0)])], &config).unwrap();
+        let field = model.steady_state(&HashMap::from([(0, 1e-3)])).unwrap();
+        // Single voxel: six faces of 1 mm² through 0.5 mm of k = 100 plus h = 10.
+        let g = 6.0 * 1e-6 / (0.5e-3 / 100.0 + 0.1);
+        let rise = kelvin_of(field.at([0, 0, 0]).unwrap()) - 298.15;
+        assert!((rise - 1e-3 / g).abs() < 1e-2);
+    }
+
+    #[test]
+    fn convected_heat_balances_injected_power() {
+        // Boundary films are ~2e4 times weaker than voxel-to-voxel links here.
+        let model = VoxelModel::build(&[bar(0, vec![(0, 0, 0)]), bar(1, vec![(1, 0, 0), (1, 1, 0)])], &VoxelThermalConfig::default()).unwrap();
+        assert_eq!(model.voxel_count(), 24);
+        let field = model.steady_state(&HashMap::from([(0, 0.01)])).unwrap();
+
+        let convected: f64 = model
+            .voxels
+            .iter()
+            .map(|voxel| voxel.boundary_conductance * (field.values[voxel.cell].unwrap() - 298.15))
+            .sum();
+        assert!((convected - 0.01).abs() < 1e-9, "convected {convected} W");
+        let stats = field.block_stats();
+        assert!(kelvin_of(stats[&0].mean) > kelvin_of(stats[&1].mean));
+    }
+
+    #[test]
+    fn heat_flows_from_source_to_fixed_sink() {
+        let config = VoxelThermalConfig::default();
+        let mut model = VoxelModel::build(&[bar(0, vec![(0, 0, 0)]), bar(1, vec![(1, 0, 0)])], &config).unwrap();
+        model.fix_block(1, ThermodynamicTemperature::new::<kelvin>(300.0));
+        let field = model.steady_state(&HashMap::from([(0, 0.1)])).unwrap();
+        let stats = field.block_stats();
+        assert!((kelvin_of(stats[&1].mean) - 300.0).abs() < 1e-4);
+        assert!(kelvin_of(stats[&0].min) > 300.0);
+        assert!(kelvin_of(stats[&0].max) > kelvin_of(stats[&0].min));
+
+        let stepped = model.step(&field, &HashMap::from([(0, 0.1)]), 1.0).unwrap();
+        assert!((kelvin_of(stepped.block_stats()[&0].mean) - kelvin_of(stats[&0].mean)).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,135 @@
+use bevy::asset::{io::Reader, AssetLoader, LoadContext};
+use bevy::prelude::*;
+use std::collections::HashMap;
+use thiserror::Error;
+
+use block3d_core::block::Block3DLike;
+
+use crate::circuit::part::Part;
+use super::Symbol;
+
+/// Directory (relative to the asset root) that symbol definitions are loaded from.
+pub const SYMBOL_DIR: &str = "symbols";
+
+/// File extension for symbol definitions, e.g. `symbols/resistor.symbol.ron`.
+pub const SYMBOL_EXTENSION: &str = "symbol.ron";
+
+// ============================================================================
+// ASSET LOADER
+// ============================================================================
+
+#[derive(Debug, Error)]
+pub enum SymbolLoaderError {
+    #[error("could not read symbol file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("could not parse symbol definition: {0}")]
+    Ron(#[from] ron::error::SpannedError),
+    #[error("symbol defines pin {0:?} more than once")]
+    DuplicatePin(String),
+}
+
+/// Loads `*.symbol.ron` files into [`Symbol`] assets.
+#[derive(Default, TypePath)]
+pub struct SymbolLoader;
+
+impl AssetLoader for SymbolLoader {
+    type Asset = Symbol;
+    type Settings = ();
+    type Error = SymbolLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let symbol: Symbol = ron::de::from_bytes(&bytes)?;
+
+        let mut seen = std::collections::HashSet::new();
+        for pin in &symbol.pins {
+            if !seen.insert(pin.name.as_str()) {
+                return Err(SymbolLoaderError::DuplicatePin(pin.name.clone()));
+            }
+        }
+        Ok(symbol)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &[SYMBOL_EXTENSION]
+    }
+}
+
+// ============================================================================
+// LIBRARY
+// ============================================================================
+
+/// Optional per-part override selecting a symbol by library ID instead of by part kind.
+#[derive(Component, Debug, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct SymbolRef(pub String);
+
+/// Handle to the symbol a part is drawn with.
+#[derive(Component, Debug, Clone)]
+pub struct PartSymbol(pub Handle<Symbol>);
+
+/// Maps part kinds to symbol library IDs and caches loaded symbol handles.
+///
+/// Library IDs resolve to `symbols/<id>.symbol.ron`, so adding a new component
+/// type only needs a new definition file and, optionally, a `SymbolRef` on the part.
+#[derive(Resource, Debug, Clone)]
+pub struct SymbolLibrary {
+    /// Part designator (`Block3DLike::symbol`, e.g. `"R"`) to library ID.
+    pub part_kinds: HashMap<String, String>,
+    handles: HashMap<String, Handle<Symbol>>,
+}
+
+impl Default for SymbolLibrary {
+    fn default() -> Self {
+        let part_kinds = [("R", "resistor"), ("C", "capacitor"), ("L", "inductor"), ("D", "diode")]
+            .into_iter()
+            .map(|(kind, id)| (kind.to_string(), id.to_string()))
+            .collect();
+        Self { part_kinds, handles: HashMap::new() }
+    }
+}
+
+impl SymbolLibrary {
+    /// Library ID for a part: an explicit `SymbolRef` wins over the kind mapping.
+    pub fn symbol_id(&self, part: &Part, symbol_ref: Option<&SymbolRef>) -> Option<String> {
+        symbol_ref
+            .map(|r| r.0.clone())
+            .or_else(|| self.part_kinds.get(&part.symbol()).cloned())
+    }
+
+    /// Asset path for a library ID.
+    pub fn path(id: &str) -> String {
+        format!("{SYMBOL_DIR}/{id}.{SYMBOL_EXTENSION}")
+    }
+
+    /// Get (loading on first use) the symbol handle for a library ID.
+    pub fn load(&mut self, id: &str, asset_server: &AssetServer) -> Handle<Symbol> {
+        self.handles
+            .entry(id.to_string())
+            .or_insert_with(|| asset_server.load(Self::path(id)))
+            .clone()
+    }
+
+    /// Resolve the symbol to draw: the loaded asset, or the built-in symbol if the
+    /// definition failed to load. `None` while the asset is still loading.
+    pub fn resolve(
+        handle: &Handle<Symbol>,
+        part: &Part,
+        symbols: &Assets<Symbol>,
+        asset_server: &AssetServer,
+    ) -> Option<Symbol> {
+        if let Some(symbol) = symbols.get(handle) {
+            return Some(symbol.clone());
+        }
+        asset_server
+            .load_state(handle)
+            .is_failed()
+            .then(|| Symbol::for_part(part))
+    }
+}
@@ -0,0 +1,373 @@
+use bevy::asset::RenderAssetUsages;
+use bevy::prelude::*;
+use bevy::render::mesh::{Indices, Mesh, MeshBuilder, Meshable, PrimitiveTopology};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::f32::consts::PI;
+
+use crate::circuit::part::Part;
+use crate::circuit::pin::PinRole;
+
+pub mod library;
+
+/// Schematic grid pitch. Pin anchors always land on multiples of this so wires
+/// snap cleanly with `Drag2dSettings::grid_snapping`.
+pub const SCHEMATIC_GRID: f32 = 10.0;
+
+// ============================================================================
+// SYMBOL DEFINITION
+// ============================================================================
+
+/// A drawable element of a schematic symbol, in symbol-local coordinates.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Reflect)]
+pub enum SymbolPrimitive {
+    /// Open stroked path through the given points.
+    Polyline(Vec<Vec2>),
+    /// Filled convex polygon.
+    Polygon(Vec<Vec2>),
+    /// Stroked circular arc, angles in radians measured counter-clockwise from +X.
+    Arc { center: Vec2, radius: f32, start_angle: f32, end_angle: f32 },
+    /// Stroked full circle.
+    Circle { center: Vec2, radius: f32 },
+}
+
+/// Which side of the symbol body a pin leaves from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Reflect, Default)]
+pub enum PinSide {
+    #[default]
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+impl PinSide {
+    /// Unit vector pointing away from the symbol body, in symbol-local space.
+    pub fn direction(self) -> Vec2 {
+        match self {
+            PinSide::Left => Vec2::NEG_X,
+            PinSide::Right => Vec2::X,
+            PinSide::Top => Vec2::Y,
+            PinSide::Bottom => Vec2::NEG_Y,
+        }
+    }
+}
+
+/// A pin connection point on a symbol.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Reflect)]
+pub struct SymbolPin {
+    /// Matches `Pin::name` on the spawned pin entity.
+    pub name: String,
+    /// Tip of the pin lead, where wires attach (symbol-local).
+    pub anchor: Vec2,
+    pub side: PinSide,
+    /// Electrical role applied to the pin entity when it is anchored.
+    #[serde(default)]
+    pub role: PinRole,
+}
+
+/// Schematic symbol: graphics plus pin geometry.
+///
+/// Loaded from `*.symbol.ron` files through [`library::SymbolLoader`]; the
+/// constructors below are the built-in fallbacks used when no definition loads.
+#[derive(Asset, TypePath, Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct Symbol {
+    pub primitives: Vec<SymbolPrimitive>,
+    pub pins: Vec<SymbolPin>,
+    /// Default property values (e.g. `"value"`, `"footprint"`) for parts using this symbol.
+    #[serde(default)]
+    pub properties: HashMap<String, String>,
+}
+
+impl Symbol {
+    /// Look up a pin by name, falling back to its 1-based position in `pins`.
+    pub fn pin(&self, name: &str, index: u8) -> Option<&SymbolPin> {
+        self.pins
+            .iter()
+            .find(|pin| pin.name == name)
+            .or_else(|| self.pins.get((index as usize).checked_sub(1)?))
+    }
+
+    /// Built-in symbol for a part kind.
+    pub fn for_part(part: &Part) -> Self {
+        match part {
+            Part::Resistor(_) => Self::resistor(),
+            Part::Capacitor(_) => Self::capacitor(),
+            Part::Inductor(_) => Self::inductor(),
+            Part::Diode(_) => Self::diode(),
+        }
+    }
+
+    /// Two-terminal horizontal symbol: leads from ±`half_length` to ±`body_half`.
+    fn two_terminal(half_length: f32, body_half: f32, body: Vec<SymbolPrimitive>) -> Self {
+        let mut primitives = vec![
+            SymbolPrimitive::Polyline(vec![Vec2::new(-half_length, 0.0), Vec2::new(-body_half, 0.0)]),
+            SymbolPrimitive::Polyline(vec![Vec2::new(body_half, 0.0), Vec2::new(half_length, 0.0)]),
+        ];
+        primitives.extend(body);
+        Self {
+            primitives,
+            pins: vec![
+                SymbolPin { name: "1".into(), anchor: Vec2::new(-half_length, 0.0), side: PinSide::Left, role: PinRole::Passive },
+                SymbolPin { name: "2".into(), anchor: Vec2::new(half_length, 0.0), side: PinSide::Right, role: PinRole::Passive },
+            ],
+            properties: HashMap::new(),
+        }
+    }
+
+    /// IEEE zigzag resistor.
+    pub fn resistor() -> Self {
+        let body_half = 2.0 * SCHEMATIC_GRID;
+        let amplitude = 0.5 * SCHEMATIC_GRID;
+        let peaks = 6;
+        let step = 2.0 * body_half / peaks as f32;
+        let mut zigzag = vec![Vec2::new(-body_half, 0.0)];
+        for i in 0..peaks {
+            let sign = if i % 2 == 0 { 1.0 } else { -1.0 };
+            zigzag.push(Vec2::new(-body_half + step * (i as f32 + 0.5), sign * amplitude));
+        }
+        zigzag.push(Vec2::new(body_half, 0.0));
+        Self::two_terminal(3.0 * SCHEMATIC_GRID, body_half, vec![SymbolPrimitive::Polyline(zigzag)])
+    }
+
+    /// Non-polarised capacitor: two parallel plates.
+    pub fn capacitor() -> Self {
+        let gap = 0.3 * SCHEMATIC_GRID;
+        let plate = SCHEMATIC_GRID;
+        Self::two_terminal(
+            2.0 * SCHEMATIC_GRID,
+            gap,
+            vec![
+                SymbolPrimitive::Polyline(vec![Vec2::new(-gap, -plate), Vec2::new(-gap, plate)]),
+                SymbolPrimitive::Polyline(vec![Vec2::new(gap, -plate), Vec2::new(gap, plate)]),
+            ],
+        )
+    }
+
+    /// Inductor: a row of semicircular coils.
+    pub fn inductor() -> Self {
+        let body_half = 2.0 * SCHEMATIC_GRID;
+        let coils = 4;
+        let radius = body_half / coils as f32;
+        let body = (0..coils)
+            .map(|i| SymbolPrimitive::Arc {
+                center: Vec2::new(-body_half + radius * (2 * i + 1) as f32, 0.0),
+                radius,
+                start_angle: PI,
+                end_angle: 0.0,
+            })
+            .collect();
+        Self::two_terminal(3.0 * SCHEMATIC_GRID, body_half, body)
+    }
+
+    /// Diode: filled triangle pointing from anode (pin 1) to cathode bar (pin 2).
+    pub fn diode() -> Self {
+        let half = 0.6 * SCHEMATIC_GRID;
+        let mut symbol = Self::two_terminal(
+            2.0 * SCHEMATIC_GRID,
+            half,
+            vec![
+                SymbolPrimitive::Polygon(vec![Vec2::new(-half, -half), Vec2::new(half, 0.0), Vec2::new(-half, half)]),
+                SymbolPrimitive::Polyline(vec![Vec2::new(half, -half), Vec2::new(half, half)]),
+            ],
+        );
+        symbol.pins[0].name = "A".into();
+        symbol.pins[1].name = "K".into();
+        symbol
+    }
+}
+
+// ============================================================================
+// MESH BUILDING
+// ============================================================================
+
+/// Triangulates a [`Symbol`] into a flat 2D mesh in the XY plane.
+#[derive(Debug, Clone)]
+pub struct SymbolMeshBuilder {
+    pub primitives: Vec<SymbolPrimitive>,
+    /// Width of stroked lines in world units.
+    pub stroke_width: f32,
+    /// Number of segments used for a full circle.
+    pub resolution: u32,
+}
+
+impl SymbolMeshBuilder {
+    pub fn stroke_width(mut self, stroke_width: f32) -> Self {
+        self.stroke_width = stroke_width;
+        self
+    }
+
+    pub fn resolution(mut self, resolution: u32) -> Self {
+        self.resolution = resolution;
+        self
+    }
+
+    fn arc_points(&self, center: Vec2, radius: f32, start: f32, end: f32) -> Vec<Vec2> {
+        let sweep = end - start;
+        let segments = ((sweep.abs() / (2.0 * PI)) * self.resolution as f32).ceil().max(1.0) as u32;
+        (0..=segments)
+            .map(|i| {
+                let angle = start + sweep * i as f32 / segments as f32;
+                center + radius * Vec2::from_angle(angle)
+            })
+            .collect()
+    }
+
+    /// Emit one quad per segment, plus a square cap at each joint to hide gaps.
+    fn stroke(&self, points: &[Vec2], positions: &mut Vec<[f32; 3]>, indices: &mut Vec<u32>) {
+        let half = 0.5 * self.stroke_width;
+        for pair in points.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            let Some(dir) = (b - a).try_normalize() else { continue; };
+            let normal = dir.perp() * half;
+            let a = a - dir * half;
+            let b = b + dir * half;
+            Self::quad(positions, indices, [a - normal, b - normal, b + normal, a + normal]);
+        }
+    }
+
+    fn fill(points: &[Vec2], positions: &mut Vec<[f32; 3]>, indices: &mut Vec<u32>) {
+        if points.len() < 3 { return; }
+        let base = positions.len() as u32;
+        positions.extend(points.iter().map(|p| [p.x, p.y, 0.0]));
+        for i in 1..points.len() as u32 - 1 {
+            indices.extend_from_slice(&[base, base + i, base + i + 1]);
+        }
+    }
+
+    fn quad(positions: &mut Vec<[f32; 3]>, indices: &mut Vec<u32>, corners: [Vec2; 4]) {
+        let base = positions.len() as u32;
+        positions.extend(corners.iter().map(|p| [p.x, p.y, 0.0]));
+        indices.extend_from_slice(&[base, base + 1, base + 2, base, base + 2, base + 3]);
+    }
+}
+
+impl MeshBuilder for SymbolMeshBuilder {
+    fn build(&self) -> Mesh {
+        let mut positions: Vec<[f32; 3]> = Vec::new();
+        let mut indices: Vec<u32> = Vec::new();
+
+        for primitive in &self.primitives {
+            match primitive {
+                SymbolPrimitive::Polyline(points) => self.stroke(points, &mut positions, &mut indices),
+                SymbolPrimitive::Polygon(points) => Self::fill(points, &mut positions, &mut indices),
+                SymbolPrimitive::Arc { center, radius, start_angle, end_angle } => {
+                    let points = self.arc_points(*center, *radius, *start_angle, *end_angle);
+                    self.stroke(&points, &mut positions, &mut indices);
+                }
+                SymbolPrimitive::Circle { center, radius } => {
+                    let points = self.arc_points(*center, *radius, 0.0, 2.0 * PI);
+                    self.stroke(&points, &mut positions, &mut indices);
+                }
+            }
+        }
+
+        let normals = vec![[0.0, 0.0, 1.0]; positions.len()];
+        Mesh::new(PrimitiveTopology::TriangleList, RenderAssetUsages::default())
+            .with_inserted_indices(Indices::U32(indices))
+            .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
+    }
+}
+
+impl Meshable for Symbol {
+    type Output = SymbolMeshBuilder;
+
+    fn mesh(&self) -> Self::Output {
+        SymbolMeshBuilder {
+            primitives: self.primitives.clone(),
+            stroke_width: 0.15 * SCHEMATIC_GRID,
+            resolution: 32,
+        }
+    }
+}
+
+impl From<Symbol> for Mesh {
+    fn from(symbol: Symbol) -> Self {
+        symbol.mesh().build()
+    }
+}
+
+// ============================================================================
+// COMPONENTS
+// ============================================================================
+
+/// Location of a pin's wire attachment point relative to its owning part.
+///
+/// Pins carrying this are parented to their part, so the anchor follows the
+/// part's rotation and mirroring through transform propagation.
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct PinAnchor {
+    pub local: Vec2,
+    pub side: PinSide,
+}
+
+/// Quarter-turn rotation and mirroring of a part's schematic symbol.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect, Default, Serialize, Deserialize)]
+#[reflect(Component, Serialize, Deserialize)]
+pub struct SymbolOrientation {
+    /// Counter-clockwise quarter turns, taken modulo 4.
+    pub quarter_turns: u8,
+    /// Mirror about the symbol's local Y axis (applied before rotation).
+    pub mirrored: bool,
+}
+
+impl SymbolOrientation {
+    pub fn rotated_ccw(self) -> Self {
+        Self { quarter_turns: (self.quarter_turns + 1) % 4, ..self }
+    }
+
+    pub fn mirrored(self) -> Self {
+        Self { mirrored: !self.mirrored, ..self }
+    }
+
+    pub fn rotation(self) -> Quat {
+        Quat::from_rotation_z(f32::from(self.quarter_turns % 4) * 0.5 * PI)
+    }
+
+    pub fn scale(self) -> Vec3 {
+        if self.mirrored { Vec3::new(-1.0, 1.0, 1.0) } else { Vec3::ONE }
+    }
+
+    /// Map a symbol-local point into the part's frame.
+    pub fn apply(self, point: Vec2) -> Vec2 {
+        (self.rotation() * (self.scale() * point.extend(0.0))).truncate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_pins_sit_on_the_grid() {
+        for symbol in [Symbol::resistor(), Symbol::capacitor(), Symbol::inductor(), Symbol::diode()] {
+            for pin in &symbol.pins {
+                let cells = pin.anchor / SCHEMATIC_GRID;
+                assert_eq!(cells, cells.round(), "pin {} is off the grid", pin.name);
+            }
+        }
+    }
+
+    #[test]
+    fn pins_resolve_by_name_then_position() {
+        let diode = Symbol::diode();
+        assert_eq!(diode.pin("K", 1).map(|pin| pin.side), Some(PinSide::Right));
+        assert_eq!(diode.pin("2", 1).map(|pin| pin.name.as_str()), Some("A"));
+        assert!(diode.pin("3", 3).is_none());
+        assert!(diode.pin("3", 0).is_none());
+    }
+
+    #[test]
+    fn orientation_mirrors_before_rotating() {
+        let lead = Vec2::new(3.0 * SCHEMATIC_GRID, 0.0);
+        let turned = SymbolOrientation { quarter_turns: 1, mirrored: false }.apply(lead);
+        assert!(turned.abs_diff_eq(Vec2::new(0.0, 3.0 * SCHEMATIC_GRID), 1e-4));
+        let mirrored = SymbolOrientation { quarter_turns: 1, mirrored: true }.apply(lead);
+        assert!(mirrored.abs_diff_eq(Vec2::new(0.0, -3.0 * SCHEMATIC_GRID), 1e-4));
+
+        let full_turn = (0..4).fold(SymbolOrientation::default(), |orientation, _| orientation.rotated_ccw());
+        assert_eq!(full_turn, SymbolOrientation::default());
+    }
+}
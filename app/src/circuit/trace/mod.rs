@@ -22,7 +22,30 @@ use bevy::prelude::*;
 // Can break routes into segments between juncture points
 
 
-
-
-
-
+/// Copper path of a net between two pins, for parasitic and crosstalk extraction.
+///
+/// The trace runs from `pins[0]` (the driven end, which sits on the net node) to
+/// `pins[1]` (the far end, which is split off onto its own node when simulated).
+#[derive(Component, Debug, Clone)]
+pub struct Trace {
+    /// Polyline through the layout, in grid units.
+    pub path: Vec<Vec3>,
+    /// Cross-section, in grid units.
+    pub width: f32,
+    pub thickness: f32,
+    pub pins: [Entity; 2],
+}
+
+impl Trace {
+    /// 1 oz copper on a 1 mm grid.
+    pub const DEFAULT_THICKNESS: f32 = 0.035;
+
+    pub fn new(path: Vec<Vec3>, width: f32, pins: [Entity; 2]) -> Self {
+        Self { path, width, thickness: Self::DEFAULT_THICKNESS, pins }
+    }
+
+    /// Path length in grid units.
+    pub fn length(&self) -> f32 {
+        self.path.windows(2).map(|pair| pair[0].distance(pair[1])).sum()
+    }
+}
@@ -0,0 +1,234 @@
+//! Part values in engineering notation and standard E-series.
+//!
+//! Values are plain `f64`s in base SI units, so they pair with any uom
+//! quantity via `new::<ohm>`/`get::<farad>` and friends.
+
+use thiserror::Error;
+
+pub use block3d_core::distribution::ESeries;
+
+/// SI prefixes from large to small. `µ` is the micro sign (U+00B5).
+const PREFIXES: [(f64, &str); 10] = [
+    (1e12, "T"), (1e9, "G"), (1e6, "M"), (1e3, "k"), (1.0, ""),
+    (1e-3, "m"), (1e-6, "µ"), (1e-9, "n"), (1e-12, "p"), (1e-15, "f"),
+];
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ValueError {
+    #[error("no value given")]
+    Empty,
+    #[error("{0:?} is not a number")]
+    InvalidNumber(String),
+    #[error("unexpected {found:?} after the value, expected {expected:?}")]
+    UnexpectedSuffix { expected: String, found: String },
+}
+
+/// Multiplier for a prefix character. Accepts the usual ASCII stand-ins:
+/// `u` for micro and `K` for kilo, plus `R`/`r` for unity as in `4R7`.
+fn prefix_scale(c: char) -> Option<f64> {
+    Some(match c {
+        'T' => 1e12,
+        'G' => 1e9,
+        'M' => 1e6,
+        'k' | 'K' => 1e3,
+        'R' | 'r' => 1.0,
+        'm' => 1e-3,
+        // Micro sign, Greek mu and ASCII fallback.
+        'µ' | 'μ' | 'u' => 1e-6,
+        'n' => 1e-9,
+        'p' => 1e-12,
+        'f' => 1e-15,
+        _ => return None,
+    })
+}
+
+/// Parses a value written in engineering notation: `4700`, `4.7k`, `4k7`,
+/// `100n`, `2.2µ`, `4R7`, `1e-6` or `10 kΩ`. A trailing `unit` is optional;
+/// anything else after the value is an error. SPICE's `meg` is read as mega.
+pub fn parse_value(text: &str, unit: &str) -> Result<f64, ValueError> {
+    let text: String = text.chars().filter(|c| !c.is_whitespace()).collect();
+    let text = text.strip_suffix(unit).filter(|_| !unit.is_empty()).unwrap_or(&text);
+    if text.is_empty() {
+        return Err(ValueError::Empty);
+    }
+
+    let number_end = text
+        .char_indices()
+        .find(|&(i, c)| !(c.is_ascii_digit() || c == '.' || (i == 0 && matches!(c, '-' | '+')) || is_exponent(text, i)))
+        .map_or(text.len(), |(i, _)| i);
+    let (number, rest) = text.split_at(number_end);
+    if number.is_empty() {
+        return Err(ValueError::InvalidNumber(text.to_string()));
+    }
+
+    let (scale, rest) = if let Some(rest) = rest.strip_prefix("meg").or_else(|| rest.strip_prefix("MEG")) {
+        (1e6, rest)
+    } else {
+        match rest.chars().next().and_then(|c| Some((prefix_scale(c)?, c))) {
+            Some((scale, c)) => (scale, &rest[c.len_utf8()..]),
+            None => (1.0, rest),
+        }
+    };
+
+    // In `4k7` the prefix stands in for the decimal point.
+    let fraction_end = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+    let (fraction, rest) = rest.split_at(fraction_end);
+    let number = if fraction.is_empty() {
+        number.to_string()
+    } else if number.contains(['.', 'e', 'E']) {
+        return Err(ValueError::InvalidNumber(text.to_string()));
+    } else {
+        format!("{number}.{fraction}")
+    };
+
+    if !rest.is_empty() {
+        return Err(ValueError::UnexpectedSuffix { expected: unit.to_string(), found: rest.to_string() });
+    }
+    number
+        .parse::<f64>()
+        .map(|value| value * scale)
+        .map_err(|_| ValueError::InvalidNumber(text.to_string()))
+}
+
+/// Whether `text[i]` is the `e` of an exponent such as `1e-6`, rather than a
+/// suffix.
+fn is_exponent(text: &str, i: usize) -> bool {
+    let bytes = text.as_bytes();
+    match bytes[i] {
+        b'e' | b'E' => {
+            i > 0
+                && bytes[i - 1].is_ascii_digit()
+                && matches!(bytes.get(i + 1), Some(b'0'..=b'9' | b'-' | b'+'))
+        }
+        b'-' | b'+' => i > 0 && matches!(bytes[i - 1], b'e' | b'E') && is_exponent(text, i - 1),
+        _ => false,
+    }
+}
+
+/// The prefix that puts `value` in `[1, 1000)`, clamped to the table.
+fn prefix_for(value: f64) -> (f64, &'static str) {
+    let magnitude = value.abs();
+    PREFIXES
+        .iter()
+        .copied()
+        .find(|(scale, _)| magnitude >= *scale * (1.0 - 1e-12))
+        .unwrap_or(PREFIXES[PREFIXES.len() - 1])
+}
+
+/// Rounds to three significant figures, so `999.9` becomes `1000` before a
+/// prefix is picked for it.
+fn round_significant(value: f64) -> f64 {
+    let scale = 10f64.powi(value.abs().log10().floor() as i32 - 2);
+    (value / scale).round() * scale
+}
+
+/// Prints a value in `[1, 1000)` with three significant figures and no
+/// trailing zeros.
+fn significant(value: f64) -> String {
+    let decimals = (2 - value.abs().log10().floor() as i32).clamp(0, 2) as usize;
+    let text = format!("{value:.decimals$}");
+    if text.contains('.') {
+        text.trim_end_matches('0').trim_end_matches('.').to_string()
+    } else {
+        text
+    }
+}
+
+/// Formats with an SI prefix and three significant figures, e.g. `0.0123` →
+/// `"12.3 mA"` or `4700.0` → `"4.7 kΩ"`.
+pub fn format_value(value: f64, unit: &str) -> String {
+    if value == 0.0 || !value.is_finite() {
+        return format!("{value} {unit}");
+    }
+    let value = round_significant(value);
+    let (scale, prefix) = prefix_for(value);
+    format!("{} {prefix}{unit}", significant(value / scale))
+}
+
+/// Formats in the IEC 60062 RKM style printed on parts and schematics, where
+/// the prefix replaces the decimal point: `4k7`, `100n`, `2µ2`, `4R7`.
+pub fn format_rkm(value: f64) -> String {
+    if value == 0.0 || !value.is_finite() {
+        return format!("{value}");
+    }
+    let value = round_significant(value);
+    let (scale, prefix) = prefix_for(value);
+    let prefix = if prefix.is_empty() { "R" } else { prefix };
+    let digits = significant(value / scale);
+    match digits.split_once('.') {
+        Some((whole, fraction)) => format!("{whole}{prefix}{fraction}"),
+        None => format!("{digits}{prefix}"),
+    }
+}
+
+/// The nearest value of `series`, by relative error.
+pub fn snap(value: f64, series: ESeries) -> Option<f64> {
+    series.nearest(value)
+}
+
+/// Whether `value` is already a member of `series`, within rounding.
+pub fn is_standard(value: f64, series: ESeries) -> bool {
+    snap(value, series).is_some_and(|snapped| ((snapped - value) / value).abs() < 1e-9)
+}
+
+/// Every value of `series` in `[min, max]`, ascending.
+pub fn series_range(series: ESeries, min: f64, max: f64) -> Vec<f64> {
+    series.values_between(min, max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f64, b: f64) -> bool {
+        ((a - b) / b).abs() < 1e-12
+    }
+
+    #[test]
+    fn parses_and_formats_engineering_notation() {
+        for (text, expected) in [
+            ("4700", 4700.0),
+            ("4.7k", 4700.0),
+            ("4k7", 4700.0),
+            ("4K7 Ω", 4700.0),
+            ("100n", 100e-9),
+            ("2.2µ", 2.2e-6),
+            ("2u2", 2.2e-6),
+            ("4R7", 4.7),
+            ("100R", 100.0),
+            ("1meg", 1e6),
+            ("1e-6", 1e-6),
+            ("3.3 mΩ", 3.3e-3),
+            ("-12m", -12e-3),
+        ] {
+            let value = parse_value(text, "Ω").unwrap_or_else(|e| panic!("{text}: {e}"));
+            assert!(close(value, expected), "{text} parsed as {value}");
+        }
+        assert_eq!(parse_value("  ", "F"), Err(ValueError::Empty));
+        assert!(matches!(parse_value("10kV", "F"), Err(ValueError::UnexpectedSuffix { .. })));
+        assert!(matches!(parse_value("k", "F"), Err(ValueError::InvalidNumber(_))));
+        assert!(matches!(parse_value("4.7k2", "F"), Err(ValueError::InvalidNumber(_))));
+
+        assert_eq!(format_value(0.0123, "A"), "12.3 mA");
+        assert_eq!(format_value(4700.0, "Ω"), "4.7 kΩ");
+        assert_eq!(format_value(-1e-7, "F"), "-100 nF");
+        assert_eq!(format_value(999.96, "V"), "1 kV");
+        assert_eq!(format_rkm(4700.0), "4k7");
+        assert_eq!(format_rkm(100e-9), "100n");
+        assert_eq!(format_rkm(2.2e-6), "2µ2");
+        assert_eq!(format_rkm(4.7), "4R7");
+        assert!(close(parse_value(&format_rkm(33.2e3), "").unwrap(), 33.2e3));
+    }
+
+    #[test]
+    fn snaps_to_series() {
+        assert!(close(snap(4600.0, ESeries::E12).unwrap(), 4700.0));
+        assert!(close(snap(9.8e-9, ESeries::E6).unwrap(), 10e-9));
+        assert!(close(snap(5.0, ESeries::E24).unwrap(), 5.1));
+        assert!(close(snap(1_234.0, ESeries::E96).unwrap(), 1_240.0));
+        assert_eq!(snap(-1.0, ESeries::E12), None);
+        assert!(is_standard(68e-12, ESeries::E6));
+        assert!(!is_standard(5.0, ESeries::E24));
+        assert_eq!(series_range(ESeries::E6, 1.0, 100.0).len(), 13);
+    }
+}
@@ -4,7 +4,7 @@ pub mod markup_tool;
 pub mod transform_tool;
 use bevy::{color::palettes, prelude::*};
 
-use block_tool::{BlockToolPlugin, BlockToolState};
+use block_tool::{BlockToolEvent, BlockToolPlugin, BlockToolState};
 use block3d_core::block::{Block3DLike};
 use comment_tool::{CommentToolPlugin, CommentToolState};
 use layer::Layer;
@@ -15,7 +15,11 @@ use ts_rs::TS;
 use widget_2d::toolbar::ToolbarState;
 use widget_3d::radial_menu::RadialItemData;
 
-use crate::{circuit::part::Part, event::BildOutEvent, view::layout_3d::CameraSettingsImpl};
+use crate::{
+    circuit::{layout::layout_cell, part::{LayoutPlacement, Part}},
+    event::BildOutEvent,
+    view::layout_3d::CameraSettingsImpl,
+};
 
 #[derive(
     Debug,
@@ -82,6 +86,7 @@ impl Plugin for ToolPlugin {
                 (
                     handle_tool_state_transition
                         .run_if(on_event::<StateTransitionEvent<ToolState>>),
+                    place_added_parts,
                 ),
             );
     }
@@ -105,6 +110,13 @@ fn handle_tool_state_transition(
     }
 }
 
+/// Parts confirmed with the block tool join the circuit at the cell they were dropped in.
+fn place_added_parts(mut commands: Commands, mut added: EventReader<BlockToolEvent<Part>>) {
+    for BlockToolEvent::Adding { position, block, .. } in added.read() {
+        commands.spawn((block.clone(), LayoutPlacement(layout_cell(*position))));
+    }
+}
+
 // Newtype wrapper to work around orphan rule
 #[derive(Debug, Clone)]
 pub struct RadialMenuItem<T>(pub T);
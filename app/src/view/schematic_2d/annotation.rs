@@ -0,0 +1,264 @@
+use bevy::prelude::*;
+use bild_core::time::TimeManager;
+
+use crate::circuit::circuit_graph::{EdgeColor, EdgeFrom, EdgeTo, NetLabel};
+use crate::circuit::circuit_graph_render::GraphRenderConfig;
+use crate::circuit::net::Net;
+use crate::circuit::part::Part;
+use crate::circuit::pin::Pin;
+use crate::circuit::relations::{OfPart, OnNet};
+use crate::circuit::simulation::{OperatingPoint, SimulationResults};
+use crate::circuit::symbol::SCHEMATIC_GRID;
+use crate::circuit::value::format_value;
+
+#[derive(Resource, Clone)]
+pub struct AnnotationConfig {
+    pub show_voltages: bool,
+    pub show_currents: bool,
+    pub show_power: bool,
+    /// Recolour wires by net voltage using `GraphRenderConfig::voltage_colormap`.
+    pub color_wires: bool,
+    pub font_size: f32,
+    pub text_color: Color,
+    pub arrow_color: Color,
+    pub arrow_length: f32,
+}
+
+impl Default for AnnotationConfig {
+    fn default() -> Self {
+        Self {
+            show_voltages: true,
+            show_currents: true,
+            show_power: true,
+            color_wires: true,
+            font_size: 8.0,
+            text_color: Color::srgb(0.55, 0.85, 1.0),
+            arrow_color: Color::srgb(1.0, 0.8, 0.3),
+            arrow_length: 0.8 * SCHEMATIC_GRID,
+        }
+    }
+}
+
+/// Operating point sampled from `SimulationResults` at the current simulation time.
+#[derive(Resource, Default)]
+pub struct DisplayedOperatingPoint(pub Option<OperatingPoint>);
+
+/// A text label showing a simulated value for `target`.
+#[derive(Component, Debug, Clone, Copy)]
+pub enum ValueAnnotation {
+    /// Node voltage, shown next to a `NetLabel` node.
+    NetVoltage { node: Entity },
+    /// Current through a wire edge.
+    WireCurrent { edge: Entity },
+    /// Power dissipated by a part.
+    PartPower { part: Entity },
+}
+
+impl ValueAnnotation {
+    fn target(self) -> Entity {
+        match self {
+            ValueAnnotation::NetVoltage { node } => node,
+            ValueAnnotation::WireCurrent { edge } => edge,
+            ValueAnnotation::PartPower { part } => part,
+        }
+    }
+}
+
+/// Marks entities that already have a `ValueAnnotation` pointing at them.
+#[derive(Component)]
+struct Annotated;
+
+/// Overlays simulated voltages, currents and power on the schematic.
+pub struct ValueAnnotationPlugin;
+
+impl Plugin for ValueAnnotationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AnnotationConfig>()
+            .init_resource::<GraphRenderConfig>()
+            .init_resource::<SimulationResults>()
+            .init_resource::<DisplayedOperatingPoint>()
+            .add_systems(Update, (
+                sample_operating_point,
+                spawn_annotations,
+                (update_annotations, color_edges_by_voltage, draw_current_arrows),
+                cleanup_annotations,
+            ).chain());
+    }
+}
+
+fn sample_operating_point(
+    results: Res<SimulationResults>,
+    time: Option<Res<TimeManager>>,
+    mut displayed: ResMut<DisplayedOperatingPoint>,
+) {
+    let now = time.map_or(f64::MAX, |time| time.current_time.as_secs_f64());
+    displayed.0 = results.at(now);
+}
+
+fn spawn_annotations(
+    mut commands: Commands,
+    config: Res<AnnotationConfig>,
+    labels: Query<Entity, (With<NetLabel>, Without<Annotated>)>,
+    edges: Query<Entity, (With<EdgeFrom>, With<EdgeTo>, Without<Annotated>)>,
+    parts: Query<Entity, (With<Part>, Without<Annotated>)>,
+) {
+    let annotations = labels
+        .iter()
+        .map(|node| ValueAnnotation::NetVoltage { node })
+        .chain(edges.iter().map(|edge| ValueAnnotation::WireCurrent { edge }))
+        .chain(parts.iter().map(|part| ValueAnnotation::PartPower { part }));
+
+    for annotation in annotations {
+        commands.entity(annotation.target()).insert(Annotated);
+        commands.spawn((
+            annotation,
+            Text2d::default(),
+            TextFont { font_size: config.font_size, ..default() },
+            TextColor(config.text_color),
+            Transform::default(),
+            Visibility::Hidden,
+        ));
+    }
+}
+
+/// Net a wire belongs to: taken from whichever endpoint is a pin on a net, or a net label.
+fn edge_net(
+    endpoints: [Entity; 2],
+    on_net: &Query<&OnNet>,
+    labels: &Query<&NetLabel>,
+    nets: &Query<(Entity, &Name), With<Net>>,
+) -> Option<Entity> {
+    endpoints.iter().find_map(|&node| {
+        on_net.get(node).ok().map(|rel| rel.0).or_else(|| {
+            let label = labels.get(node).ok()?;
+            nets.iter().find(|(_, name)| name.as_str() == label.name).map(|(net, _)| net)
+        })
+    })
+}
+
+/// Current flowing along the edge `from -> to`, derived from a pin endpoint's part current.
+fn edge_current(
+    point: &OperatingPoint,
+    from: Entity,
+    to: Entity,
+    pins: &Query<(&Pin, &OfPart)>,
+) -> Option<f64> {
+    if let Ok((pin, of_part)) = pins.get(to) {
+        return point.pin_current(of_part.0, pin.index);
+    }
+    let (pin, of_part) = pins.get(from).ok()?;
+    point.pin_current(of_part.0, pin.index).map(|i| -i)
+}
+
+fn update_annotations(
+    config: Res<AnnotationConfig>,
+    displayed: Res<DisplayedOperatingPoint>,
+    mut annotations: Query<(&ValueAnnotation, &mut Text2d, &mut Transform, &mut Visibility)>,
+    transforms: Query<&GlobalTransform>,
+    edges: Query<(&EdgeFrom, &EdgeTo)>,
+    pins: Query<(&Pin, &OfPart)>,
+    labels: Query<&NetLabel>,
+    nets: Query<(Entity, &Name), With<Net>>,
+) {
+    for (annotation, mut text, mut transform, mut visibility) in &mut annotations {
+        let content = displayed.0.as_ref().and_then(|point| match *annotation {
+            ValueAnnotation::NetVoltage { node } => {
+                if !config.show_voltages { return None; }
+                let label = labels.get(node).ok()?;
+                let net = nets.iter().find(|(_, name)| name.as_str() == label.name)?.0;
+                let position = transforms.get(node).ok()?.translation().truncate();
+                let volts = point.net_voltage(net)?;
+                Some((format!("{}: {}", label.name, format_value(volts, "V")), position + Vec2::new(0.0, SCHEMATIC_GRID)))
+            }
+            ValueAnnotation::WireCurrent { edge } => {
+                if !config.show_currents { return None; }
+                let (from, to) = edges.get(edge).ok()?;
+                let a = transforms.get(from.0).ok()?.translation().truncate();
+                let b = transforms.get(to.0).ok()?.translation().truncate();
+                let amps = edge_current(point, from.0, to.0, &pins)?;
+                let normal = (b - a).try_normalize().unwrap_or(Vec2::X).perp();
+                Some((format_value(amps.abs(), "A"), (a + b) * 0.5 + normal * 0.8 * SCHEMATIC_GRID))
+            }
+            ValueAnnotation::PartPower { part } => {
+                if !config.show_power { return None; }
+                let position = transforms.get(part).ok()?.translation().truncate();
+                let watts = point.part_power(part)?;
+                Some((format_value(watts, "W"), position + Vec2::new(0.0, 1.5 * SCHEMATIC_GRID)))
+            }
+        });
+
+        match content {
+            Some((value, position)) => {
+                if text.0 != value { text.0 = value; }
+                transform.translation = position.extend(1.0);
+                *visibility = Visibility::Inherited;
+            }
+            None => *visibility = Visibility::Hidden,
+        }
+    }
+}
+
+fn color_edges_by_voltage(
+    mut commands: Commands,
+    config: Res<AnnotationConfig>,
+    render: Res<GraphRenderConfig>,
+    displayed: Res<DisplayedOperatingPoint>,
+    edges: Query<(Entity, &EdgeFrom, &EdgeTo, Option<&EdgeColor>)>,
+    on_net: Query<&OnNet>,
+    labels: Query<&NetLabel>,
+    nets: Query<(Entity, &Name), With<Net>>,
+) {
+    if !config.color_wires { return; }
+    let Some(point) = displayed.0.as_ref() else { return; };
+    let Some(range) = render
+        .voltage_range
+        .or_else(|| point.voltage_range().map(|(lo, hi)| (lo as f32, hi as f32)))
+    else {
+        return;
+    };
+
+    for (edge, from, to, current) in &edges {
+        let Some(volts) = edge_net([from.0, to.0], &on_net, &labels, &nets).and_then(|net| point.net_voltage(net)) else { continue; };
+        let color = render.voltage_colormap.sample_range(volts as f32, range);
+        // Only touch the component when the colour moves, so change detection stays quiet
+        if current.is_none_or(|current| current.0 != color) {
+            commands.entity(edge).insert(EdgeColor(color));
+        }
+    }
+}
+
+fn draw_current_arrows(
+    mut gizmos: Gizmos,
+    config: Res<AnnotationConfig>,
+    displayed: Res<DisplayedOperatingPoint>,
+    edges: Query<(&EdgeFrom, &EdgeTo)>,
+    transforms: Query<&GlobalTransform>,
+    pins: Query<(&Pin, &OfPart)>,
+) {
+    if !config.show_currents { return; }
+    let Some(point) = displayed.0.as_ref() else { return; };
+
+    for (from, to) in &edges {
+        let Some(amps) = edge_current(point, from.0, to.0, &pins) else { continue; };
+        if amps == 0.0 { continue; }
+        let (Ok(ta), Ok(tb)) = (transforms.get(from.0), transforms.get(to.0)) else { continue; };
+        let (a, b) = (ta.translation().truncate(), tb.translation().truncate());
+        let Some(dir) = (b - a).try_normalize() else { continue; };
+        let dir = dir * amps.signum() as f32;
+        let mid = (a + b) * 0.5;
+        let half = 0.5 * config.arrow_length;
+        gizmos.arrow_2d(mid - dir * half, mid + dir * half, config.arrow_color);
+    }
+}
+
+fn cleanup_annotations(
+    mut commands: Commands,
+    annotations: Query<(Entity, &ValueAnnotation)>,
+    targets: Query<(), With<Annotated>>,
+) {
+    for (entity, annotation) in &annotations {
+        if targets.get(annotation.target()).is_err() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
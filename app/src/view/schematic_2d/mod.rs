@@ -1,9 +1,15 @@
+pub mod annotation;
+pub mod symbol;
 
 use bevy::prelude::*;
 use camera_2d::{EditorCamera2d, EditorCamera2dPlugin};
 use interaction::{drag::two_d::{Drag2dSettings}, InteractiveMeshPlugin};
 use camera::controller::CameraSettings;
-use crate::circuit::{part::Part, commands::CommandsCircuitExt};
+use crate::circuit::{part::Part, commands::CommandsCircuitExt, symbol::SymbolOrientation};
+use crate::circuit::net::{NetKind, NetSupply};
+use crate::circuit::simulation::{electro_thermal::ElectroThermalConfig, SolveCircuit};
+use annotation::ValueAnnotationPlugin;
+use symbol::SchematicSymbolPlugin;
 
 
 
@@ -16,26 +22,37 @@ impl Plugin for Schematic2dPlugin {
         app.init_resource::<Camera2dSettingsImpl>()
             .add_plugins(EditorCamera2dPlugin)
             .add_plugins(InteractiveMeshPlugin::<Camera2dSettingsImpl>::default())
+            .add_plugins((SchematicSymbolPlugin, ValueAnnotationPlugin))
             .add_systems(Startup, (setup_camera_2d, init_drag_settings, setup_demo_circuit));
     }
 }
 
 
-fn setup_demo_circuit(mut commands: Commands) {
+fn setup_demo_circuit(mut commands: Commands, mut solve: EventWriter<SolveCircuit>) {
     // Nets
-    let vcc = commands.spawn_net("VCC");
-    let gnd = commands.spawn_net("GND");
+    let vcc = commands.spawn_net_with_kind("VCC", NetKind::Power);
+    commands.entity(vcc).insert(NetSupply { volts: 5.0 });
+    let gnd = commands.spawn_net_with_kind("GND", NetKind::Ground);
     let n1 = commands.spawn_net("N1");
 
     // Parts with pins
-    let (_r1, r1_pins) = commands.spawn_part_with_pins("R1", Part::resistor(), &["1", "2"]);
-    let (_c1, c1_pins) = commands.spawn_part_with_pins("C1", Part::capacitor(), &["1", "2"]);
+    let (r1, r1_pins) = commands.spawn_part_with_pins("R1", Part::resistor(), &["1", "2"]);
+    let (c1, c1_pins) = commands.spawn_part_with_pins("C1", Part::capacitor(), &["1", "2"]);
+
+    // Lay the parts out so the symbols don't overlap; C1 stands vertically.
+    commands.entity(r1).insert(Transform::from_xyz(-40.0, 20.0, 0.0));
+    commands.entity(c1).insert((
+        Transform::from_xyz(30.0, -10.0, 0.0),
+        SymbolOrientation::default().rotated_ccw(),
+    ));
 
     // Connect pins to nets: VCC -> R1.1 -> N1 -> C1.1 -> GND
     commands.connect_pin_to_net(r1_pins[0], vcc);
     commands.connect_pin_to_net(r1_pins[1], n1);
     commands.connect_pin_to_net(c1_pins[0], n1);
     commands.connect_pin_to_net(c1_pins[1], gnd);
+
+    solve.write(SolveCircuit::ElectroThermal(ElectroThermalConfig::default()));
 }
 
 
@@ -0,0 +1,152 @@
+use bevy::prelude::*;
+use bevy::render::mesh::{MeshBuilder, Meshable};
+use std::collections::HashSet;
+
+use crate::circuit::part::Part;
+use crate::circuit::pin::Pin;
+use crate::circuit::relations::{OfPart, Pins};
+use crate::circuit::symbol::library::{PartSymbol, SymbolLibrary, SymbolLoader, SymbolRef};
+use crate::circuit::symbol::{PinAnchor, Symbol, SymbolOrientation};
+
+#[derive(Resource, Clone)]
+pub struct SymbolRenderConfig {
+    pub stroke_color: Color,
+    pub stroke_width: f32,
+    /// Segments per full circle when tessellating arcs.
+    pub resolution: u32,
+}
+
+impl Default for SymbolRenderConfig {
+    fn default() -> Self {
+        Self {
+            stroke_color: Color::srgb(0.9, 0.9, 0.85),
+            stroke_width: 1.5,
+            resolution: 32,
+        }
+    }
+}
+
+/// Default property values copied from a part's symbol definition, refreshed
+/// whenever the symbol is rebuilt.
+#[derive(Component, Debug, Clone, Default)]
+pub struct SymbolProperties(pub std::collections::HashMap<String, String>);
+
+/// Draws a schematic symbol for every `Part` and anchors its pins to the symbol's pin geometry.
+pub struct SchematicSymbolPlugin;
+
+impl Plugin for SchematicSymbolPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<Symbol>()
+            .init_asset_loader::<SymbolLoader>()
+            .init_resource::<SymbolRenderConfig>()
+            .init_resource::<SymbolLibrary>()
+            .register_type::<PinAnchor>()
+            .register_type::<SymbolOrientation>()
+            .register_type::<SymbolRef>()
+            .add_systems(Update, (
+                (assign_part_symbols, reload_changed_symbols, spawn_part_symbols, anchor_pins).chain(),
+                apply_symbol_orientation,
+            ));
+    }
+}
+
+fn assign_part_symbols(
+    mut commands: Commands,
+    mut library: ResMut<SymbolLibrary>,
+    asset_server: Res<AssetServer>,
+    mut symbols: ResMut<Assets<Symbol>>,
+    parts: Query<(Entity, &Part, Option<&SymbolRef>, Option<&Pins>), Or<(Without<PartSymbol>, Changed<SymbolRef>)>>,
+) {
+    for (entity, part, symbol_ref, pins) in &parts {
+        let handle = match library.symbol_id(part, symbol_ref) {
+            Some(id) => library.load(&id, &asset_server),
+            None => symbols.add(Symbol::for_part(part)),
+        };
+        commands.entity(entity).insert(PartSymbol(handle)).remove::<Mesh2d>();
+        for pin in pins.into_iter().flat_map(Pins::iter) {
+            commands.entity(pin).remove::<PinAnchor>();
+        }
+    }
+}
+
+/// Hot-reload: drop the mesh and pin anchors of every part whose definition changed,
+/// so the systems below rebuild them from the new asset.
+fn reload_changed_symbols(
+    mut commands: Commands,
+    mut events: EventReader<AssetEvent<Symbol>>,
+    parts: Query<(Entity, &PartSymbol, Option<&Pins>)>,
+) {
+    for event in events.read() {
+        let AssetEvent::Modified { id } = event else { continue; };
+        for (entity, part_symbol, pins) in &parts {
+            if part_symbol.0.id() != *id { continue; }
+            info!("Symbol for {:?} changed, rebuilding", entity);
+            commands.entity(entity).remove::<Mesh2d>();
+            for pin in pins.into_iter().flat_map(Pins::iter) {
+                commands.entity(pin).remove::<PinAnchor>();
+            }
+        }
+    }
+}
+
+fn spawn_part_symbols(
+    mut commands: Commands,
+    config: Res<SymbolRenderConfig>,
+    asset_server: Res<AssetServer>,
+    symbols: Res<Assets<Symbol>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    parts: Query<(Entity, &Part, &PartSymbol), Without<Mesh2d>>,
+) {
+    for (entity, part, part_symbol) in &parts {
+        let Some(symbol) = SymbolLibrary::resolve(&part_symbol.0, part, &symbols, &asset_server) else { continue; };
+        let mesh = symbol
+            .mesh()
+            .stroke_width(config.stroke_width)
+            .resolution(config.resolution)
+            .build();
+        commands.entity(entity).insert((
+            Mesh2d(meshes.add(mesh)),
+            MeshMaterial2d(materials.add(ColorMaterial::from_color(config.stroke_color))),
+            SymbolProperties(symbol.properties),
+        ));
+    }
+}
+
+/// Parent each pin to its part at the matching symbol anchor so wires attach to the lead tip
+/// and follow the part's rotation and mirroring. Pins the symbol lacks are reported once.
+fn anchor_pins(
+    mut commands: Commands,
+    mut unresolved: Local<HashSet<Entity>>,
+    asset_server: Res<AssetServer>,
+    symbols: Res<Assets<Symbol>>,
+    pins: Query<(Entity, &Pin, &OfPart), Without<PinAnchor>>,
+    parts: Query<(&Part, &PartSymbol)>,
+) {
+    for (entity, pin, of_part) in &pins {
+        let Ok((part, part_symbol)) = parts.get(of_part.0) else { continue; };
+        let Some(symbol) = SymbolLibrary::resolve(&part_symbol.0, part, &symbols, &asset_server) else { continue; };
+        let Some(symbol_pin) = symbol.pin(&pin.name, pin.index) else {
+            if unresolved.insert(entity) {
+                warn!("No symbol pin for {:?} ({}) on part {:?}", entity, pin.name, of_part.0);
+            }
+            continue;
+        };
+        unresolved.remove(&entity);
+        commands.entity(entity).insert((
+            PinAnchor { local: symbol_pin.anchor, side: symbol_pin.side },
+            symbol_pin.role,
+            Transform::from_translation(symbol_pin.anchor.extend(0.1)),
+            ChildOf(of_part.0),
+        ));
+    }
+}
+
+fn apply_symbol_orientation(
+    mut parts: Query<(&SymbolOrientation, &mut Transform), Changed<SymbolOrientation>>,
+) {
+    for (orientation, mut transform) in &mut parts {
+        transform.rotation = orientation.rotation();
+        transform.scale = orientation.scale();
+    }
+}
@@ -0,0 +1,453 @@
+//! Co-simulation of coupled physics domains.
+//!
+//! Each domain (circuit, thermal, mechanical, ...) keeps its own solver and
+//! publishes named quantities into shared [`CouplingFields`]. Exchanges declare
+//! which domain produces a quantity and which ones consume it, e.g. dissipated
+//! power from the circuit to the thermal solver and temperatures back to the
+//! circuit, where they drift its resistances.
+//!
+//! Every step the domains run in registration order and each sees the latest
+//! values the others published:
+//! - [`CouplingScheme::Loose`] sweeps once (staggered coupling), so a domain
+//!   reading a quantity produced later in the order sees the previous step's value.
+//! - [`CouplingScheme::Tight`] repeats the sweep until no exchanged quantity changes
+//!   by more than the tolerance between sweeps.
+//!
+//! [`CoSimulation`] is itself a [`SimulationBackend`], and any other backend can
+//! take part as a domain through [`BackendDomain`].
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+use thiserror::Error;
+
+use crate::simulation::SimulationBackend;
+use crate::time::{SimulationTime, TimeBackend};
+
+/// Error returned by a domain solver.
+pub type DomainError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Named quantities exchanged between domains, each a vector of values (one per
+/// node, part, voxel, ... in an order the producer and consumers agree on).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CouplingFields(pub HashMap<String, Vec<f64>>);
+
+impl CouplingFields {
+    pub fn get(&self, quantity: &str) -> Option<&[f64]> {
+        self.0.get(quantity).map(Vec::as_slice)
+    }
+
+    pub fn set(&mut self, quantity: impl Into<String>, values: Vec<f64>) {
+        self.0.insert(quantity.into(), values);
+    }
+
+    /// Overwrite quantities with those in `other`.
+    pub fn merge(&mut self, other: &CouplingFields) {
+        self.0.extend(other.0.iter().map(|(k, v)| (k.clone(), v.clone())));
+    }
+}
+
+/// One physics domain taking part in a co-simulation.
+pub trait CoupledDomain: Send + Sync + 'static {
+    fn name(&self) -> &str;
+
+    /// Solve the step ending at `time`, reading inputs from `fields` and returning
+    /// the quantities this domain publishes. Tight coupling calls this several
+    /// times for the same step, so it must start from the last accepted state.
+    fn solve(&mut self, fields: &CouplingFields, time: &SimulationTime) -> Result<CouplingFields, DomainError>;
+
+    /// The last `solve` was accepted and the next call starts a new step.
+    fn accept(&mut self) {}
+}
+
+type ApplyFn<S> = Box<dyn Fn(&mut S, &CouplingFields) + Send + Sync>;
+type PublishFn<R> = Box<dyn Fn(&R) -> CouplingFields + Send + Sync>;
+
+/// Adapts a [`SimulationBackend`] into a [`CoupledDomain`]: `apply` writes coupled
+/// inputs into the backend state before each step, and `publish` extracts the
+/// exchanged quantities from the step result.
+///
+/// Backends used with tight coupling must compute each step from `state` and
+/// `time` alone, since the same step is repeated until it converges.
+pub struct BackendDomain<B: SimulationBackend> {
+    name: String,
+    backend: B,
+    state: B::State,
+    apply: ApplyFn<B::State>,
+    publish: PublishFn<B::Result>,
+}
+
+impl<B: SimulationBackend> BackendDomain<B> {
+    pub fn new(
+        name: impl Into<String>,
+        backend: B,
+        state: B::State,
+        apply: impl Fn(&mut B::State, &CouplingFields) + Send + Sync + 'static,
+        publish: impl Fn(&B::Result) -> CouplingFields + Send + Sync + 'static,
+    ) -> Self {
+        Self { name: name.into(), backend, state, apply: Box::new(apply), publish: Box::new(publish) }
+    }
+
+    pub fn backend(&self) -> &B {
+        &self.backend
+    }
+
+    pub fn state(&self) -> &B::State {
+        &self.state
+    }
+}
+
+impl<B: SimulationBackend> CoupledDomain for BackendDomain<B> {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn solve(&mut self, fields: &CouplingFields, time: &SimulationTime) -> Result<CouplingFields, DomainError> {
+        (self.apply)(&mut self.state, fields);
+        let result = self.backend.step(&self.state, time.clone())?;
+        Ok((self.publish)(&result))
+    }
+}
+
+/// A quantity `producer` publishes for `consumers` to read.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Exchange {
+    pub quantity: String,
+    pub producer: String,
+    pub consumers: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum CouplingScheme {
+    /// One staggered sweep through the domains per step.
+    Loose,
+    /// Sweep until every exchanged quantity changes by at most `tolerance`
+    /// (relative) between sweeps, blending each new value with the previous one
+    /// by `relaxation` (1 = no under-relaxation).
+    Tight { tolerance: f64, max_iterations: usize, relaxation: f64 },
+}
+
+impl Default for CouplingScheme {
+    fn default() -> Self {
+        CouplingScheme::Tight { tolerance: 1e-6, max_iterations: 50, relaxation: 1.0 }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CouplingConfig {
+    pub scheme: CouplingScheme,
+    /// Simulated time per step.
+    pub step: Duration,
+    /// Values of exchanged quantities before the first step.
+    pub initial: CouplingFields,
+}
+
+impl Default for CouplingConfig {
+    fn default() -> Self {
+        Self { scheme: CouplingScheme::default(), step: Duration::from_millis(1), initial: CouplingFields::default() }
+    }
+}
+
+/// Outcome of one co-simulation step.
+#[derive(Debug, Clone)]
+pub struct CouplingStep {
+    pub timestamp: f64,
+    /// Sweeps through the domains; always 1 for loose coupling.
+    pub iterations: usize,
+    /// Relative change of each exchanged quantity over the last sweep (from the
+    /// previous step under loose coupling). Infinite when a quantity first appears.
+    pub residuals: Vec<(String, f64)>,
+    /// Whether tight coupling met its tolerance; always true for loose coupling.
+    pub converged: bool,
+    pub fields: CouplingFields,
+}
+
+impl CouplingStep {
+    pub fn max_residual(&self) -> f64 {
+        self.residuals.iter().map(|(_, r)| *r).fold(0.0, f64::max)
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum CoSimulationError {
+    #[error("co-simulation stepped before it was initialized")]
+    NotInitialized,
+    #[error("a domain named {0:?} is already registered")]
+    DuplicateDomain(String),
+    #[error("exchange of {quantity:?} names unknown domain {domain:?}")]
+    UnknownDomain { quantity: String, domain: String },
+    #[error("{quantity:?} is produced by both {first:?} and {second:?}")]
+    DuplicateProducer { quantity: String, first: String, second: String },
+    #[error("{domain:?} did not publish {quantity:?}")]
+    MissingOutput { domain: String, quantity: String },
+    #[error("{domain:?} published {quantity:?}, which is produced by {producer:?}")]
+    WrongProducer { domain: String, quantity: String, producer: String },
+    #[error("{domain:?} failed: {source}")]
+    Domain { domain: String, source: DomainError },
+}
+
+/// Coordinates registered domains through their declared exchanges.
+#[derive(Default)]
+pub struct CoSimulation {
+    domains: Vec<Box<dyn CoupledDomain>>,
+    exchanges: Vec<Exchange>,
+    config: Option<CouplingConfig>,
+    fields: CouplingFields,
+}
+
+impl CoSimulation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a domain; domains solve in the order they are registered.
+    pub fn register(&mut self, domain: impl CoupledDomain) -> Result<&mut Self, CoSimulationError> {
+        if self.domains.iter().any(|d| d.name() == domain.name()) {
+            return Err(CoSimulationError::DuplicateDomain(domain.name().to_owned()));
+        }
+        self.domains.push(Box::new(domain));
+        Ok(self)
+    }
+
+    /// Declare that `producer` publishes `quantity` for `consumers`.
+    pub fn exchange(&mut self, quantity: &str, producer: &str, consumers: &[&str]) -> Result<&mut Self, CoSimulationError> {
+        for domain in std::iter::once(&producer).chain(consumers) {
+            if !self.domains.iter().any(|d| d.name() == *domain) {
+                return Err(CoSimulationError::UnknownDomain { quantity: quantity.to_owned(), domain: (*domain).to_owned() });
+            }
+        }
+        if let Some(existing) = self.exchanges.iter_mut().find(|e| e.quantity == quantity) {
+            if existing.producer != producer {
+                return Err(CoSimulationError::DuplicateProducer {
+                    quantity: quantity.to_owned(),
+                    first: existing.producer.clone(),
+                    second: producer.to_owned(),
+                });
+            }
+            existing.consumers.extend(consumers.iter().map(|c| (*c).to_owned()));
+        } else {
+            self.exchanges.push(Exchange {
+                quantity: quantity.to_owned(),
+                producer: producer.to_owned(),
+                consumers: consumers.iter().map(|c| (*c).to_owned()).collect(),
+            });
+        }
+        Ok(self)
+    }
+
+    pub fn exchanges(&self) -> &[Exchange] {
+        &self.exchanges
+    }
+
+    /// Latest values of every published quantity.
+    pub fn fields(&self) -> &CouplingFields {
+        &self.fields
+    }
+
+    /// One sweep through the domains, updating `self.fields`.
+    fn sweep(&mut self, time: &SimulationTime, relaxation: f64) -> Result<(), CoSimulationError> {
+        for domain in &mut self.domains {
+            let name = domain.name().to_owned();
+            let outputs = domain
+                .solve(&self.fields, time)
+                .map_err(|source| CoSimulationError::Domain { domain: name.clone(), source })?;
+            for exchange in self.exchanges.iter().filter(|e| e.producer == name) {
+                if outputs.get(&exchange.quantity).is_none() {
+                    return Err(CoSimulationError::MissingOutput { domain: name, quantity: exchange.quantity.clone() });
+                }
+            }
+            for (quantity, mut values) in outputs.0 {
+                if let Some(exchange) = self.exchanges.iter().find(|e| e.quantity == quantity && e.producer != name) {
+                    return Err(CoSimulationError::WrongProducer { domain: name, quantity, producer: exchange.producer.clone() });
+                }
+                if let Some(previous) = self.fields.get(&quantity).filter(|p| p.len() == values.len()) {
+                    for (value, old) in values.iter_mut().zip(previous) {
+                        *value = old + relaxation * (*value - old);
+                    }
+                }
+                self.fields.set(quantity, values);
+            }
+        }
+        Ok(())
+    }
+
+    /// Relative change of each exchanged quantity from `previous` to the current fields.
+    fn residuals(&self, previous: &CouplingFields) -> Vec<(String, f64)> {
+        self.exchanges
+            .iter()
+            .map(|exchange| {
+                let current = self.fields.get(&exchange.quantity).unwrap_or_default();
+                let residual = match previous.get(&exchange.quantity) {
+                    Some(old) if old.len() == current.len() => {
+                        let scale = current.iter().fold(0.0, |m: f64, v| m.max(v.abs())).max(f64::EPSILON);
+                        current.iter().zip(old).fold(0.0, |m: f64, (v, o)| m.max((v - o).abs())) / scale
+                    }
+                    _ => f64::INFINITY,
+                };
+                (exchange.quantity.clone(), residual)
+            })
+            .collect()
+    }
+}
+
+impl SimulationBackend for CoSimulation {
+    type Config = CouplingConfig;
+    /// Boundary conditions imposed from outside the domains (ambient temperature,
+    /// supply voltages, ...), merged into the fields before every step.
+    type State = CouplingFields;
+    type Result = CouplingStep;
+    type Error = CoSimulationError;
+
+    fn initialize(&mut self, config: Self::Config) -> Result<(), Self::Error> {
+        self.fields = config.initial.clone();
+        self.config = Some(config);
+        Ok(())
+    }
+
+    fn step(&mut self, state: &Self::State, time: SimulationTime) -> Result<Self::Result, Self::Error> {
+        let scheme = self.config.as_ref().ok_or(CoSimulationError::NotInitialized)?.scheme;
+        self.fields.merge(state);
+        let (mut iterations, mut residuals, mut converged) = (0, Vec::new(), true);
+        match scheme {
+            CouplingScheme::Loose => {
+                let previous = self.fields.clone();
+                self.sweep(&time, 1.0)?;
+                iterations = 1;
+                residuals = self.residuals(&previous);
+            }
+            CouplingScheme::Tight { tolerance, max_iterations, relaxation } => {
+                converged = false;
+                while iterations < max_iterations.max(1) {
+                    let previous = self.fields.clone();
+                    // The first sweep of a step starts from the previous step's values, so
+                    // relaxing it would only slow the step down.
+                    self.sweep(&time, if iterations == 0 { 1.0 } else { relaxation })?;
+                    iterations += 1;
+                    residuals = self.residuals(&previous);
+                    debug!("Coupling sweep {iterations} at t = {}: residuals {residuals:?}", time.timestamp);
+                    if iterations > 1 && residuals.iter().all(|(_, r)| *r <= tolerance) {
+                        converged = true;
+                        break;
+                    }
+                }
+                if !converged {
+                    warn!("Coupling did not converge in {iterations} sweeps at t = {}: residuals {residuals:?}", time.timestamp);
+                }
+            }
+        }
+        for domain in &mut self.domains {
+            domain.accept();
+        }
+        let step = CouplingStep { timestamp: time.timestamp, iterations, residuals, converged, fields: self.fields.clone() };
+        debug!("Coupled step at t = {}: {} sweeps, max residual {:.3e}", step.timestamp, step.iterations, step.max_residual());
+        Ok(step)
+    }
+
+    fn run_steps(&mut self, state: &Self::State, time: SimulationTime, steps: usize) -> Result<Vec<Self::Result>, Self::Error> {
+        let step = self.config.as_ref().ok_or(CoSimulationError::NotInitialized)?.step;
+        let mut time = time;
+        (0..steps)
+            .map(|_| {
+                time.advance(step);
+                self.step(state, time.clone())
+            })
+            .collect()
+    }
+
+    fn time_backend(&self) -> TimeBackend {
+        let step_size = self.config.as_ref().map_or(Duration::ZERO, |c| c.step);
+        TimeBackend::FixedStep { step_size }
+    }
+
+    fn is_ready(&self) -> bool {
+        self.config.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Resistor across a fixed voltage whose resistance drifts with temperature.
+    struct Circuit;
+
+    impl CoupledDomain for Circuit {
+        fn name(&self) -> &str {
+            "circuit"
+        }
+
+        fn solve(&mut self, fields: &CouplingFields, _time: &SimulationTime) -> Result<CouplingFields, DomainError> {
+            let temperature = fields.get("temperature").map_or(25.0, |t| t[0]);
+            let resistance = 10.0 * (1.0 + 0.004 * (temperature - 25.0));
+            let mut outputs = CouplingFields::default();
+            outputs.set("power", vec![10.0 * 10.0 / resistance]);
+            Ok(outputs)
+        }
+    }
+
+    /// Steady-state heating through a 2 K/W thermal resistance.
+    struct Thermal;
+
+    impl CoupledDomain for Thermal {
+        fn name(&self) -> &str {
+            "thermal"
+        }
+
+        fn solve(&mut self, fields: &CouplingFields, _time: &SimulationTime) -> Result<CouplingFields, DomainError> {
+            let power = fields.get("power").map_or(0.0, |p| p[0]);
+            let mut outputs = CouplingFields::default();
+            outputs.set("temperature", vec![25.0 + 2.0 * power]);
+            Ok(outputs)
+        }
+    }
+
+    fn electro_thermal(scheme: CouplingScheme) -> CoSimulation {
+        let mut simulation = CoSimulation::new();
+        simulation.register(Circuit).unwrap().register(Thermal).unwrap();
+        simulation.exchange("power", "circuit", &["thermal"]).unwrap();
+        simulation.exchange("temperature", "thermal", &["circuit"]).unwrap();
+        simulation.initialize(CouplingConfig { scheme, ..CouplingConfig::default() }).unwrap();
+        simulation
+    }
+
+    /// Fixed point of `T = 25 + 2·100 / (10·(1 + 0.004·(T − 25)))`.
+    fn steady_temperature() -> f64 {
+        let (a, b): (f64, f64) = (0.04, 10.0);
+        // a·ΔT² + b·ΔT − 200 = 0
+        25.0 + (-b + (b * b + 4.0 * a * 200.0).sqrt()) / (2.0 * a)
+    }
+
+    #[test]
+    fn tight_coupling_converges_within_a_step() {
+        let mut simulation = electro_thermal(CouplingScheme::default());
+        let step = simulation.step(&CouplingFields::default(), SimulationTime::default()).unwrap();
+        assert!(step.converged, "{step:?}");
+        assert!(step.iterations > 2);
+        assert!((step.fields.get("temperature").unwrap()[0] - steady_temperature()).abs() < 1e-3);
+    }
+
+    #[test]
+    fn loose_coupling_lags_and_settles_over_steps() {
+        let mut simulation = electro_thermal(CouplingScheme::Loose);
+        let steps = simulation.run_steps(&CouplingFields::default(), SimulationTime::default(), 40).unwrap();
+        assert!(steps.iter().all(|s| s.iterations == 1));
+        assert!(steps[0].residuals.iter().all(|(_, r)| r.is_infinite()));
+        assert!((steps[0].fields.get("temperature").unwrap()[0] - 45.0).abs() < 1e-9);
+        let last = steps.last().unwrap();
+        assert!(last.max_residual() < 1e-6);
+        assert!((last.fields.get("temperature").unwrap()[0] - steady_temperature()).abs() < 1e-3);
+    }
+
+    #[test]
+    fn exchanges_are_validated() {
+        let mut simulation = CoSimulation::new();
+        simulation.register(Circuit).unwrap().register(Thermal).unwrap();
+        assert!(matches!(simulation.register(Thermal), Err(CoSimulationError::DuplicateDomain(_))));
+        assert!(matches!(simulation.exchange("power", "mechanical", &[]), Err(CoSimulationError::UnknownDomain { .. })));
+        simulation.exchange("power", "thermal", &["circuit"]).unwrap();
+        simulation.initialize(CouplingConfig::default()).unwrap();
+        let error = simulation.step(&CouplingFields::default(), SimulationTime::default()).unwrap_err();
+        assert!(matches!(error, CoSimulationError::WrongProducer { .. }), "{error}");
+    }
+}
@@ -1,5 +1,6 @@
 pub mod comment;
 pub mod simulation;
+pub mod cosimulation;
 pub mod time;
 pub mod selection;
 
@@ -1,4 +1,5 @@
 use block3d_core::connection::OrientedInterface;
+use block3d_core::face::Direction;
 use block3d_core::Orientation;
 use petgraph::graph::NodeIndex;
 
@@ -7,6 +8,8 @@ use petgraph::graph::NodeIndex;
 pub struct ConnectionPoint {
     /// The interface type (stud, tube, etc.) and its orientation
     pub interface: OrientedInterface,
+    /// Side of the block the connection point looks out of, in local space
+    pub direction: Direction,
     /// Offset from block origin (x,y,z) in local space
     pub position_offset: (f32, f32, f32),
     /// Reference to another node if connected
@@ -15,9 +18,10 @@ pub struct ConnectionPoint {
 
 impl ConnectionPoint {
     /// Creates a new connection point
-    pub fn new(interface: OrientedInterface, position_offset: (f32, f32, f32)) -> Self {
+    pub fn new(interface: OrientedInterface, direction: Direction, position_offset: (f32, f32, f32)) -> Self {
         Self {
             interface,
+            direction,
             position_offset,
             connected_to: None,
         }
@@ -25,24 +29,13 @@ impl ConnectionPoint {
     
     /// Checks if this connection point is compatible with a given interface
     pub fn is_compatible_with(&self, other_interface: &OrientedInterface) -> bool {
-       true
+        (self.interface + *other_interface).is_some()
     }
     
-    /// Gets the world position of this connection point based on block position and orientation
-    pub fn world_position(&self, block_position: (f32, f32, f32), block_orientation: Orientation) -> (f32, f32, f32) {
-        // Apply rotation based on block orientation
-        let rotated_offset = match block_orientation {
-            Orientation::O0 => self.position_offset,
-            Orientation::O90 => (self.position_offset.2, self.position_offset.1, -self.position_offset.0),
-            Orientation::O180 => (-self.position_offset.0, self.position_offset.1, -self.position_offset.2),
-            Orientation::O270 => (-self.position_offset.2, self.position_offset.1, self.position_offset.0),
-        };
-        
-        // Apply translation
-        (
-            block_position.0 + rotated_offset.0,
-            block_position.1 + rotated_offset.1,
-            block_position.2 + rotated_offset.2,
-        )
+    /// Gets the world position of this connection point for a block of local `block_size`
+    /// placed with its minimum corner at `block_position`
+    pub fn world_position(&self, block_position: (f32, f32, f32), block_orientation: Orientation, block_size: (u32, u32, u32)) -> (f32, f32, f32) {
+        let (x, y, z): (f32, f32, f32) = block_orientation.apply_in_box(self.position_offset.into(), block_size).into();
+        (block_position.0 + x, block_position.1 + y, block_position.2 + z)
     }
 } 
\ No newline at end of file
@@ -0,0 +1,33 @@
+use rand::RngCore;
+use block3d_core::block::Block3DLike;
+use petgraph::graph::NodeIndex;
+use crate::wfc::solver::WFCSolver;
+use super::{Heuristic, min_by_key_random};
+
+/// Grows the layout outwards from a seed position: the uncollapsed node with the
+/// smallest Manhattan distance to `seed` goes next.
+pub struct DistanceFromSeedHeuristic {
+    pub seed: (usize, usize, usize),
+}
+
+impl DistanceFromSeedHeuristic {
+    pub fn new(seed: (usize, usize, usize)) -> Self {
+        Self { seed }
+    }
+}
+
+impl<T: Block3DLike> Heuristic<T> for DistanceFromSeedHeuristic {
+    fn select_node_to_collapse(&self, solver: &WFCSolver<T>, rng: &mut dyn RngCore) -> Option<NodeIndex> {
+        min_by_key_random(
+            solver.uncollapsed(),
+            |node| {
+                solver.graph.node_weight(node).map(|state| {
+                    state.position.0.abs_diff(self.seed.0)
+                        + state.position.1.abs_diff(self.seed.1)
+                        + state.position.2.abs_diff(self.seed.2)
+                })
+            },
+            rng,
+        )
+    }
+}
@@ -0,0 +1,43 @@
+use rand::RngCore;
+use block3d_core::block::Block3DLike;
+use petgraph::graph::NodeIndex;
+use crate::wfc::solver::WFCSolver;
+use super::{Heuristic, min_by_key_random};
+
+/// Minimum-entropy selection: collapses the node whose remaining states have the
+/// lowest Shannon entropy, with each state weighted by its block's `ranking`.
+pub struct EntropyHeuristic;
+
+impl EntropyHeuristic {
+    /// `H = ln Σw − (Σ w ln w) / Σw` over the weights of the remaining states.
+    /// Non-positive rankings count as zero; if nothing is left, states are equally likely.
+    pub fn entropy(weights: impl IntoIterator<Item = f32>) -> f64 {
+        let (mut count, mut sum, mut sum_w_ln_w) = (0usize, 0.0f64, 0.0f64);
+        for weight in weights {
+            count += 1;
+            let weight = f64::from(weight.max(0.0));
+            if weight > 0.0 {
+                sum += weight;
+                sum_w_ln_w += weight * weight.ln();
+            }
+        }
+        if sum > 0.0 {
+            sum.ln() - sum_w_ln_w / sum
+        } else {
+            (count.max(1) as f64).ln()
+        }
+    }
+
+    pub fn node_entropy<T: Block3DLike>(solver: &WFCSolver<T>, node: NodeIndex) -> f64 {
+        let values = solver.values();
+        Self::entropy(
+            solver.domain(node).into_iter().flat_map(|domain| domain.ones()).map(|value| values.block(value).ranking()),
+        )
+    }
+}
+
+impl<T: Block3DLike> Heuristic<T> for EntropyHeuristic {
+    fn select_node_to_collapse(&self, solver: &WFCSolver<T>, rng: &mut dyn RngCore) -> Option<NodeIndex> {
+        min_by_key_random(solver.uncollapsed(), |node| Self::node_entropy(solver, node), rng)
+    }
+}
@@ -1,17 +1,69 @@
 pub mod weighted_random_heuristic;
+pub mod entropy_heuristic;
+pub mod scanline_heuristic;
+pub mod distance_heuristic;
 use block3d_core::block::Block3DLike;
 use petgraph::graph::NodeIndex;
+use rand::{Rng, RngCore, distributions::WeightedIndex, prelude::Distribution};
 
 use crate::wfc::solver::state::NodeState;
+use crate::wfc::solver::WFCSolver;
 
+pub use entropy_heuristic::EntropyHeuristic;
+pub use scanline_heuristic::ScanlineHeuristic;
+pub use distance_heuristic::DistanceFromSeedHeuristic;
+pub use weighted_random_heuristic::WeightedRandomHeuristic;
 
-
+/// Chooses where and how the solver collapses next. All randomness comes from
+/// `rng`, which the solver seeds, so runs are reproducible.
 pub trait Heuristic<T: Block3DLike> {
-    /// Select a node to collapse based on entropy or other criteria
-    fn select_node_to_collapse(&self, solver_states: &[Vec<NodeState<T>>], collapsed: &std::collections::HashSet<NodeIndex>) -> Option<NodeIndex>;
+    /// Select an uncollapsed node to collapse next, or `None` when all are collapsed
+    fn select_node_to_collapse(&self, solver: &WFCSolver<T>, rng: &mut dyn RngCore) -> Option<NodeIndex>;
 
-    /// Given multiple valid states for a node, select one (weighted random, etc.)
-    fn select_state_for_node(&self, node: NodeIndex, valid_states: &[NodeState<T>]) -> Option<NodeState<T>>;
+    /// Given multiple valid states for a node, select one by its index. Defaults
+    /// to a random pick weighted by `Block3DLike::ranking`.
+    fn select_state_for_node(&self, _node: NodeIndex, valid_states: &[NodeState<T>], rng: &mut dyn RngCore) -> Option<usize> {
+        weighted_by_ranking(valid_states, rng)
+    }
 }
 
+/// Random index into `valid_states` weighted by block ranking, uniform if the
+/// rankings give no usable weights.
+pub fn weighted_by_ranking<T: Block3DLike>(valid_states: &[NodeState<T>], rng: &mut dyn RngCore) -> Option<usize> {
+    if valid_states.is_empty() {
+        return None;
+    }
+    let weights: Vec<f32> = valid_states.iter().map(|state| state.block.ranking().max(0.0)).collect();
+    match WeightedIndex::new(&weights) {
+        Ok(dist) => Some(dist.sample(rng)),
+        Err(_) => Some(rng.gen_range(0..valid_states.len())),
+    }
+}
 
+/// The node with the smallest key, ties broken uniformly at random. Nodes are
+/// visited in index order so the same seed always makes the same choice.
+pub fn min_by_key_random<K: PartialOrd>(
+    nodes: impl Iterator<Item = NodeIndex>,
+    key: impl Fn(NodeIndex) -> K,
+    rng: &mut dyn RngCore,
+) -> Option<NodeIndex> {
+    let mut best: Option<K> = None;
+    let mut ties = Vec::new();
+    for node in nodes {
+        let node_key = key(node);
+        match best.as_ref().and_then(|best| node_key.partial_cmp(best)) {
+            Some(std::cmp::Ordering::Greater) => {}
+            Some(std::cmp::Ordering::Equal) => ties.push(node),
+            _ => {
+                best = Some(node_key);
+                ties.clear();
+                ties.push(node);
+            }
+        }
+    }
+    match ties.len() {
+        0 => None,
+        1 => Some(ties[0]),
+        n => Some(ties[rng.gen_range(0..n)]),
+    }
+}
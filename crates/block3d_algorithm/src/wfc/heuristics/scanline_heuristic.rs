@@ -0,0 +1,18 @@
+use rand::RngCore;
+use block3d_core::block::Block3DLike;
+use petgraph::graph::NodeIndex;
+use crate::wfc::solver::WFCSolver;
+use super::{Heuristic, min_by_key_random};
+
+/// Fills the grid layer by layer from the bottom: lowest Y first, then Z, then X.
+pub struct ScanlineHeuristic;
+
+impl<T: Block3DLike> Heuristic<T> for ScanlineHeuristic {
+    fn select_node_to_collapse(&self, solver: &WFCSolver<T>, rng: &mut dyn RngCore) -> Option<NodeIndex> {
+        min_by_key_random(
+            solver.uncollapsed(),
+            |node| solver.graph.node_weight(node).map(|state| (state.position.1, state.position.2, state.position.0)),
+            rng,
+        )
+    }
+}
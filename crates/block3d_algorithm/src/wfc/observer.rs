@@ -11,6 +11,8 @@ pub trait WFCObserverLike<T: Block3DLike> {
     fn on_collapse(&self, node: NodeIndex, state: &NodeState<T>);
     // Method to be called when propagation occurs
     fn on_propagate(&self, affected: &[NodeIndex]);
+    // Method to be called when backtracking undoes the collapse of a node
+    fn on_backtrack(&self, _node: NodeIndex) {}
 }
 
 // Define a struct implementing the observer pattern for WFC
@@ -1,12 +1,11 @@
 use std::collections::HashMap;
 
 use block3d_core::block::Block3DLike;
-use petgraph::graph::NodeIndex;
-use block3d_core::connection::ConnectorInterface;
-use block3d_core::face::Face;
 
 use super::{state::NodeState};
 
+/// Position of the second state relative to the first.
+pub type Offset = (i64, i64, i64);
 
 pub struct CompatibilityRule<T: Block3DLike> {
     pub check: Box<dyn Fn(&NodeState<T>, &NodeState<T>) -> bool>,
@@ -16,13 +15,25 @@ impl<T: Block3DLike> CompatibilityRule<T> {
     pub fn new(check: Box<dyn Fn(&NodeState<T>, &NodeState<T>) -> bool>, description: Option<String>) -> Self {
         Self { check, description }
     }
-    
+
+    /// Faces that meet must form a connection; blocks may still sit side by
+    /// side where no faces touch.
+    pub fn touching_faces_mate() -> Self {
+        Self::new(
+            Box::new(|a: &NodeState<T>, b: &NodeState<T>| a.faces_mate_with(b)),
+            Some("touching faces must mate".to_string()),
+        )
+    }
 }
 
 
+/// Pairwise constraints between neighbouring nodes. Two states are compatible
+/// when they don't overlap and every rule accepts them. Rules must only depend
+/// on the states' blocks, orientations and relative position, so results are
+/// cached per (value, value, offset).
 pub struct CompatibilityTable<T: Block3DLike> {
     rules: Vec<CompatibilityRule<T>>,
-    cache: HashMap<(NodeIndex, NodeIndex), bool>,
+    cache: HashMap<(usize, usize, Offset), bool>,
 }
 
 impl<T: Block3DLike> Default for CompatibilityTable<T> {
@@ -49,13 +60,27 @@ impl<T: Block3DLike> CompatibilityTable<T> {
         self.rebuild_cache();
     }
 
-    pub fn is_compatible(&mut self, node1: NodeIndex, node2: NodeIndex, state1: &NodeState<T>, state2: &NodeState<T>) -> bool {
-        if let Some(&cached_result) = self.cache.get(&(node1, node2)) {
+    pub fn with_rule(mut self, rule: CompatibilityRule<T>) -> Self {
+        self.add_rule(rule);
+        self
+    }
+
+    /// Whether `value1` and `value2` can sit `offset` apart. `states` builds the
+    /// two node states and is only called on a cache miss.
+    pub fn is_compatible(
+        &mut self,
+        value1: usize,
+        value2: usize,
+        offset: Offset,
+        states: impl FnOnce() -> (NodeState<T>, NodeState<T>),
+    ) -> bool {
+        if let Some(&cached_result) = self.cache.get(&(value1, value2, offset)) {
             return cached_result;
         }
 
-        let result = self.rules.iter().all(|rule| (rule.check)(state1, state2));
-        self.cache.insert((node1, node2), result);
+        let (state1, state2) = states();
+        let result = !state1.collides_with(&state2) && self.rules.iter().all(|rule| (rule.check)(&state1, &state2));
+        self.cache.insert((value1, value2, offset), result);
         result
     }
-}
\ No newline at end of file
+}
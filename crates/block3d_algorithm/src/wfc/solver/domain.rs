@@ -0,0 +1,75 @@
+use block3d_core::block::Block3DLike;
+use block3d_core::Orientation;
+use fixedbitset::FixedBitSet;
+
+use super::state::NodeState;
+
+/// Every (block, orientation) pair a node can take, numbered
+/// `block * Orientation::COUNT + orientation.index()` so a node's superposition
+/// is a bitset over these values.
+pub struct ValueSpace<T: Block3DLike> {
+    blocks: Vec<T>,
+    // One prototype per value at the origin; `None` for orientations the
+    // block's symmetry makes redundant.
+    templates: Vec<Option<NodeState<T>>>,
+}
+
+impl<T: Block3DLike> ValueSpace<T> {
+    pub fn new(blocks: impl IntoIterator<Item = T>) -> Self {
+        let blocks: Vec<T> = blocks.into_iter().collect();
+        let mut templates = vec![None; blocks.len() * Orientation::COUNT];
+        for (index, block) in blocks.iter().enumerate() {
+            for orientation in block.symmetry().distinct_orientations() {
+                templates[Self::value(index, orientation)] =
+                    Some(NodeState::with_position(block.clone(), orientation, (0, 0, 0)));
+            }
+        }
+        Self { blocks, templates }
+    }
+
+    pub fn value(block: usize, orientation: Orientation) -> usize {
+        block * Orientation::COUNT + orientation.index()
+    }
+
+    pub fn len(&self) -> usize {
+        self.templates.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.templates.is_empty()
+    }
+
+    pub fn blocks(&self) -> &[T] {
+        &self.blocks
+    }
+
+    pub fn block(&self, value: usize) -> &T {
+        &self.blocks[value / Orientation::COUNT]
+    }
+
+    pub fn block_index(&self, value: usize) -> usize {
+        value / Orientation::COUNT
+    }
+
+    pub fn orientation(&self, value: usize) -> Orientation {
+        Orientation::from_index(value % Orientation::COUNT).unwrap_or_default()
+    }
+
+    /// The domain of a node nothing is known about yet.
+    pub fn full_domain(&self) -> FixedBitSet {
+        let mut domain = FixedBitSet::with_capacity(self.len());
+        for (value, template) in self.templates.iter().enumerate() {
+            domain.set(value, template.is_some());
+        }
+        domain
+    }
+
+    /// The node state `value` stands for at `position`.
+    pub fn state(&self, value: usize, position: (usize, usize, usize)) -> NodeState<T> {
+        let mut state = self.templates[value]
+            .clone()
+            .unwrap_or_else(|| NodeState::with_position(self.block(value).clone(), self.orientation(value), position));
+        state.position = position;
+        state
+    }
+}
@@ -1,13 +1,30 @@
 use petgraph::graph::NodeIndex;
 use thiserror::Error;
 
-
+/// How far a failed `solve` got before it gave up.
+#[derive(Debug, Clone, Default, PartialEq, Error)]
+#[error(
+    "No solution found: reached {} of {total} nodes, last failing at {failed_at:?}, after {backtracks} backtracks and {restarts} restarts",
+    deepest.len()
+)]
+pub struct PartialSolution {
+    pub total: usize,
+    /// Position and block symbol of every node in the largest assignment reached
+    pub deepest: Vec<((usize, usize, usize), String)>,
+    /// The last node that had no usable state
+    pub failed_at: Option<NodeIndex>,
+    pub backtracks: usize,
+    pub restarts: usize,
+}
 
 #[derive(Debug, Error)]
 pub enum WFCError {
     #[error("No valid states available for node {0:?}")]
     NoValidStates(NodeIndex),
     
+    #[error("Propagation emptied the domain of node {0:?}")]
+    Contradiction(NodeIndex),
+
     #[error("Propagation failed: {0:?}")]
     PropagationFailed(String),
     
@@ -29,6 +46,9 @@ pub enum WFCError {
     #[error("No solution found")]
     NoSolution,
 
+    #[error(transparent)]
+    Unsolved(Box<PartialSolution>),
+
     #[error("Node not found in graph")]
     NodeNotFound(NodeIndex),
 
@@ -1,16 +1,19 @@
 pub mod compatibility;
+pub mod domain;
 pub mod error;
 pub mod state;
 pub mod spatial_grid;
+pub mod trail;
 
 use block3d_core::block::Block3DLike;
 use block3d_core::Orientation;
 use petgraph::graph::NodeIndex;
 use spatial_grid::SpatialGrid;
-use std::collections::{HashMap, HashSet, BTreeMap};
+use std::collections::{HashMap, HashSet, VecDeque};
 
-use error::WFCError;
-use petgraph::visit::Dfs;
+use error::{PartialSolution, WFCError};
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
 
 use state::NodeState;
 
@@ -18,9 +21,10 @@ use super::graph::WFCGraph;
 use super::observer::WFCObserverLike;
 use crate::wfc::heuristics::Heuristic;
 use crate::wfc::invariants::WFCInvariant;
-use crate::wfc::solver::compatibility::CompatibilityTable;
+use crate::wfc::solver::compatibility::{CompatibilityTable, Offset};
+use domain::ValueSpace;
 use fixedbitset::FixedBitSet;
-use strum::IntoEnumIterator;
+use trail::{Decision, SavedDomains};
 
 
 pub struct WFCSolver<T: Block3DLike> {
@@ -31,18 +35,28 @@ pub struct WFCSolver<T: Block3DLike> {
     pub compatibility: CompatibilityTable<T>,
     pub heuristic: Box<dyn Heuristic<T>>,
     observers: Vec<Box<dyn WFCObserverLike<T>>>,
-    // A set of all blocks
-    block_set: HashSet<T>,
+    // Every (block, orientation) a node can take
+    values: ValueSpace<T>,
+    // Values still possible per node, indexed by node index
+    domains: Vec<FixedBitSet>,
     // Spatial grid for collision detection
     spatial_grid: SpatialGrid,
     // Connection graph to track block relationships
     connections: HashMap<NodeIndex, HashMap<String, (NodeIndex, String)>>,
+    // Seed for heuristic choices; `None` draws a fresh one per run
+    seed: Option<u64>,
+    // Collapses made by the current run, oldest first
+    trail: Vec<Decision<T>>,
+    // Backtracks allowed per attempt before restarting; `None` searches exhaustively
+    max_backtracks: Option<usize>,
+    // Fresh attempts allowed once an attempt runs out of backtracks
+    max_restarts: usize,
 }
 
 impl<T: Block3DLike> WFCSolver<T> {
     pub fn new(
         graph: WFCGraph<T>,
-        block_set: HashSet<T>,
+        block_set: impl IntoIterator<Item = T>,
         invariants: Vec<Box<dyn WFCInvariant<T>>>,
         heuristic: Box<dyn Heuristic<T>>,
         observers: Vec<Box<dyn WFCObserverLike<T>>>,
@@ -55,72 +69,337 @@ impl<T: Block3DLike> WFCSolver<T> {
             compatibility: CompatibilityTable::new(),
             heuristic,
             observers,
-            block_set,
+            values: ValueSpace::new(block_set),
+            domains: Vec::new(),
             spatial_grid: SpatialGrid::new(1.0), // 1.0 unit cell size
             connections: HashMap::new(),
+            seed: None,
+            trail: Vec::new(),
+            max_backtracks: None,
+            max_restarts: 0,
         }
     }
 
+    pub fn with_compatibility(mut self, compatibility: CompatibilityTable<T>) -> Self {
+        self.compatibility = compatibility;
+        self
+    }
+
+    /// Makes every run with the same graph, blocks and heuristic reproducible.
+    /// Block order matters too, so pass blocks in a fixed order rather than a `HashSet`.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn with_heuristic(mut self, heuristic: Box<dyn Heuristic<T>>) -> Self {
+        self.heuristic = heuristic;
+        self
+    }
+
+    /// Swaps the node selection strategy for the next run.
+    pub fn set_heuristic(&mut self, heuristic: Box<dyn Heuristic<T>>) {
+        self.heuristic = heuristic;
+    }
+
+    /// Caps how many decisions one attempt may undo before it gives up.
+    pub fn with_max_backtracks(mut self, max_backtracks: usize) -> Self {
+        self.max_backtracks = Some(max_backtracks);
+        self
+    }
+
+    /// Lets `solve` start over this many times, with the rng reseeded from the
+    /// previous attempt, when an attempt hits the backtrack limit.
+    pub fn with_max_restarts(mut self, max_restarts: usize) -> Self {
+        self.max_restarts = max_restarts;
+        self
+    }
+
+    fn rng(&self) -> StdRng {
+        match self.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        }
+    }
+
+    /// Nodes still waiting to be collapsed, in index order.
+    pub fn uncollapsed(&self) -> impl Iterator<Item = NodeIndex> + '_ {
+        self.graph.node_indices().filter(|node| !self.collapsed.contains(node))
+    }
+
+    /// Collapses every node, backtracking chronologically on dead ends. Nodes
+    /// collapsed before the call are kept fixed. When the search gives up every
+    /// decision is undone, leaving the solver as the call found it, and the
+    /// returned [`PartialSolution`] describes the furthest the search got.
     pub fn solve(&mut self) -> Result<(), WFCError> {
         // Initialize node states with potential blocks
         self.initialize_states()?;
-        
-        let node_id = self.graph.add_node(NodeState::new(T::default(), Orientation::default()));
-        let mut dfs = Dfs::new(&self.graph.0, node_id);
+        self.trail.clear();
+        let mut rng = self.rng();
 
-        let mut stack = Vec::<(NodeIndex, NodeState<T>)>::new();
+        let mut report = PartialSolution { total: self.graph.node_count(), ..Default::default() };
+        let mut attempt_backtracks = 0;
 
-        while let Some(node) = dfs.next(&self.graph.0) {
-            
-            let node_state = self.graph.node_weight(node)
-                .ok_or(WFCError::NodeNotFound(node))?
-                .clone();
-            
-            stack.push((node, node_state));
-
-            match self.collapse_node(node) {
-                Ok(_) => {},
-                Err(_) => {
-                    match self.backtrack(&mut dfs, &mut stack) {
-                        Ok(true) => {
-                            // We now need to re-collapse in a different way
-                            self.collapse_node(node)?;
-                        },
-                        _ => return Err(WFCError::NoSolution),
+        while let Some(node) = self.heuristic.select_node_to_collapse(self, &mut rng) {
+            match self.collapse_node(node, &mut rng) {
+                Ok(_) => {
+                    if self.collapsed.len() > report.deepest.len() {
+                        report.deepest = self.placed();
+                    }
+                }
+                Err(WFCError::Contradiction(_) | WFCError::NoValidStates(_) | WFCError::NoValidStatesAfterInvariants(_)) => {
+                    report.failed_at = Some(node);
+                    if self.max_backtracks.is_some_and(|max| attempt_backtracks >= max) {
+                        self.unwind();
+                        if report.restarts >= self.max_restarts {
+                            return Err(WFCError::Unsolved(Box::new(report)));
+                        }
+                        // Start over with different choices
+                        rng = StdRng::seed_from_u64(rng.next_u64());
+                        report.restarts += 1;
+                        attempt_backtracks = 0;
+                        continue;
+                    }
+                    match self.backtrack() {
+                        Some(undone) => {
+                            attempt_backtracks += undone;
+                            report.backtracks += undone;
+                        }
+                        // Every choice has been tried
+                        None => return Err(WFCError::Unsolved(Box::new(report))),
                     }
                 }
+                Err(error) => {
+                    self.unwind();
+                    return Err(error);
+                }
             }
         }
 
         Ok(())
     }
 
-    fn backtrack(&mut self, dfs: &mut Dfs<NodeIndex, FixedBitSet>, stack: &mut Vec<(NodeIndex, NodeState<T>)>) -> Result<bool, WFCError> {
-        if let Some((prev_node, prev_state)) = stack.pop() {
-            // Remove from spatial grid and connections
-            self.spatial_grid.remove_node(prev_node);
-            self.connections.remove(&prev_node);
-            
-            // Restore previous state
-            match self.graph.node_weight_mut(prev_node) {
-                Some(node_state) => {
-                    *node_state = prev_state;
-                    dfs.stack.push(prev_node); // Revisit the previous node
-                    Ok(true)
+    /// Undoes every decision on the trail.
+    fn unwind(&mut self) {
+        while let Some(decision) = self.trail.pop() {
+            self.undo(decision);
+        }
+    }
+
+    /// Undoes decisions until the most recent one can be refuted: its value is
+    /// removed from the domain it was picked from and the removal propagates.
+    /// Returns how many decisions were undone, or `None` once the trail is
+    /// exhausted.
+    fn backtrack(&mut self) -> Option<usize> {
+        let mut undone = 0;
+        while let Some(decision) = self.trail.pop() {
+            let (node, value) = (decision.node, decision.value);
+            self.undo(decision);
+            undone += 1;
+
+            let mut domain = self.domains[node.index()].clone();
+            domain.set(value, false);
+            if domain.is_clear() {
+                continue;
+            }
+            if let Ok((mut affected, saved)) = self.narrow(node, domain) {
+                // The refutation rests on the decisions still on the trail
+                if let Some(previous) = self.trail.last_mut() {
+                    previous.save_domains(saved);
                 }
-                None => Err(WFCError::NoSolution),
+                affected.insert(0, node);
+                self.notify_propagate(&affected);
+                return Some(undone);
+            }
+        }
+        None
+    }
+
+    /// Puts back the domains, node weights, connections and spatial grid as
+    /// they were before `decision`.
+    fn undo(&mut self, decision: Decision<T>) {
+        let Decision { node, domains, states, connections, .. } = decision;
+        self.spatial_grid.remove_node(node);
+        self.collapsed.remove(&node);
+        for (saved, domain) in domains {
+            self.domains[saved.index()] = domain;
+        }
+        for (saved, state) in states {
+            if let Some(weight) = self.graph.node_weight_mut(saved) {
+                *weight = state;
             }
-        } else {
-            Err(WFCError::NoSolution)
         }
+        for (saved, entry) in connections {
+            match entry {
+                Some(entry) => self.connections.insert(saved, entry),
+                None => self.connections.remove(&saved),
+            };
+        }
+        self.notify_backtrack(node);
+    }
+
+    /// Position and block symbol of every collapsed node, in index order.
+    fn placed(&self) -> Vec<((usize, usize, usize), String)> {
+        self.graph.node_indices()
+            .filter(|node| self.collapsed.contains(node))
+            .filter_map(|node| self.graph.node_weight(node))
+            .map(|state| (state.position, state.block.symbol()))
+            .collect()
     }
 
-    fn initialize_states(&mut self) -> Result<(), WFCError> {
-        // If needed, run invariants or prune impossible states upfront
+    /// Gives every node the full domain and makes it arc consistent. Does
+    /// nothing once domains exist for all nodes.
+    pub fn initialize_states(&mut self) -> Result<(), WFCError> {
+        if self.domains.len() == self.graph.node_count() {
+            return Ok(());
+        }
+        let mut domains = vec![self.values.full_domain(); self.graph.node_count()];
+        let nodes: Vec<NodeIndex> = self.graph.node_indices().collect();
+        self.propagate(&mut domains, nodes, &mut Vec::new())?;
+        self.domains = domains;
         Ok(())
     }
 
-    fn collapse_node(&mut self, node: NodeIndex) -> Result<NodeState<T>, WFCError> {
+    /// Values still possible at `node`; see [`ValueSpace`] for the numbering.
+    pub fn domain(&self, node: NodeIndex) -> Option<&FixedBitSet> {
+        self.domains.get(node.index())
+    }
+
+    /// Blocks and orientations still possible at `node`.
+    pub fn candidates(&self, node: NodeIndex) -> impl Iterator<Item = (&T, Orientation)> + '_ {
+        self.domain(node)
+            .into_iter()
+            .flat_map(|domain| domain.ones())
+            .map(|value| (self.values.block(value), self.values.orientation(value)))
+    }
+
+    pub fn values(&self) -> &ValueSpace<T> {
+        &self.values
+    }
+
+    /// Removes the values `keep` rejects from `node` and propagates. On a
+    /// contradiction nothing changes.
+    pub fn restrict(&mut self, node: NodeIndex, keep: impl Fn(&T, Orientation) -> bool) -> Result<Vec<NodeIndex>, WFCError> {
+        self.initialize_states()?;
+        let mut domain = self.domains.get(node.index()).ok_or(WFCError::NodeNotFound(node))?.clone();
+        let rejected: Vec<usize> = domain.ones()
+            .filter(|&value| !keep(self.values.block(value), self.values.orientation(value)))
+            .collect();
+        if rejected.is_empty() {
+            return Ok(Vec::new());
+        }
+        for value in rejected {
+            domain.set(value, false);
+        }
+        if domain.is_clear() {
+            return Err(WFCError::Contradiction(node));
+        }
+        let (affected, saved) = self.narrow(node, domain)?;
+        if let Some(decision) = self.trail.last_mut() {
+            decision.save_domains(saved);
+        }
+        self.notify_propagate(&affected);
+        Ok(affected)
+    }
+
+    /// Narrows `node` to `domain` and propagates. Returns the nodes whose
+    /// domains shrank and every changed domain as it was before; on a
+    /// contradiction nothing changes.
+    fn narrow(&mut self, node: NodeIndex, domain: FixedBitSet) -> Result<(Vec<NodeIndex>, SavedDomains), WFCError> {
+        let mut domains = std::mem::take(&mut self.domains);
+        let mut saved = vec![(node, std::mem::replace(&mut domains[node.index()], domain))];
+        let result = self.propagate(&mut domains, [node], &mut saved);
+        if result.is_err() {
+            for (node, domain) in saved.drain(..) {
+                domains[node.index()] = domain;
+            }
+        }
+        self.domains = domains;
+        result.map(|affected| (affected, saved))
+    }
+
+    /// AC-3 over the graph edges, starting from the arcs into `changed`.
+    /// Returns the nodes whose domains shrank, or the first node left empty.
+    /// Each domain is added to `saved` as it was before its first change,
+    /// unless `saved` already holds it.
+    fn propagate(
+        &mut self,
+        domains: &mut [FixedBitSet],
+        changed: impl IntoIterator<Item = NodeIndex>,
+        saved: &mut SavedDomains,
+    ) -> Result<Vec<NodeIndex>, WFCError> {
+        let mut queue: VecDeque<NodeIndex> = changed.into_iter().collect();
+        let mut queued = FixedBitSet::with_capacity(domains.len());
+        queue.iter().for_each(|node| queued.insert(node.index()));
+        let mut is_saved = FixedBitSet::with_capacity(domains.len());
+        saved.iter().for_each(|(node, _)| is_saved.insert(node.index()));
+        let mut affected = Vec::new();
+
+        while let Some(node) = queue.pop_front() {
+            queued.set(node.index(), false);
+            let neighbours: Vec<NodeIndex> = self.graph.neighbors_undirected(node).collect();
+            for neighbour in neighbours {
+                let dropped = self.revise(domains, neighbour, node);
+                if dropped.is_empty() {
+                    continue;
+                }
+                if !is_saved.put(neighbour.index()) {
+                    let mut before = domains[neighbour.index()].clone();
+                    dropped.iter().for_each(|&value| before.insert(value));
+                    saved.push((neighbour, before));
+                }
+                if domains[neighbour.index()].is_clear() {
+                    return Err(WFCError::Contradiction(neighbour));
+                }
+                if !affected.contains(&neighbour) {
+                    affected.push(neighbour);
+                }
+                if !queued.put(neighbour.index()) {
+                    queue.push_back(neighbour);
+                }
+            }
+        }
+        Ok(affected)
+    }
+
+    /// Drops the values of `node` that no value left at `support` is compatible
+    /// with. Returns the dropped values.
+    fn revise(&mut self, domains: &mut [FixedBitSet], node: NodeIndex, support: NodeIndex) -> Vec<usize> {
+        let (Some(position), Some(support_position)) = (self.position(node), self.position(support)) else {
+            return Vec::new();
+        };
+        let offset = Self::offset(position, support_position);
+        let supporting = domains[support.index()].clone();
+        let unsupported: Vec<usize> = domains[node.index()]
+            .ones()
+            .filter(|&value| {
+                !supporting.ones().any(|other| {
+                    let values = &self.values;
+                    self.compatibility.is_compatible(value, other, offset, || {
+                        (values.state(value, position), values.state(other, support_position))
+                    })
+                })
+            })
+            .collect();
+        for value in &unsupported {
+            domains[node.index()].set(*value, false);
+        }
+        unsupported
+    }
+
+    fn position(&self, node: NodeIndex) -> Option<(usize, usize, usize)> {
+        self.graph.node_weight(node).map(|state| state.position)
+    }
+
+    fn offset(from: (usize, usize, usize), to: (usize, usize, usize)) -> Offset {
+        (
+            to.0 as i64 - from.0 as i64,
+            to.1 as i64 - from.1 as i64,
+            to.2 as i64 - from.2 as i64,
+        )
+    }
+
+    fn collapse_node(&mut self, node: NodeIndex, rng: &mut dyn RngCore) -> Result<NodeState<T>, WFCError> {
         let node_state = self.graph.node_weight(node)
             .ok_or(WFCError::NodeNotFound(node))?;
 
@@ -131,48 +410,48 @@ impl<T: Block3DLike> WFCSolver<T> {
 
         // Get the current position from the node
         let current_position = node_state.position;
+        let domain = self.domains.get(node.index()).ok_or(WFCError::NodeNotFound(node))?;
 
-        // Get valid states based on invariants and connection constraints
-        let mut valid_states: Vec<NodeState<T>> = self.block_set
-            .iter()
-            .flat_map(|block| {
-                Orientation::iter().map(move |orientation| {
-                    // Use with_position to preserve the node's position and initialize connections
-                    NodeState::with_position(block.clone(), orientation, current_position)
-                })
-            })
-            .filter(|state| {
+        // Get valid states from the domain, invariants and connection constraints
+        let mut candidates: Vec<(usize, NodeState<T>)> = domain
+            .ones()
+            .map(|value| (value, self.values.state(value, current_position)))
+            .filter(|(_, state)| {
                 // Apply invariants
                 self.invariants.iter().all(|inv| inv.check(node, state, self)) &&
                 // Check for collisions
                 !self.would_collide(state) &&
-                // Check if it can connect to at least one existing block (unless it's the first block)
-                (self.collapsed.is_empty() || self.can_connect_to_existing(state))
+                // Check if it can connect to at least one placed neighbour (unless none is placed yet)
+                self.can_connect_to_existing(node, state)
             })
             .collect();
 
-        // If this is the first node, any valid state is acceptable
-        if self.collapsed.is_empty() {
-            valid_states = self.block_set
-                .iter()
-                .flat_map(|block| {
-                    Orientation::iter().map(move |orientation| {
-                        NodeState::with_position(block.clone(), orientation, current_position)
-                    })
-                })
-                .filter(|state| {
-                    self.invariants.iter().all(|inv| inv.check(node, state, self))
-                })
-                .collect();
-        }
-
-        if valid_states.is_empty() {
+        if candidates.is_empty() {
             return Err(WFCError::NoValidStatesAfterInvariants(node));
         }
 
-        // Use heuristic to select state if multiple options exist
-        let selected_state = self.heuristic.select_state_for_node(node, &valid_states)
-            .ok_or(WFCError::HeuristicFailure(node))?;
+        // Try candidates in the heuristic's order until one propagates without emptying a domain
+        let (value, selected_state, affected, saved) = loop {
+            let states: Vec<NodeState<T>> = candidates.iter().map(|(_, state)| state.clone()).collect();
+            let choice = self.heuristic.select_state_for_node(node, &states, rng)
+                .filter(|&choice| choice < candidates.len())
+                .ok_or(WFCError::HeuristicFailure(node))?;
+            let (value, state) = candidates.remove(choice);
+
+            let mut domain = FixedBitSet::with_capacity(self.values.len());
+            domain.insert(value);
+            match self.narrow(node, domain) {
+                Ok((affected, saved)) => break (value, state, affected, saved),
+                Err(WFCError::Contradiction(_)) if !candidates.is_empty() => continue,
+                Err(error) => return Err(error),
+            }
+        };
+
+        // Record what the collapse overwrites so backtracking can undo it
+        let mut decision = Decision::new(node, value, saved);
+        if let Some(node_weight) = self.graph.node_weight(node) {
+            decision.save(node, node_weight, self.connections.get(&node));
+        }
 
         // Update node state
         if let Some(node_weight) = self.graph.node_weight_mut(node) {
@@ -186,25 +465,29 @@ impl<T: Block3DLike> WFCSolver<T> {
             selected_state.position.2 as f32,
         );
         let size = (
-            selected_state.block.size().0 as f32,
-            selected_state.block.size().1 as f32,
-            selected_state.block.size().2 as f32,
+            selected_state.size().0 as f32,
+            selected_state.size().1 as f32,
+            selected_state.size().2 as f32,
         );
         self.spatial_grid.add_node(node, position, size);
 
         // Update connections
-        self.establish_connections(node, &selected_state)?;
+        self.establish_connections(node, &selected_state, &mut decision)?;
 
         // Mark as collapsed
         self.collapsed.insert(node);
+        self.trail.push(decision);
 
         // Notify observers
         self.notify_collapse(node, &selected_state);
 
-        // Propagate constraints to neighbors
-        let affected_nodes: Vec<NodeIndex> = self.invariants.iter()
-            .flat_map(|inv| inv.propagate(node, self))
-            .collect();
+        // Invariants may flag further nodes; their checks run when those nodes collapse
+        let mut affected_nodes = affected;
+        for extra in self.invariants.iter().flat_map(|inv| inv.propagate(node, self)) {
+            if !affected_nodes.contains(&extra) {
+                affected_nodes.push(extra);
+            }
+        }
 
         if !affected_nodes.is_empty() {
             self.notify_propagate(&affected_nodes);
@@ -217,9 +500,9 @@ impl<T: Block3DLike> WFCSolver<T> {
     fn would_collide(&self, state: &NodeState<T>) -> bool {
         let position = state.world_position();
         let size = (
-            state.block.size().0 as f32,
-            state.block.size().1 as f32,
-            state.block.size().2 as f32,
+            state.size().0 as f32,
+            state.size().1 as f32,
+            state.size().2 as f32,
         );
         
         // Get potential collision candidates
@@ -237,27 +520,25 @@ impl<T: Block3DLike> WFCSolver<T> {
         false
     }
     
-    // Check if a state can connect to at least one existing block
-    fn can_connect_to_existing(&self, state: &NodeState<T>) -> bool {
-        // Skip if no blocks exist yet
-        if self.collapsed.is_empty() {
+    // Check if a state can connect to at least one collapsed neighbour. Nodes can be
+    // collapsed in any order, so one with no collapsed neighbours yet is unconstrained.
+    fn can_connect_to_existing(&self, node: NodeIndex, state: &NodeState<T>) -> bool {
+        let mut placed = self.graph.neighbors_undirected(node)
+            .filter(|neighbour| self.collapsed.contains(neighbour))
+            .filter_map(|neighbour| self.graph.node_weight(neighbour))
+            .peekable();
+
+        // Skip if no neighbouring blocks exist yet
+        if placed.peek().is_none() {
             return true;
         }
-        
+
         // Check connection possibilities with neighboring blocks
-        for collapsed_node in &self.collapsed {
-            if let Some(collapsed_state) = self.graph.node_weight(*collapsed_node) {
-                if state.can_connect_to(collapsed_state).is_some() {
-                    return true;
-                }
-            }
-        }
-        
-        false
+        placed.any(|collapsed_state| state.can_connect_to(collapsed_state).is_some())
     }
     
     // Establish connections between a node and existing blocks
-    fn establish_connections(&mut self, node: NodeIndex, state: &NodeState<T>) -> Result<(), WFCError> {
+    fn establish_connections(&mut self, node: NodeIndex, state: &NodeState<T>, decision: &mut Decision<T>) -> Result<(), WFCError> {
         // Skip if no blocks exist yet
         if self.collapsed.is_empty() {
             return Ok(());
@@ -282,6 +563,9 @@ impl<T: Block3DLike> WFCSolver<T> {
         if !possible_connections.is_empty() {
             // For simplicity, just use the first compatible connection
             let (other_node, self_conn_id, other_conn_id) = &possible_connections[0];
+            if let Some(other_state) = self.graph.node_weight(*other_node) {
+                decision.save(*other_node, other_state, self.connections.get(other_node));
+            }
             
             // Update connection maps
             self.connections.entry(node).or_insert_with(HashMap::new)
@@ -325,9 +609,12 @@ impl<T: Block3DLike> WFCSolver<T> {
         }
     }
 
-    /// Collapses a specific node and propagates constraints to its neighbors.
-  
-    
+    fn notify_backtrack(&self, node: NodeIndex) {
+        for observer in &self.observers {
+            observer.on_backtrack(node);
+        }
+    }
+
     /// Find a node at the given grid position
     pub fn find_node_at_position(&self, position: (usize, usize, usize)) -> Option<NodeIndex> {
         for node_idx in self.graph.node_indices() {
@@ -341,16 +628,12 @@ impl<T: Block3DLike> WFCSolver<T> {
     }
 
     pub fn collapse_node_at_position(&mut self, position: (usize, usize, usize)) -> Result<(), WFCError> {
-
         let node_index = self.find_node_at_position(position)
             .ok_or(WFCError::NodeNotFoundAtPosition(position))?;
+        self.initialize_states()?;
 
-          // Collapse the specified node
-          match self.collapse_node(node_index) {
-            Ok(_) => {},
-            Err(e) => return Err(e), // Return error if initial collapse fails
-        }
-        
+        let mut rng = self.rng();
+        self.collapse_node(node_index, &mut rng)?;
         Ok(())
     }
     pub fn set_node_at_position(&mut self, position: (usize, usize, usize), block: T) -> Result<(), WFCError> {
@@ -365,3 +648,285 @@ impl<T: Block3DLike> WFCSolver<T> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::{Cell, RefCell};
+    use std::rc::Rc;
+    use crate::wfc::heuristics::{DistanceFromSeedHeuristic, EntropyHeuristic, ScanlineHeuristic};
+    use crate::wfc::solver::compatibility::CompatibilityRule;
+    use block3d_core::connection::{ConnectorInterface, OrientedInterface};
+    use block3d_core::face::{Direction, Face};
+    use block3d_core::symmetry::SymmetryClass;
+
+    /// A cube with the same interface on every side: pads only mate with balls.
+    #[derive(Clone, Debug, Default, PartialEq)]
+    struct Cube {
+        pad: bool,
+    }
+
+    impl Block3DLike for Cube {
+        fn size(&self) -> (u32, u32, u32) {
+            (1, 1, 1)
+        }
+
+        fn faces(&self) -> impl Iterator<Item = Face> {
+            let interface = if self.pad { ConnectorInterface::MetalPad } else { ConnectorInterface::SolderBall };
+            Direction::ALL.into_iter().map(move |direction| {
+                Face::new(direction, OrientedInterface { interface, orientation: Orientation::IDENTITY })
+            })
+        }
+
+        fn symbol(&self) -> String {
+            if self.pad { "P" } else { "B" }.to_string()
+        }
+
+        fn symmetry(&self) -> SymmetryClass {
+            SymmetryClass::cubic()
+        }
+    }
+
+    struct First;
+
+    impl Heuristic<Cube> for First {
+        fn select_node_to_collapse(&self, solver: &WFCSolver<Cube>, _: &mut dyn RngCore) -> Option<NodeIndex> {
+            solver.uncollapsed().next()
+        }
+
+        fn select_state_for_node(&self, _: NodeIndex, _: &[NodeState<Cube>], _: &mut dyn RngCore) -> Option<usize> {
+            Some(0)
+        }
+    }
+
+    fn line() -> WFCSolver<Cube> {
+        WFCSolver::new(WFCGraph::grid_graph((3, 1, 1)), [Cube { pad: true }, Cube { pad: false }], vec![], Box::new(First), vec![])
+            .with_compatibility(CompatibilityTable::new().with_rule(CompatibilityRule::touching_faces_mate()))
+    }
+
+    fn pads(solver: &WFCSolver<Cube>, x: usize) -> Vec<bool> {
+        let node = solver.find_node_at_position((x, 0, 0)).unwrap();
+        solver.candidates(node).map(|(cube, _)| cube.pad).collect()
+    }
+
+    #[test]
+    fn collapsing_propagates_along_edges() {
+        let mut solver = line();
+        solver.collapse_node_at_position((0, 0, 0)).unwrap();
+        assert_eq!([pads(&solver, 0), pads(&solver, 1), pads(&solver, 2)], [[true], [false], [true]]);
+    }
+
+    #[test]
+    fn contradictions_leave_domains_untouched() {
+        let mut solver = line();
+        let middle = solver.find_node_at_position((1, 0, 0)).unwrap();
+        solver.restrict(middle, |cube, _| cube.pad).unwrap();
+        assert_eq!(pads(&solver, 0), [false]);
+
+        let first = solver.find_node_at_position((0, 0, 0)).unwrap();
+        assert!(matches!(solver.restrict(first, |cube, _| cube.pad), Err(WFCError::Contradiction(node)) if node == first));
+        assert_eq!([pads(&solver, 0), pads(&solver, 1), pads(&solver, 2)], [[false], [true], [false]]);
+    }
+
+    #[test]
+    fn failed_narrowing_restores_every_domain() {
+        let mut solver = line();
+        let first = solver.find_node_at_position((0, 0, 0)).unwrap();
+        let middle = solver.find_node_at_position((1, 0, 0)).unwrap();
+        solver.restrict(first, |cube, _| cube.pad).unwrap();
+        let domains = solver.domains.clone();
+
+        // A pad in the middle leaves its neighbours nothing, partway through propagating.
+        let pad = (0..solver.values.len()).find(|&value| solver.values.block(value).pad).unwrap();
+        let mut domain = FixedBitSet::with_capacity(solver.values.len());
+        domain.insert(pad);
+        assert!(matches!(solver.narrow(middle, domain), Err(WFCError::Contradiction(_))));
+        assert_eq!(solver.domains, domains);
+    }
+
+    /// Position and whether a pad landed there.
+    type Placement = (usize, usize, usize, bool);
+
+    /// Records where blocks land, in collapse order.
+    #[derive(Clone, Default)]
+    struct Order(Rc<RefCell<Vec<Placement>>>);
+
+    impl WFCObserverLike<Cube> for Order {
+        fn on_collapse(&self, _: NodeIndex, state: &NodeState<Cube>) {
+            let (x, y, z) = state.position;
+            self.0.borrow_mut().push((x, y, z, state.block.pad));
+        }
+
+        fn on_propagate(&self, _: &[NodeIndex]) {}
+    }
+
+    fn solve(dimensions: (usize, usize, usize), heuristic: Box<dyn Heuristic<Cube>>, seed: u64) -> Vec<Placement> {
+        let order = Order::default();
+        let mut solver = WFCSolver::new(
+            WFCGraph::grid_graph(dimensions),
+            [Cube { pad: true }, Cube { pad: false }],
+            vec![],
+            heuristic,
+            vec![Box::new(order.clone())],
+        )
+        .with_compatibility(CompatibilityTable::new().with_rule(CompatibilityRule::touching_faces_mate()))
+        .with_seed(seed);
+        solver.solve().unwrap();
+        assert_eq!(solver.uncollapsed().count(), 0);
+        order.0.take()
+    }
+
+    #[test]
+    fn entropy_follows_block_rankings() {
+        assert!((EntropyHeuristic::entropy([1.0, 1.0]) - 2f64.ln()).abs() < 1e-12);
+        assert_eq!(EntropyHeuristic::entropy([5.0]), 0.0);
+        assert!(EntropyHeuristic::entropy([9.0, 1.0]) < EntropyHeuristic::entropy([1.0, 1.0]));
+        assert!((EntropyHeuristic::entropy([0.0, 0.0, 0.0]) - 3f64.ln()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn seeded_entropy_runs_repeat() {
+        let run = solve((5, 1, 1), Box::new(EntropyHeuristic), 11);
+        assert_eq!(run, solve((5, 1, 1), Box::new(EntropyHeuristic), 11));
+        // Neighbouring cubes always alternate between pads and balls.
+        let mut by_x = run.clone();
+        by_x.sort();
+        assert!(by_x.windows(2).all(|pair| pair[0].3 != pair[1].3));
+    }
+
+    #[test]
+    fn alternative_heuristics_set_the_order() {
+        let positions = |run: Vec<Placement>| run.into_iter().map(|(x, y, z, _)| (x, y, z)).collect::<Vec<_>>();
+        assert_eq!(
+            positions(solve((2, 2, 1), Box::new(ScanlineHeuristic), 3)),
+            [(0, 0, 0), (1, 0, 0), (0, 1, 0), (1, 1, 0)]
+        );
+        assert_eq!(
+            positions(solve((4, 1, 1), Box::new(DistanceFromSeedHeuristic::new((3, 0, 0))), 3)),
+            [(3, 0, 0), (2, 0, 0), (1, 0, 0), (0, 0, 0)]
+        );
+    }
+
+    /// Rejects pads at the listed x positions; arc consistency can't see this.
+    struct NoPadAt(Vec<usize>);
+
+    impl WFCInvariant<Cube> for NoPadAt {
+        fn check(&self, _: NodeIndex, state: &NodeState<Cube>, _: &WFCSolver<Cube>) -> bool {
+            !(state.block.pad && self.0.contains(&state.position.0))
+        }
+
+        fn propagate(&self, _: NodeIndex, _: &WFCSolver<Cube>) -> Vec<NodeIndex> {
+            Vec::new()
+        }
+    }
+
+    /// Counts undone collapses.
+    #[derive(Clone, Default)]
+    struct Backtracks(Rc<RefCell<usize>>);
+
+    impl WFCObserverLike<Cube> for Backtracks {
+        fn on_collapse(&self, _: NodeIndex, _: &NodeState<Cube>) {}
+
+        fn on_propagate(&self, _: &[NodeIndex]) {}
+
+        fn on_backtrack(&self, _: NodeIndex) {
+            *self.0.borrow_mut() += 1;
+        }
+    }
+
+    fn forbidding(xs: Vec<usize>, backtracks: &Backtracks) -> WFCSolver<Cube> {
+        let mut solver = line();
+        solver.invariants.push(Box::new(NoPadAt(xs)));
+        solver.add_observer(Box::new(backtracks.clone()));
+        solver
+    }
+
+    #[test]
+    fn backtracking_undoes_collapses_exactly() {
+        let mut solver = line();
+        solver.initialize_states().unwrap();
+        let mut rng = solver.rng();
+        let first = solver.find_node_at_position((0, 0, 0)).unwrap();
+        let second = solver.find_node_at_position((1, 0, 0)).unwrap();
+        solver.collapse_node(first, &mut rng).unwrap();
+
+        let domains = solver.domains.clone();
+        let connections = solver.connections.clone();
+        let before = solver.graph[first].clone();
+        solver.collapse_node(second, &mut rng).unwrap();
+        assert!(solver.graph[first].is_connected);
+
+        let decision = solver.trail.pop().unwrap();
+        // Only what the collapse narrowed is kept, never the settled first node
+        assert!(!decision.domains.is_empty() && decision.domains.iter().all(|(node, _)| *node != first));
+        solver.undo(decision);
+        assert_eq!(solver.domains, domains);
+        assert_eq!(solver.connections, connections);
+        assert_eq!(solver.collapsed, HashSet::from([first]));
+        assert_eq!(solver.graph[first].is_connected, before.is_connected);
+        assert!(solver.graph[first].connections.values().all(|point| point.connected_to.is_none()));
+        assert!(solver.spatial_grid.potential_collisions((1.0, 0.0, 0.0), (0.5, 0.5, 0.5)).iter().all(|&node| node != second));
+    }
+
+    #[test]
+    fn dead_ends_retry_untried_choices() {
+        // `First` puts a pad at x = 0, which forces one at x = 2 as well.
+        let backtracks = Backtracks::default();
+        let mut solver = forbidding(vec![2], &backtracks);
+        solver.solve().unwrap();
+        let placed: Vec<bool> = (0..3)
+            .map(|x| solver.graph[solver.find_node_at_position((x, 0, 0)).unwrap()].block.pad)
+            .collect();
+        assert_eq!(placed, [false, true, false]);
+        assert_eq!(*backtracks.0.borrow(), 2);
+        assert_eq!(solver.connections.values().map(HashMap::len).sum::<usize>(), 4);
+    }
+
+    #[test]
+    fn exhausted_search_reports_progress() {
+        let backtracks = Backtracks::default();
+        let mut solver = forbidding(vec![0, 1, 2], &backtracks);
+        let Err(WFCError::Unsolved(report)) = solver.solve() else { panic!("expected no solution") };
+        assert_eq!(report.total, 3);
+        assert_eq!(report.deepest, [((0, 0, 0), "B".to_string())]);
+        assert_eq!(report.failed_at, solver.find_node_at_position((0, 0, 0)));
+        assert_eq!((report.backtracks, report.restarts), (1, 0));
+        assert!(solver.collapsed.is_empty());
+    }
+
+    #[test]
+    fn backtrack_limit_triggers_restarts() {
+        let backtracks = Backtracks::default();
+        let mut solver = forbidding(vec![2], &backtracks).with_max_backtracks(0).with_max_restarts(2).with_seed(5);
+        let Err(WFCError::Unsolved(report)) = solver.solve() else { panic!("expected to give up") };
+        assert_eq!((report.backtracks, report.restarts), (0, 2));
+        assert_eq!(report.deepest.len(), 2);
+        // Two decisions undone per attempt, the last one included
+        assert_eq!(*backtracks.0.borrow(), 6);
+        assert!(solver.collapsed.is_empty());
+    }
+
+    /// Picks the first state once, then hands back a choice out of range.
+    struct GivesUp(Cell<bool>);
+
+    impl Heuristic<Cube> for GivesUp {
+        fn select_node_to_collapse(&self, solver: &WFCSolver<Cube>, _: &mut dyn RngCore) -> Option<NodeIndex> {
+            solver.uncollapsed().next()
+        }
+
+        fn select_state_for_node(&self, _: NodeIndex, _: &[NodeState<Cube>], _: &mut dyn RngCore) -> Option<usize> {
+            Some(if self.0.replace(true) { usize::MAX } else { 0 })
+        }
+    }
+
+    #[test]
+    fn heuristic_failures_undo_the_trail() {
+        let mut solver = line().with_heuristic(Box::new(GivesUp(Cell::new(false))));
+        solver.initialize_states().unwrap();
+        let domains = solver.domains.clone();
+        let second = solver.find_node_at_position((1, 0, 0)).unwrap();
+        assert!(matches!(solver.solve(), Err(WFCError::HeuristicFailure(node)) if node == second));
+        assert!(solver.collapsed.is_empty() && solver.trail.is_empty());
+        assert_eq!(solver.domains, domains);
+    }
+}
@@ -1,5 +1,4 @@
 use block3d_core::{block::Block3DLike, Orientation};
-use strum::IntoEnumIterator;
 use std::collections::HashMap;
 use crate::connection::ConnectionPoint;
 
@@ -40,44 +39,51 @@ impl<T: Block3DLike> NodeState<T> {
         // Clear existing connections
         self.connections.clear();
         
-        // Get the block's interface definitions
-        let faces = self.block.faces();
-        
-        // Create connection points for each face
-        for (index, face) in faces.enumerate() {
-            let interface = face.oriented_interface();
-            // Create a connection point with a unique ID
-            let conn_id = format!("conn_{}", index);
-                
-            // Calculate position offset based on face position and block size
-            let size = self.block.size();
-            let pos_offset = match index {
-                0 => (0.0, size.1 as f32, 0.0), // Top face
-                1 => (0.0, 0.0, 0.0),           // Bottom face
-                // Add other faces as needed
-                _ => (0.0, 0.0, 0.0),
-            };
-            
-            // Create connection point
-            let conn_point = ConnectionPoint::new(
-                interface.clone(),
-                pos_offset,
-            );
-            
-            self.connections.insert(conn_id, conn_point);
+        // Create connection points for each face, turning its interface with the block
+        for (index, face) in self.block.faces().enumerate() {
+            let mut interface = face.oriented_interface();
+            interface.orientation = self.orientation.compose(interface.orientation);
+            let conn_point = ConnectionPoint::new(interface, face.direction(), face.position_offset());
+            self.connections.insert(format!("conn_{}", index), conn_point);
         }
     }
     
-    // Check if this node can connect to another node
+    // Check if this node can connect to another node through a pair of touching faces
     pub fn can_connect_to(&self, other: &NodeState<T>) -> Option<(String, String)> {
-        for (self_id, self_conn) in &self.connections {
-            for (other_id, other_conn) in &other.connections {
-                if self_conn.is_compatible_with(&other_conn.interface) {
-                    return Some((self_id.clone(), other_id.clone()));
-                }
-            }
+        let mut pairs: Vec<_> = self.touching_pairs(other)
+            .filter(|((_, self_conn), (_, other_conn))| self_conn.is_compatible_with(&other_conn.interface))
+            .map(|((self_id, _), (other_id, _))| (self_id.clone(), other_id.clone()))
+            .collect();
+        // Connection maps are unordered; pick the same pair every time
+        pairs.sort();
+        pairs.into_iter().next()
+    }
+
+    // Whether every pair of faces where the blocks touch forms a connection
+    pub fn faces_mate_with(&self, other: &NodeState<T>) -> bool {
+        self.touching_pairs(other)
+            .all(|((_, self_conn), (_, other_conn))| self_conn.is_compatible_with(&other_conn.interface))
+    }
+
+    // Connection points of the two nodes that look at each other from the same spot
+    fn touching_pairs<'a>(
+        &'a self,
+        other: &'a NodeState<T>,
+    ) -> impl Iterator<Item = ((&'a String, &'a ConnectionPoint), (&'a String, &'a ConnectionPoint))> + 'a {
+        self.connections.iter()
+            .flat_map(|a| other.connections.iter().map(move |b| (a, b)))
+            .filter(|((self_id, self_conn), (other_id, other_conn))| {
+                self_conn.direction.oriented(self.orientation) == other_conn.direction.oriented(other.orientation).opposite()
+                    && self.touches(self_id, other, other_id)
+            })
+    }
+
+    // Whether two connection points sit at the same place in the world
+    fn touches(&self, self_id: &str, other: &NodeState<T>, other_id: &str) -> bool {
+        match (self.connection_position(self_id), other.connection_position(other_id)) {
+            (Some(a), Some(b)) => (a.0 - b.0).abs() < 1e-4 && (a.1 - b.1).abs() < 1e-4 && (a.2 - b.2).abs() < 1e-4,
+            _ => false,
         }
-        None
     }
     
     // Calculate world position of the block in floating point for precise positioning
@@ -85,6 +91,17 @@ impl<T: Block3DLike> NodeState<T> {
         (self.position.0 as f32, self.position.1 as f32, self.position.2 as f32)
     }
     
+    // Extent of the oriented block along the world axes
+    pub fn size(&self) -> (u32, u32, u32) {
+        self.orientation.apply_size(self.block.size())
+    }
+
+    // World position of one of this block's connection points
+    pub fn connection_position(&self, id: &str) -> Option<(f32, f32, f32)> {
+        let connection = self.connections.get(id)?;
+        Some(connection.world_position(self.world_position(), self.orientation, self.block.size()))
+    }
+
     // Check if this block collides with another block
     pub fn collides_with(&self, other: &NodeState<T>) -> bool {
         // Simple AABB collision check - can be improved with more precise collision detection
@@ -92,15 +109,15 @@ impl<T: Block3DLike> NodeState<T> {
         let other_pos = other.world_position();
         
         let self_size = (
-            self.block.size().0 as f32,
-            self.block.size().1 as f32,
-            self.block.size().2 as f32
+            self.size().0 as f32,
+            self.size().1 as f32,
+            self.size().2 as f32
         );
         
         let other_size = (
-            other.block.size().0 as f32,
-            other.block.size().1 as f32,
-            other.block.size().2 as f32
+            other.size().0 as f32,
+            other.size().1 as f32,
+            other.size().2 as f32
         );
         
         // AABB collision check
@@ -113,7 +130,7 @@ impl<T: Block3DLike> NodeState<T> {
     }
     
     fn orientation_iter() -> impl Iterator<Item = Orientation> {
-        Orientation::iter()
+        Orientation::rotations()
     }
 }
 
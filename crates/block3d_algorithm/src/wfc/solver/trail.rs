@@ -0,0 +1,54 @@
+use std::collections::HashMap;
+
+use block3d_core::block::Block3DLike;
+use fixedbitset::FixedBitSet;
+use petgraph::graph::NodeIndex;
+
+use super::state::NodeState;
+
+/// Connector id to the node and connector it is joined with.
+pub type ConnectionMap = HashMap<String, (NodeIndex, String)>;
+
+/// Domains as they were before a change, one entry per node it touched.
+pub type SavedDomains = Vec<(NodeIndex, FixedBitSet)>;
+
+/// One collapse on the search trail, with everything it overwrote so it can
+/// be undone exactly.
+pub struct Decision<T: Block3DLike> {
+    pub node: NodeIndex,
+    /// Value picked for `node`; see [`super::domain::ValueSpace`].
+    pub value: usize,
+    /// Domains the collapse (and any later refutation at this depth) narrowed,
+    /// as they were before, first write wins.
+    pub domains: SavedDomains,
+    /// Node weights as they were before the collapse, first write wins.
+    pub states: Vec<(NodeIndex, NodeState<T>)>,
+    /// Connection map entries as they were before the collapse.
+    pub connections: Vec<(NodeIndex, Option<ConnectionMap>)>,
+}
+
+impl<T: Block3DLike> Decision<T> {
+    pub fn new(node: NodeIndex, value: usize, domains: SavedDomains) -> Self {
+        Self { node, value, domains, states: Vec::new(), connections: Vec::new() }
+    }
+
+    /// Adds domains narrowed after the collapse, keeping any saved earlier.
+    pub fn save_domains(&mut self, domains: SavedDomains) {
+        for (node, domain) in domains {
+            if !self.domains.iter().any(|(saved, _)| *saved == node) {
+                self.domains.push((node, domain));
+            }
+        }
+    }
+
+    /// Remembers `node`'s weight and connections unless an earlier write in this
+    /// decision already did.
+    pub fn save(&mut self, node: NodeIndex, state: &NodeState<T>, connections: Option<&ConnectionMap>) {
+        if !self.states.iter().any(|(saved, _)| *saved == node) {
+            self.states.push((node, state.clone()));
+        }
+        if !self.connections.iter().any(|(saved, _)| *saved == node) {
+            self.connections.push((node, connections.cloned()));
+        }
+    }
+}
@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 use strum::AsRefStr;
 use strum::EnumProperty;
 use crate::face::Face;
+use crate::symmetry::SymmetryClass;
 use strum::EnumIter;
 
 
@@ -23,6 +24,11 @@ pub trait Block3DLike: Clone + Default {
     }
 
     fn symbol(&self) -> String;
+
+    /// Orientations that leave this block unchanged, so the solver can skip equivalent ones
+    fn symmetry(&self) -> SymmetryClass {
+        SymmetryClass::NONE
+    }
     
     /// Check if this block can be placed at a given position without conflicts
     fn can_place_at(&self, _position: (i32, i32, i32)) -> bool {
@@ -0,0 +1,284 @@
+//! Loadable rules saying which interface pairs mate and through which connector.
+//!
+//! Tables are written in RON or JSON as a list of rules. Each rule names an
+//! unordered pair of interfaces (either side may be `Any`), the connector the
+//! pair forms and, optionally, how the two sides must be oriented relative to
+//! each other. Tables are generic over the interface and connector types, so a
+//! process that needs interfaces the enums don't know about can load a
+//! `CompatibilityTable<String, String>` from the same format.
+//!
+//! A loaded table takes effect once passed to
+//! [`install_semiconductor_rules`](super::install_semiconductor_rules) or
+//! [`install_analog_rules`](super::install_analog_rules).
+//!
+//! ```ron
+//! (rules: [
+//!     (between: (Is(MetalPad), Is(SolderBall)), connector: ElectricalConnection),
+//!     (between: (Is(MountingSurface), Is(MountingSurface)), connector: MechanicalBond, orientation: Proper),
+//!     (between: (Is(TestPoint), Any), connector: ElectricalWire),
+//! ])
+//! ```
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::Orientation;
+
+#[derive(Debug, Error)]
+pub enum CompatibilityError {
+    #[error("failed to read compatibility table: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("invalid RON compatibility table: {0}")]
+    Ron(#[from] ron::error::SpannedError),
+    #[error("invalid JSON compatibility table: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("unsupported compatibility table format {0:?}, expected .ron or .json")]
+    UnsupportedFormat(String),
+    #[error("rule {index} repeats the pair {pair}")]
+    Duplicate { index: usize, pair: String },
+    #[error("rule {index} gives {pair} a different connector or constraint than its mirror")]
+    Asymmetric { index: usize, pair: String },
+    #[error("rule {index}: relative orientations must include the inverse of each entry")]
+    OneSidedOrientation { index: usize },
+}
+
+/// One side of a rule.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InterfaceMatch<I> {
+    Any,
+    Is(I),
+}
+
+impl<I: PartialEq> InterfaceMatch<I> {
+    pub fn matches(&self, interface: &I) -> bool {
+        match self {
+            InterfaceMatch::Any => true,
+            InterfaceMatch::Is(i) => i == interface,
+        }
+    }
+}
+
+/// How the two sides of a connection must be oriented relative to each other.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OrientationConstraint {
+    #[default]
+    Any,
+    /// Both sides carry the same orientation.
+    Aligned,
+    /// The sides differ by a proper rotation; mirrored parts don't mate.
+    Proper,
+    /// The orientation taking the left side to the right one is in the list.
+    /// Must be closed under inverse so the rule reads the same from either side.
+    Relative(Vec<Orientation>),
+}
+
+impl OrientationConstraint {
+    pub fn allows(&self, left: Orientation, right: Orientation) -> bool {
+        let relative = left.inverse().compose(right);
+        match self {
+            OrientationConstraint::Any => true,
+            OrientationConstraint::Aligned => left == right,
+            OrientationConstraint::Proper => relative.is_rotation(),
+            OrientationConstraint::Relative(allowed) => allowed.contains(&relative),
+        }
+    }
+
+    fn is_symmetric(&self) -> bool {
+        match self {
+            OrientationConstraint::Relative(allowed) => allowed.iter().all(|o| allowed.contains(&o.inverse())),
+            _ => true,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CompatibilityRule<I, C> {
+    pub between: (InterfaceMatch<I>, InterfaceMatch<I>),
+    pub connector: C,
+    #[serde(default)]
+    pub orientation: OrientationConstraint,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct TableFile<I, C> {
+    rules: Vec<CompatibilityRule<I, C>>,
+}
+
+/// Validated rule set. Exact pairs win over rules with an `Any` side, which are
+/// tried in file order.
+#[derive(Clone, Debug)]
+pub struct CompatibilityTable<I, C> {
+    rules: Vec<CompatibilityRule<I, C>>,
+    exact: HashMap<(I, I), usize>,
+    wildcards: Vec<usize>,
+}
+
+impl<I, C> CompatibilityTable<I, C>
+where
+    I: Clone + Eq + Hash + std::fmt::Debug,
+    C: Clone + PartialEq,
+{
+    /// Validates `rules`: every pair is listed once, a pair spelled in both
+    /// orders must agree, and relative orientations must read the same from
+    /// either side.
+    pub fn new(rules: Vec<CompatibilityRule<I, C>>) -> Result<Self, CompatibilityError> {
+        let mut exact = HashMap::new();
+        let mut wildcards = Vec::new();
+        for (index, rule) in rules.iter().enumerate() {
+            if !rule.orientation.is_symmetric() {
+                return Err(CompatibilityError::OneSidedOrientation { index });
+            }
+            let (InterfaceMatch::Is(a), InterfaceMatch::Is(b)) = &rule.between else {
+                wildcards.push(index);
+                continue;
+            };
+            let pair = format!("({a:?}, {b:?})");
+            if let Some(&previous) = exact.get(&(a.clone(), b.clone())) {
+                let previous: &CompatibilityRule<I, C> = &rules[previous];
+                let agrees = previous.connector == rule.connector && previous.orientation == rule.orientation;
+                return Err(if agrees || previous.between == rule.between {
+                    CompatibilityError::Duplicate { index, pair }
+                } else {
+                    CompatibilityError::Asymmetric { index, pair }
+                });
+            }
+            exact.insert((a.clone(), b.clone()), index);
+            exact.insert((b.clone(), a.clone()), index);
+        }
+        Ok(Self { rules, exact, wildcards })
+    }
+
+    pub fn rules(&self) -> &[CompatibilityRule<I, C>] {
+        &self.rules
+    }
+
+    /// The rule pairing `a` with `b`, whichever order it was written in.
+    pub fn rule(&self, a: &I, b: &I) -> Option<&CompatibilityRule<I, C>> {
+        if let Some(&index) = self.exact.get(&(a.clone(), b.clone())) {
+            return Some(&self.rules[index]);
+        }
+        self.wildcards.iter().map(|&index| &self.rules[index]).find(|rule| {
+            let (left, right) = &rule.between;
+            (left.matches(a) && right.matches(b)) || (left.matches(b) && right.matches(a))
+        })
+    }
+
+    /// The connector `a` and `b` form, ignoring orientation.
+    pub fn connector(&self, a: &I, b: &I) -> Option<C> {
+        self.rule(a, b).map(|rule| rule.connector.clone())
+    }
+
+    /// The connector formed by `a` and `b` oriented as given, if the rule allows it.
+    pub fn connector_oriented(&self, (a, a_orientation): (&I, Orientation), (b, b_orientation): (&I, Orientation)) -> Option<C> {
+        self.rule(a, b)
+            .filter(|rule| rule.orientation.allows(a_orientation, b_orientation))
+            .map(|rule| rule.connector.clone())
+    }
+}
+
+impl<I, C> CompatibilityTable<I, C>
+where
+    I: Clone + Eq + Hash + std::fmt::Debug + for<'de> Deserialize<'de>,
+    C: Clone + PartialEq + for<'de> Deserialize<'de>,
+{
+    pub fn from_ron(source: &str) -> Result<Self, CompatibilityError> {
+        let file: TableFile<I, C> = ron::from_str(source)?;
+        Self::new(file.rules)
+    }
+
+    pub fn from_json(source: &str) -> Result<Self, CompatibilityError> {
+        let file: TableFile<I, C> = serde_json::from_str(source)?;
+        Self::new(file.rules)
+    }
+
+    /// Loads a `.ron` or `.json` table.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, CompatibilityError> {
+        let path = path.as_ref();
+        let source = std::fs::read_to_string(path)?;
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("ron") => Self::from_ron(&source),
+            Some("json") => Self::from_json(&source),
+            other => Err(CompatibilityError::UnsupportedFormat(other.unwrap_or_default().to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connection::{install_semiconductor_rules, semiconductor_rules, AnalogInterface, Connector};
+    use crate::connection::{ConnectorInterface, OrientedInterface};
+    use crate::face::{Direction, Face};
+    use crate::orientation::Axis;
+
+    #[test]
+    fn builtin_tables_are_valid_and_symmetric() {
+        use ConnectorInterface::*;
+        let table = semiconductor_rules();
+        assert_eq!(table.connector(&PassiveSurface, &ThermalPad), table.connector(&ThermalPad, &PassiveSurface));
+        assert_eq!(table.connector(&WireBondPad, &MetalPad), table.connector(&MetalPad, &WireBondPad));
+        assert_eq!(table.connector(&AirGap, &Via), None);
+
+        use AnalogInterface::*;
+        assert!(TestPoint.can_connect_to(&DifferentialInputPlus));
+        assert!(DifferentialInputPlus.can_connect_to(&DifferentialInputMinus));
+        assert!(!DifferentialInputPlus.can_connect_to(&DifferentialInputPlus));
+        assert!(!PowerPin.can_connect_to(&GroundPin));
+    }
+
+    #[test]
+    fn tables_reject_mismatched_mirrors_and_check_orientation() {
+        let mirrored = r#"(rules: [
+            (between: (Is("pad"), Is("ball")), connector: "bump"),
+            (between: (Is("ball"), Is("pad")), connector: "wire"),
+        ])"#;
+        assert!(matches!(
+            CompatibilityTable::<String, String>::from_ron(mirrored),
+            Err(CompatibilityError::Asymmetric { index: 1, .. })
+        ));
+
+        let quarter = Orientation::about(Axis::Y, 1);
+        let one_sided = CompatibilityRule {
+            between: (InterfaceMatch::Is(0), InterfaceMatch::Any),
+            connector: (),
+            orientation: OrientationConstraint::Relative(vec![quarter]),
+        };
+        assert!(matches!(
+            CompatibilityTable::new(vec![one_sided]),
+            Err(CompatibilityError::OneSidedOrientation { index: 0 })
+        ));
+
+        let json = r#"{"rules": [{"between": [{"Is": "rail"}, {"Is": "rail"}], "connector": "busbar", "orientation": "Aligned"}]}"#;
+        let table = CompatibilityTable::<String, String>::from_json(json).unwrap();
+        let rail = "rail".to_string();
+        assert_eq!(table.connector_oriented((&rail, quarter), (&rail, quarter)), Some("busbar".to_string()));
+        assert_eq!(table.connector_oriented((&rail, quarter), (&rail, Orientation::IDENTITY)), None);
+    }
+
+    #[test]
+    fn installed_tables_change_what_connects() {
+        // Other tests share the installed rules, so only add a pair none of them uses.
+        let interface = OrientedInterface { interface: ConnectorInterface::AirGap, orientation: Orientation::IDENTITY };
+        let gap = |direction| Face::new(direction, interface);
+        assert!(!gap(Direction::PosZ).can_connect_to(&gap(Direction::NegZ)));
+
+        let builtin = include_str!("semiconductor.ron").trim_end().strip_suffix("])").unwrap();
+        let path = std::env::temp_dir().join(format!("block3d_rules_{}.ron", std::process::id()));
+        let extra = "(between: (Is(AirGap), Is(AirGap)), connector: ThermalConduction),";
+        std::fs::write(&path, format!("{builtin}    {extra}\n])")).unwrap();
+        let loaded = CompatibilityTable::load(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        let previous = install_semiconductor_rules(loaded.unwrap());
+        let connects = gap(Direction::PosZ).can_connect_to(&gap(Direction::NegZ));
+        let connector = semiconductor_rules().connector(&ConnectorInterface::AirGap, &ConnectorInterface::AirGap);
+        install_semiconductor_rules(previous);
+        assert!(connects);
+        assert_eq!(connector, Some(Connector::ThermalConduction));
+        assert!(!gap(Direction::PosZ).can_connect_to(&gap(Direction::NegZ)));
+    }
+}
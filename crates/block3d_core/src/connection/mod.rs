@@ -1,8 +1,13 @@
 use std::ops::Add;
+use std::sync::{Arc, OnceLock, PoisonError, RwLock};
 use serde::{Deserialize, Serialize};
 
 use crate::Orientation;
 
+pub mod compatibility;
+
+pub use compatibility::{CompatibilityError, CompatibilityRule, CompatibilityTable, InterfaceMatch, OrientationConstraint};
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Connector {
     /// Thermal conduction path
@@ -88,50 +93,8 @@ impl Add for OrientedInterface {
     type Output = Option<OrientedConnection>;
 
     fn add(self, rhs: Self) -> Self::Output {
-        use Connector::*;
-        
-        // Helper macro to build an OrientedConnection
-        macro_rules! connection {
-            ($c:expr) => {
-                Some(OrientedConnection {
-                    left: self,
-                    connector: Some($c),
-                    right: rhs,
-                })
-            };
-        }
-
-        match (self.interface, rhs.interface) {
-            // Die to substrate thermal connection
-            (ConnectorInterface::PassiveSurface, ConnectorInterface::ThermalPad) | 
-            (ConnectorInterface::ThermalPad, ConnectorInterface::PassiveSurface) => connection!(ThermalConduction),
-            
-            // Electrical connections
-            (ConnectorInterface::MetalPad, ConnectorInterface::SolderBall) | 
-            (ConnectorInterface::SolderBall, ConnectorInterface::MetalPad) => connection!(ElectricalConnection),
-            
-            (ConnectorInterface::WireBondPad, ConnectorInterface::MetalPad) => connection!(ElectricalConnection),
-            
-            // Thermal interface connections
-            (ConnectorInterface::ThermalPad, ConnectorInterface::HeatSinkInterface) | 
-            (ConnectorInterface::HeatSinkInterface, ConnectorInterface::ThermalPad) => connection!(ThermalInterface),
-            
-            // Mechanical connections
-            (ConnectorInterface::MountingSurface, ConnectorInterface::MountingSurface) => connection!(MechanicalBond),
-            
-            // Die attach
-            (ConnectorInterface::ActiveSurface, ConnectorInterface::ThermalPad) => connection!(DieAttach),
-            
-            // PCB connections
-            (ConnectorInterface::SolderBall, ConnectorInterface::PCBTrace) | 
-            (ConnectorInterface::PCBTrace, ConnectorInterface::SolderBall) => connection!(PackageConnection),
-            
-            // Via connections
-            (ConnectorInterface::Via, ConnectorInterface::PCBTrace) | 
-            (ConnectorInterface::PCBTrace, ConnectorInterface::Via) => connection!(ElectricalConnection),
-            
-            _ => None,
-        }
+        let rules = semiconductor_table().read().unwrap_or_else(PoisonError::into_inner);
+        self.connect_with(rhs, &rules)
     }
 }
 
@@ -140,6 +103,19 @@ impl OrientedInterface {
     pub fn connect(self, other: OrientedInterface) -> Option<OrientedConnection> {
         self + other
     }
+
+    /// Connects through a custom rule set instead of the installed one.
+    pub fn connect_with(
+        self,
+        other: OrientedInterface,
+        rules: &CompatibilityTable<ConnectorInterface, Connector>,
+    ) -> Option<OrientedConnection> {
+        let connector = rules.connector_oriented(
+            (&self.interface, self.orientation),
+            (&other.interface, other.orientation),
+        )?;
+        Some(OrientedConnection { left: self, connector: Some(connector), right: other })
+    }
 }
 
 /// Analog circuit specific connectors
@@ -197,42 +173,56 @@ pub enum AnalogInterface {
 impl AnalogInterface {
     /// Check if this interface can connect to another
     pub fn can_connect_to(&self, other: &Self) -> bool {
-        use AnalogInterface::*;
-        match (self, other) {
-            // Power connections
-            (PowerPin, PowerPin) => true,
-            (GroundPin, GroundPin) => true,
-            
-            // Signal connections
-            (SignalOutput, SignalInput) => true,
-            (SignalInput, SignalOutput) => true,
-            (AnalogOutput, AnalogInput) => true,
-            (AnalogInput, AnalogOutput) => true,
-            
-            // Differential pairs
-            (DifferentialInputPlus, DifferentialInputPlus) => false, // Can't connect same polarity
-            (DifferentialInputMinus, DifferentialInputMinus) => false,
-            (DifferentialInputPlus, DifferentialInputMinus) => true,
-            (DifferentialInputMinus, DifferentialInputPlus) => true,
-            
-            // Test points can connect to anything
-            (TestPoint, _) => true,
-            (_, TestPoint) => true,
-            
-            // Component pins are general purpose
-            (ComponentPin, _) => true,
-            (_, ComponentPin) => true,
-            
-            // Control and feedback
-            (ControlInput, SignalOutput) => true,
-            (SignalOutput, ControlInput) => true,
-            (FeedbackConnection, _) => true,
-            (_, FeedbackConnection) => true,
-            
-            _ => false,
-        }
+        self.connector(other).is_some()
+    }
+
+    /// The connector joining this interface to another under the installed rules.
+    pub fn connector(&self, other: &Self) -> Option<AnalogConnector> {
+        analog_table().read().unwrap_or_else(PoisonError::into_inner).connector(self, other)
     }
 }
 
+fn semiconductor_table() -> &'static RwLock<Arc<CompatibilityTable<ConnectorInterface, Connector>>> {
+    static RULES: OnceLock<RwLock<Arc<CompatibilityTable<ConnectorInterface, Connector>>>> = OnceLock::new();
+    RULES.get_or_init(|| {
+        let builtin = CompatibilityTable::from_ron(include_str!("semiconductor.ron"));
+        RwLock::new(Arc::new(builtin.expect("built-in semiconductor rules are valid")))
+    })
+}
+
+fn analog_table() -> &'static RwLock<Arc<CompatibilityTable<AnalogInterface, AnalogConnector>>> {
+    static RULES: OnceLock<RwLock<Arc<CompatibilityTable<AnalogInterface, AnalogConnector>>>> = OnceLock::new();
+    RULES.get_or_init(|| {
+        let builtin = CompatibilityTable::from_ron(include_str!("analog.ron"));
+        RwLock::new(Arc::new(builtin.expect("built-in analog rules are valid")))
+    })
+}
+
+/// The semiconductor rules `+`, [`Face::can_connect_to`](crate::face::Face::can_connect_to)
+/// and the WFC solver go by: those from `semiconductor.ron` until a table is installed.
+pub fn semiconductor_rules() -> Arc<CompatibilityTable<ConnectorInterface, Connector>> {
+    semiconductor_table().read().unwrap_or_else(PoisonError::into_inner).clone()
+}
 
+/// Replaces the semiconductor rules process-wide, e.g. with a table from
+/// [`CompatibilityTable::load`]. Returns the rules it replaced.
+pub fn install_semiconductor_rules(
+    rules: impl Into<Arc<CompatibilityTable<ConnectorInterface, Connector>>>,
+) -> Arc<CompatibilityTable<ConnectorInterface, Connector>> {
+    let mut installed = semiconductor_table().write().unwrap_or_else(PoisonError::into_inner);
+    std::mem::replace(&mut *installed, rules.into())
+}
 
+/// The analog rules [`AnalogInterface::connector`] goes by: those from
+/// `analog.ron` until a table is installed.
+pub fn analog_rules() -> Arc<CompatibilityTable<AnalogInterface, AnalogConnector>> {
+    analog_table().read().unwrap_or_else(PoisonError::into_inner).clone()
+}
+
+/// Replaces the analog rules process-wide. Returns the rules it replaced.
+pub fn install_analog_rules(
+    rules: impl Into<Arc<CompatibilityTable<AnalogInterface, AnalogConnector>>>,
+) -> Arc<CompatibilityTable<AnalogInterface, AnalogConnector>> {
+    let mut installed = analog_table().write().unwrap_or_else(PoisonError::into_inner);
+    std::mem::replace(&mut *installed, rules.into())
+}
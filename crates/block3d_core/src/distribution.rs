@@ -1,272 +1,262 @@
-use rand::distributions::{Distribution, Standard, Uniform};
-use rand::Rng;
-use crate::block::{Block3DLike};
-use crate::face::Face;
-use uom::si::f64::*;
-use uom::si::{
-    electric_potential::volt,
-    electrical_resistance::ohm,
-    capacitance::farad,
-    inductance::henry,
-    thermodynamic_temperature::kelvin,
-    power::watt,
-    electric_current::ampere,
-    frequency::hertz,
-};
-
-// /// A distribution that generates random blocks implementing Block3DLike
-// pub struct BlockDistribution {
-//     /// Weights for different block types (Resistor, Capacitor, Inductor, Diode, OpAmp)
-//     pub weights: [f32; 5],
-//     /// Size range for generated blocks (min, max) for each dimension
-//     pub size_range: ((u32, u32), (u32, u32), (u32, u32)),
-// }
-
-// impl Default for BlockDistribution {
-//     fn default() -> Self {
-//         Self {
-//             // Equal probability for all component types
-//             weights: [1.0, 1.0, 1.0, 1.0, 1.0],
-//             // Default size range: 1-3 units in each dimension
-//             size_range: ((1, 3), (1, 3), (1, 3)),
-//         }
-//     }
-// }
-
-// impl BlockDistribution {
-//     /// Create a new distribution with custom weights
-//     pub fn with_weights(weights: [f32; 5]) -> Self {
-//         Self {
-//             weights,
-//             ..Default::default()
-//         }
-//     }
-    
-//     /// Create a distribution favoring passive components (R, L, C)
-//     pub fn passive_heavy() -> Self {
-//         Self::with_weights([3.0, 3.0, 3.0, 1.0, 0.5]) // Favor R, L, C
-//     }
-    
-//     /// Create a distribution favoring active components
-//     pub fn active_heavy() -> Self {
-//         Self::with_weights([1.0, 1.0, 1.0, 2.0, 3.0]) // Favor diodes and op-amps
-//     }
-    
-//     /// Set the size range for generated blocks
-//     pub fn with_size_range(mut self, size_range: ((u32, u32), (u32, u32), (u32, u32))) -> Self {
-//         self.size_range = size_range;
-//         self
-//     }
-    
-//     /// Generate a random size within the configured range
-//     fn random_size<R: Rng + ?Sized>(&self, rng: &mut R) -> (u32, u32, u32) {
-//         let width = rng.gen_range(self.size_range.0.0..=self.size_range.0.1);
-//         let height = rng.gen_range(self.size_range.1.0..=self.size_range.1.1);
-//         let depth = rng.gen_range(self.size_range.2.0..=self.size_range.2.1);
-//         (width, height, depth)
-//     }
-// }
-
-
-
-
-
-// impl Distribution<AnalogComponent> for BlockDistribution {
-//     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> AnalogComponent {
-//         // Weighted selection of block type
-//         let total_weight: f32 = self.weights.iter().sum();
-//         let mut threshold = rng.gen_range(0.0..1.0) * total_weight;
-        
-//         let size = self.random_size(rng);
-        
-//         for (i, &weight) in self.weights.iter().enumerate() {
-//             threshold -= weight;
-//             if threshold <= 0.0 {
-//                 return match i {
-//                     0 => AnalogComponent::Resistor(random_resistor(rng, size)),
-//                     1 => AnalogComponent::Capacitor(random_capacitor(rng, size)),
-//                     2 => AnalogComponent::Inductor(random_inductor(rng, size)),
-//                     3 => AnalogComponent::Diode(random_diode(rng, size)),
-//                     // 4 => AnalogComponent::OpAmp(random_opamp(rng, size)),
-//                     _ => unreachable!(),
-//                 };
-//             }
-//         }
-        
-//         // Fallback (shouldn't happen)
-//         AnalogComponent::Resistor(random_resistor(rng, size))
-//     }
-// }
-
-// /// Generate a random resistor with realistic values
-// fn random_resistor<R: Rng + ?Sized>(rng: &mut R, size: (u32, u32, u32)) -> Resistor {
-//     // E24 series resistor values (common standard values)
-//     let e24_values = [
-//         1.0, 1.1, 1.2, 1.3, 1.5, 1.6, 1.8, 2.0, 2.2, 2.4, 2.7, 3.0,
-//         3.3, 3.6, 3.9, 4.3, 4.7, 5.1, 5.6, 6.2, 6.8, 7.5, 8.2, 9.1
-//     ];
-    
-//     let base_value = e24_values[rng.gen_range(0..e24_values.len())];
-//     let multiplier = 10.0_f64.powi(rng.gen_range(0..7)); // 1Ω to 1MΩ
-//     let resistance = base_value * multiplier;
-    
-//     let power_rating = match rng.gen_range(0..4) {
-//         0 => Power::new::<watt>(0.125),
-//         1 => Power::new::<watt>(0.25),
-//         2 => Power::new::<watt>(0.5),
-//         _ => Power::new::<watt>(1.0),
-//     };
-    
-//     Resistor {
-//         size,
-//         faces: Vec::new(),
-//         resistance: ElectricalResistance::new::<ohm>(resistance),
-//         power_rating,
-//         tolerance: rng.gen_range(1.0..20.0), // 1% to 20% tolerance
-//         temperature_coefficient: rng.gen_range(-200.0..200.0), // ppm/°C
-//         package: if rng.gen_bool(0.7) { PackageType::SurfaceMount } else { PackageType::ThroughHole },
-//         operating_temperature: ThermodynamicTemperature::new::<kelvin>(rng.gen_range(273.0..398.0)),
-//     }
-// }
-
-// /// Generate a random capacitor with realistic values
-// fn random_capacitor<R: Rng + ?Sized>(rng: &mut R, size: (u32, u32, u32)) -> Capacitor {
-//     // Common capacitor values
-//     let base_values = [1.0, 2.2, 4.7, 10.0, 22.0, 47.0, 100.0, 220.0, 470.0];
-//     let base_value = base_values[rng.gen_range(0..base_values.len())];
-//     let multiplier = 10.0_f64.powi(rng.gen_range(-12..-3)); // pF to mF range
-//     let capacitance = base_value * multiplier;
-    
-//     let voltage_rating = match rng.gen_range(0..6) {
-//         0 => ElectricPotential::new::<volt>(6.3),
-//         1 => ElectricPotential::new::<volt>(10.0),
-//         2 => ElectricPotential::new::<volt>(16.0),
-//         3 => ElectricPotential::new::<volt>(25.0),
-//         4 => ElectricPotential::new::<volt>(50.0),
-//         _ => ElectricPotential::new::<volt>(100.0),
-//     };
-    
-//     Capacitor {
-//         size,
-//         faces: Vec::new(),
-//         capacitance: Capacitance::new::<farad>(capacitance),
-//         voltage_rating,
-//         tolerance: rng.gen_range(5.0..20.0),
-//         dielectric: match rng.gen_range(0..4) {
-//             0 => DielectricType::Ceramic,
-//             1 => DielectricType::Electrolytic,
-//             2 => DielectricType::Film,
-//             _ => DielectricType::Tantalum,
-//         },
-//         esr: ElectricalResistance::new::<ohm>(rng.gen_range(0.001..1.0)),
-//         package: if rng.gen_bool(0.6) { PackageType::SurfaceMount } else { PackageType::ThroughHole },
-//         operating_temperature: ThermodynamicTemperature::new::<kelvin>(rng.gen_range(273.0..398.0)),
-//     }
-// }
-
-// /// Generate a random inductor with realistic values
-// fn random_inductor<R: Rng + ?Sized>(rng: &mut R, size: (u32, u32, u32)) -> Inductor {
-//     let base_values = [1.0, 2.2, 4.7, 10.0, 22.0, 47.0, 100.0, 220.0, 470.0];
-//     let base_value = base_values[rng.gen_range(0..base_values.len())];
-//     let multiplier = 10.0_f64.powi(rng.gen_range(-9..-3)); // nH to mH range
-//     let inductance = base_value * multiplier;
-    
-//     Inductor {
-//         size,
-//         faces: Vec::new(),
-//         inductance: Inductance::new::<henry>(inductance),
-//         current_rating: ElectricCurrent::new::<ampere>(rng.gen_range(0.1..10.0)),
-//         dc_resistance: ElectricalResistance::new::<ohm>(rng.gen_range(0.01..10.0)),
-//         core_material: match rng.gen_range(0..4) {
-//             0 => CoreMaterial::Air,
-//             1 => CoreMaterial::Ferrite,
-//             2 => CoreMaterial::Iron,
-//             _ => CoreMaterial::Laminated,
-//         },
-//         package: if rng.gen_bool(0.5) { PackageType::SurfaceMount } else { PackageType::ThroughHole },
-//         operating_temperature: ThermodynamicTemperature::new::<kelvin>(rng.gen_range(273.0..398.0)),
-//     }
-// }
-
-// /// Generate a random diode
-// fn random_diode<R: Rng + ?Sized>(rng: &mut R, size: (u32, u32, u32)) -> Diode {
-//     Diode {
-//         size,
-//         faces: Vec::new(),
-//         forward_voltage: ElectricPotential::new::<volt>(rng.gen_range(0.3..3.3)),
-//         reverse_breakdown_voltage: ElectricPotential::new::<volt>(rng.gen_range(5.0..1000.0)),
-//         forward_current_rating: ElectricCurrent::new::<ampere>(rng.gen_range(0.001..10.0)),
-//         reverse_recovery_time: rng.gen_range(1.0..100.0), // ns
-//         junction_capacitance: Capacitance::new::<farad>(rng.gen_range(1e-12..100e-12)), // pF range
-//         package: if rng.gen_bool(0.7) { PackageType::SurfaceMount } else { PackageType::ThroughHole },
-//         operating_temperature: ThermodynamicTemperature::new::<kelvin>(rng.gen_range(273.0..398.0)),
-//     }
-// }
-
-// /// Generate a random op-amp
-// // fn random_opamp<R: Rng>(rng: &mut R, size: (u32, u32, u32)) -> OpAmp {
-// //     OpAmp {
-// //         size,
-// //         faces: Vec::new(),
-// //         supply_voltage: ElectricPotential::new::<volt>(rng.gen_range(3.0..15.0)),
-// //         input_offset_voltage: ElectricPotential::new::<volt>(rng.gen_range(0.001..0.01)),
-// //         gain_bandwidth_product: Frequency::new::<hertz>(rng.gen_range(1e6..100e6)),
-// //         slew_rate: rng.gen_range(0.1..100.0), // V/μs
-// //         package: PackageType::SurfaceMount, // Op-amps are typically SMT
-// //         operating_temperature: ThermodynamicTemperature::new::<kelvin>(rng.gen_range(273.0..398.0)),
-// //     }
-// // }
-
-// /// Convenience function to generate a vector of random blocks
-// pub fn generate_random_blocks<R: Rng + ?Sized>(
-//     rng: &mut R, 
-//     count: usize, 
-//     distribution: &BlockDistribution
-// ) -> Vec<AnalogComponent> {
-//     (0..count)
-//         .map(|_| distribution.sample(rng))
-//         .collect()
-// }
-
-// /// Generate blocks with a specific distribution pattern
-// pub fn generate_circuit_blocks<R: Rng + ?Sized>(rng: &mut R, count: usize) -> Vec<AnalogComponent> {
-//     let distribution = BlockDistribution::passive_heavy()
-//         .with_size_range(((1, 2), (1, 2), (1, 1))); // Smaller components
-//     generate_random_blocks(rng, count, &distribution)
-// }
-
-// #[cfg(test)]
-// mod tests {
-//     use super::*;
-//     use rand::thread_rng;
-
-//     #[test]
-//     fn test_block_distribution() {
-//         let mut rng = thread_rng();
-//         let distribution = BlockDistribution::default();
-        
-//         let blocks = generate_random_blocks(&mut rng, 10, &distribution);
-//         assert_eq!(blocks.len(), 10);
-        
-//         // Test that all blocks implement Block3DLike
-//         for block in blocks {
-//             assert!(block.size().0 > 0);
-//             assert!(block.size().1 > 0);
-//             assert!(block.size().2 > 0);
-//             assert!(!block.symbol().is_empty());
-//         }
-//     }
-    
-//     #[test]
-//     fn test_passive_heavy_distribution() {
-//         let mut rng = thread_rng();
-//         let blocks = generate_circuit_blocks(&mut rng, 20);
-        
-//         let passive_count = blocks.iter()
-//             .filter(|b| matches!(b, AnalogComponent::Resistor(_) | AnalogComponent::Capacitor(_) | AnalogComponent::Inductor(_)))
-//             .count();
-            
-//         // Should have more passive than active components
-//         assert!(passive_count > blocks.len() / 2);
-//     }
-// } 
\ No newline at end of file
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::rngs::StdRng;
+use rand::{Rng, RngCore, SeedableRng};
+use crate::block::Block3DLike;
+
+/// IEC 60063 preferred number series. Mantissas are stored in hundredths so
+/// decades scale without accumulating rounding error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ESeries {
+    /// 20% tolerance
+    E6,
+    /// 10% tolerance
+    E12,
+    /// 5% tolerance
+    E24,
+    /// 2% tolerance
+    E48,
+    /// 1% tolerance
+    E96,
+}
+
+const E6: [u16; 6] = [100, 150, 220, 330, 470, 680];
+
+const E12: [u16; 12] = [100, 120, 150, 180, 220, 270, 330, 390, 470, 560, 680, 820];
+
+const E24: [u16; 24] = [
+    100, 110, 120, 130, 150, 160, 180, 200, 220, 240, 270, 300,
+    330, 360, 390, 430, 470, 510, 560, 620, 680, 750, 820, 910,
+];
+
+const E96: [u16; 96] = [
+    100, 102, 105, 107, 110, 113, 115, 118, 121, 124, 127, 130,
+    133, 137, 140, 143, 147, 150, 154, 158, 162, 165, 169, 174,
+    178, 182, 187, 191, 196, 200, 205, 210, 215, 221, 226, 232,
+    237, 243, 249, 255, 261, 267, 274, 280, 287, 294, 301, 309,
+    316, 324, 332, 340, 348, 357, 365, 374, 383, 392, 402, 412,
+    422, 432, 442, 453, 464, 475, 487, 499, 511, 523, 536, 549,
+    562, 576, 590, 604, 619, 634, 649, 665, 681, 698, 715, 732,
+    750, 768, 787, 806, 825, 845, 866, 887, 909, 931, 953, 976,
+];
+
+// E48 is every other E96 value.
+const E48: [u16; 48] = [
+    100, 105, 110, 115, 121, 127, 133, 140, 147, 154, 162, 169,
+    178, 187, 196, 205, 215, 226, 237, 249, 261, 274, 287, 301,
+    316, 332, 348, 365, 383, 402, 422, 442, 464, 487, 511, 536,
+    562, 590, 619, 649, 681, 715, 750, 787, 825, 866, 909, 953,
+];
+
+impl ESeries {
+    pub const ALL: [ESeries; 5] = [ESeries::E6, ESeries::E12, ESeries::E24, ESeries::E48, ESeries::E96];
+
+    /// Mantissas of one decade in hundredths, e.g. 470 for 4.7.
+    pub fn mantissas(self) -> &'static [u16] {
+        match self {
+            ESeries::E6 => &E6,
+            ESeries::E12 => &E12,
+            ESeries::E24 => &E24,
+            ESeries::E48 => &E48,
+            ESeries::E96 => &E96,
+        }
+    }
+
+    /// Relative tolerance the series is designed for.
+    pub fn tolerance(self) -> f64 {
+        match self {
+            ESeries::E6 => 0.20,
+            ESeries::E12 => 0.10,
+            ESeries::E24 => 0.05,
+            ESeries::E48 => 0.02,
+            ESeries::E96 => 0.01,
+        }
+    }
+
+    /// The series value closest to `value` on a log scale, i.e. with the
+    /// smallest relative error. `None` for non-positive or non-finite input.
+    pub fn nearest(self, value: f64) -> Option<f64> {
+        if !(value > 0.0 && value.is_finite()) {
+            return None;
+        }
+        let decade = value.log10().floor() as i32;
+        // Neighbouring decades cover values just below 1.0 or at 10.0 after rounding.
+        (decade - 1..=decade + 1)
+            .flat_map(|decade| {
+                let scale = 10f64.powi(decade - 2);
+                self.mantissas().iter().map(move |&m| f64::from(m) * scale)
+            })
+            .min_by(|a, b| (a / value).ln().abs().total_cmp(&(b / value).ln().abs()))
+    }
+
+    /// Every value of the series in `[min, max]`, ascending.
+    pub fn values_between(self, min: f64, max: f64) -> Vec<f64> {
+        if !(min > 0.0 && min <= max && max.is_finite()) {
+            return Vec::new();
+        }
+        let first = min.log10().floor() as i32;
+        let last = max.log10().floor() as i32;
+        (first..=last)
+            .flat_map(|decade| {
+                let scale = 10f64.powi(decade - 2);
+                self.mantissas().iter().map(move |&m| f64::from(m) * scale)
+            })
+            // Allow for the scale not being exact in binary.
+            .filter(|v| *v >= min * (1.0 - 1e-9) && *v <= max * (1.0 + 1e-9))
+            .collect()
+    }
+
+    /// A distribution over the series values in `[min, max]`. Every value is
+    /// equally likely, so samples spread evenly per decade like real part bins.
+    pub fn between(self, min: f64, max: f64) -> ESeriesValue {
+        ESeriesValue { values: self.values_between(min, max) }
+    }
+}
+
+/// Uniform choice among the E-series values of a range, in base SI units.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ESeriesValue {
+    values: Vec<f64>,
+}
+
+impl ESeriesValue {
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+}
+
+impl Distribution<f64> for ESeriesValue {
+    /// Panics if the range held no series value.
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        assert!(!self.values.is_empty(), "E-series range contains no values");
+        self.values[rng.gen_range(0..self.values.len())]
+    }
+}
+
+type Factory<B> = Box<dyn Fn(&mut dyn RngCore) -> B + Send + Sync>;
+
+/// A distribution that generates random blocks implementing Block3DLike from
+/// weighted factories. With no positive weights it yields `B::default()`.
+pub struct BlockDistribution<B: Block3DLike> {
+    factories: Vec<Factory<B>>,
+    weights: Vec<f32>,
+    index: Option<WeightedIndex<f32>>,
+}
+
+impl<B: Block3DLike> Default for BlockDistribution<B> {
+    fn default() -> Self {
+        Self { factories: Vec::new(), weights: Vec::new(), index: None }
+    }
+}
+
+impl<B: Block3DLike> BlockDistribution<B> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a factory picked with probability proportional to `weight`.
+    pub fn with(mut self, weight: f32, factory: impl Fn(&mut dyn RngCore) -> B + Send + Sync + 'static) -> Self {
+        assert!(weight.is_finite() && weight >= 0.0, "block weight must be finite and non-negative, got {weight}");
+        self.factories.push(Box::new(factory));
+        self.weights.push(weight);
+        self.index = WeightedIndex::new(&self.weights).ok();
+        self
+    }
+
+    /// Adds a factory weighted by the `ranking` of a block it produces, so the
+    /// mix matches what the WFC solver would prefer.
+    pub fn with_ranked(self, factory: impl Fn(&mut dyn RngCore) -> B + Send + Sync + 'static) -> Self {
+        let weight = factory(&mut StdRng::seed_from_u64(0)).ranking().max(0.0);
+        self.with(weight, factory)
+    }
+
+    pub fn len(&self) -> usize {
+        self.factories.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.factories.is_empty()
+    }
+
+    /// Generates `count` blocks.
+    pub fn generate<R: Rng + ?Sized>(&self, rng: &mut R, count: usize) -> Vec<B> {
+        (0..count).map(|_| self.sample(rng)).collect()
+    }
+
+    /// Generates `count` blocks reproducibly from `seed`.
+    pub fn generate_seeded(&self, seed: u64, count: usize) -> Vec<B> {
+        self.generate(&mut StdRng::seed_from_u64(seed), count)
+    }
+}
+
+impl<B: Block3DLike> Distribution<B> for BlockDistribution<B> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> B {
+        let Some(index) = &self.index else {
+            return B::default();
+        };
+        let factory = &self.factories[index.sample(rng)];
+        // Factories take a trait object, which an unsized `R` can't become directly.
+        let mut rng = StdRng::seed_from_u64(rng.next_u64());
+        factory(&mut rng)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::face::Face;
+
+    #[derive(Clone, Debug, Default, PartialEq)]
+    struct Passive {
+        kind: &'static str,
+        value: f64,
+    }
+
+    impl Block3DLike for Passive {
+        fn size(&self) -> (u32, u32, u32) {
+            (1, 1, 1)
+        }
+
+        fn faces(&self) -> impl Iterator<Item = Face> {
+            std::iter::empty()
+        }
+
+        fn symbol(&self) -> String {
+            self.kind.to_string()
+        }
+    }
+
+    fn passives() -> BlockDistribution<Passive> {
+        let resistors = ESeries::E24.between(10.0, 1e6);
+        let capacitors = ESeries::E12.between(1e-12, 1e-6);
+        BlockDistribution::new()
+            .with(3.0, move |rng| Passive { kind: "R", value: resistors.sample(rng) })
+            .with(1.0, move |rng| Passive { kind: "C", value: capacitors.sample(rng) })
+            .with(0.0, |_| Passive { kind: "never", value: 0.0 })
+    }
+
+    #[test]
+    fn series_values_cover_each_decade() {
+        assert_eq!(ESeries::E96.mantissas().len(), 96);
+        assert!(ESeries::E48.mantissas().iter().zip(ESeries::E96.mantissas().iter().step_by(2)).all(|(a, b)| a == b));
+        let values = ESeries::E12.values_between(1_000.0, 10_000.0);
+        assert_eq!(values.len(), 13);
+        assert!((values[8] - 4_700.0).abs() < 1e-9);
+        assert!(ESeries::E24.values_between(5.0, 1.0).is_empty());
+        assert!(ESeries::E96.values_between(0.0, 1.0).is_empty());
+    }
+
+    #[test]
+    fn seeded_generation_is_reproducible_and_weighted() {
+        let distribution = passives();
+        let blocks = distribution.generate_seeded(7, 400);
+        assert_eq!(blocks, distribution.generate_seeded(7, 400));
+
+        let resistors = blocks.iter().filter(|b| b.kind == "R").count();
+        assert!(blocks.iter().all(|b| b.kind != "never"));
+        assert!((250..350).contains(&resistors), "{resistors} resistors");
+
+        let e24 = ESeries::E24.values_between(10.0, 1e6);
+        assert!(blocks.iter().filter(|b| b.kind == "R").all(|b| e24.contains(&b.value)));
+        assert_eq!(BlockDistribution::<Passive>::new().generate_seeded(1, 2), [Passive::default(), Passive::default()]);
+    }
+}
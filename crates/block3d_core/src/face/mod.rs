@@ -1,21 +1,157 @@
 use crate::connection::OrientedInterface;
+use crate::orientation::{Axis, Orientation};
+use bevy::prelude::IVec3;
 use serde::{Serialize, Deserialize};
+use strum::EnumIter;
+
+/// The side of a cell a face looks out of.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Hash, Eq, PartialEq, PartialOrd, Ord, EnumIter)]
+pub enum Direction {
+    PosX,
+    NegX,
+    PosY,
+    NegY,
+    PosZ,
+    NegZ,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 6] = [
+        Direction::PosX,
+        Direction::NegX,
+        Direction::PosY,
+        Direction::NegY,
+        Direction::PosZ,
+        Direction::NegZ,
+    ];
+
+    pub fn new(axis: Axis, positive: bool) -> Self {
+        match (axis, positive) {
+            (Axis::X, true) => Direction::PosX,
+            (Axis::X, false) => Direction::NegX,
+            (Axis::Y, true) => Direction::PosY,
+            (Axis::Y, false) => Direction::NegY,
+            (Axis::Z, true) => Direction::PosZ,
+            (Axis::Z, false) => Direction::NegZ,
+        }
+    }
+
+    pub fn axis(self) -> Axis {
+        match self {
+            Direction::PosX | Direction::NegX => Axis::X,
+            Direction::PosY | Direction::NegY => Axis::Y,
+            Direction::PosZ | Direction::NegZ => Axis::Z,
+        }
+    }
+
+    pub fn is_positive(self) -> bool {
+        matches!(self, Direction::PosX | Direction::PosY | Direction::PosZ)
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::new(self.axis(), !self.is_positive())
+    }
+
+    /// Unit vector pointing out of the face.
+    pub fn normal(self) -> IVec3 {
+        if self.is_positive() { self.axis().unit() } else { -self.axis().unit() }
+    }
+
+    /// Where this direction points once the block is in `orientation`.
+    pub fn oriented(self, orientation: Orientation) -> Self {
+        let local = self.axis().index();
+        Self::new(orientation.axes()[local], self.is_positive() != orientation.flips()[local])
+    }
+}
+
+/// A connector on one side of one cell of a block.
 #[derive(Serialize, Deserialize, Debug, Clone, Hash, Eq, PartialEq)]
 pub struct Face {
-   oriented_interface: OrientedInterface
+    oriented_interface: OrientedInterface,
+    direction: Direction,
+    /// Cell of a multi-cell block the face belongs to, in block-local coordinates.
+    #[serde(default)]
+    cell: (u32, u32, u32),
 }
 
 impl Face {
-    pub fn new(oriented_interface: OrientedInterface) -> Self {
-        Self { oriented_interface }
+    pub fn new(direction: Direction, oriented_interface: OrientedInterface) -> Self {
+        Self { oriented_interface, direction, cell: (0, 0, 0) }
+    }
+
+    /// The same face on another cell of a multi-cell block.
+    pub fn at_cell(self, cell: (u32, u32, u32)) -> Self {
+        Self { cell, ..self }
     }
-    
+
     pub fn oriented_interface(&self) -> OrientedInterface {
         self.oriented_interface
     }
 
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    pub fn cell(&self) -> (u32, u32, u32) {
+        self.cell
+    }
+
+    /// This face on a block of local `size` placed in `orientation`: direction,
+    /// cell and interface all turn with the block.
+    pub fn oriented(&self, orientation: Orientation, size: (u32, u32, u32)) -> Self {
+        let mut oriented_interface = self.oriented_interface;
+        oriented_interface.orientation = orientation.compose(oriented_interface.orientation);
+        Self {
+            oriented_interface,
+            direction: self.direction.oriented(orientation),
+            cell: orientation.apply_cell(self.cell, size),
+        }
+    }
+
+    /// Centre of the face relative to the block's minimum corner.
+    pub fn position_offset(&self) -> (f32, f32, f32) {
+        let normal = self.direction.normal().as_vec3();
+        (
+            self.cell.0 as f32 + 0.5 + 0.5 * normal.x,
+            self.cell.1 as f32 + 0.5 + 0.5 * normal.y,
+            self.cell.2 as f32 + 0.5 + 0.5 * normal.z,
+        )
+    }
+
+    /// Whether the faces look at each other and their interfaces form a connection.
+    /// Both faces must be in the same frame, e.g. both `oriented` into the world.
     pub fn can_connect_to(&self, other: &Self) -> bool {
-        // Use the existing connection system to check if these interfaces can connect
-        (self.oriented_interface + other.oriented_interface).is_some()
+        self.direction == other.direction.opposite()
+            && (self.oriented_interface + other.oriented_interface).is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connection::ConnectorInterface;
+
+    fn face(direction: Direction, interface: ConnectorInterface) -> Face {
+        Face::new(direction, OrientedInterface { interface, orientation: Orientation::IDENTITY })
+    }
+
+    #[test]
+    fn faces_turn_with_the_block() {
+        // East face on the far cell of a 2×1×1 block, turned a quarter about Y.
+        let turn = Orientation::about(Axis::Y, 1);
+        let east = face(Direction::PosX, ConnectorInterface::MetalPad).at_cell((1, 0, 0));
+        let turned = east.oriented(turn, (2, 1, 1));
+        assert_eq!(turned.direction(), Direction::NegZ);
+        assert_eq!(turned.cell(), (0, 0, 0));
+        assert_eq!(turned.oriented_interface().orientation, turn);
+        assert_eq!(turned.position_offset(), (0.5, 0.5, 0.0));
+    }
+
+    #[test]
+    fn only_facing_faces_connect() {
+        let pad = face(Direction::PosY, ConnectorInterface::MetalPad);
+        let ball = face(Direction::NegY, ConnectorInterface::SolderBall);
+        assert!(pad.can_connect_to(&ball));
+        assert!(!pad.can_connect_to(&face(Direction::PosX, ConnectorInterface::SolderBall)));
     }
 }
@@ -0,0 +1,183 @@
+use std::collections::HashMap;
+
+use super::grid_position::GridPosition;
+use super::{Grid3DLike, GridError};
+
+/// Edge length of a chunk, in cells.
+pub const CHUNK_SIZE: i32 = 16;
+const CHUNK_VOLUME: usize = (CHUNK_SIZE * CHUNK_SIZE * CHUNK_SIZE) as usize;
+
+/// An unbounded sparse 3D grid. Cells are allocated in `CHUNK_SIZE`³ chunks on
+/// first write and chunks are dropped again once emptied, so memory follows the
+/// occupied region rather than its bounding box.
+#[derive(Debug, Clone)]
+pub struct ChunkedGrid<T> {
+    chunks: HashMap<GridPosition, Chunk<T>>,
+    len: usize,
+}
+
+#[derive(Debug, Clone)]
+struct Chunk<T> {
+    cells: Box<[Option<T>]>,
+    /// Number of `Some` cells, so emptied chunks are spotted without a scan.
+    occupied: usize,
+}
+
+impl<T> Chunk<T> {
+    fn new() -> Self {
+        Self { cells: std::iter::repeat_with(|| None).take(CHUNK_VOLUME).collect(), occupied: 0 }
+    }
+}
+
+impl<T> Default for ChunkedGrid<T> {
+    fn default() -> Self {
+        Self { chunks: HashMap::new(), len: 0 }
+    }
+}
+
+impl<T> ChunkedGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of occupied cells.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Number of allocated chunks.
+    pub fn chunk_count(&self) -> usize {
+        self.chunks.len()
+    }
+
+    pub fn remove(&mut self, pos: GridPosition) -> Option<T> {
+        let (key, offset) = Self::split(pos);
+        let chunk = self.chunks.get_mut(&key)?;
+        let old = chunk.cells[offset].take()?;
+        self.len -= 1;
+        chunk.occupied -= 1;
+        if chunk.occupied == 0 {
+            self.chunks.remove(&key);
+        }
+        Some(old)
+    }
+
+    pub fn clear(&mut self) {
+        self.chunks.clear();
+        self.len = 0;
+    }
+
+    /// Inclusive corners of the box enclosing every occupied cell.
+    pub fn bounds(&self) -> Option<(GridPosition, GridPosition)> {
+        self.iter().fold(None, |bounds, (pos, _)| {
+            let (min, max) = bounds.unwrap_or((pos, pos));
+            Some((
+                GridPosition::new(min.x.min(pos.x), min.y.min(pos.y), min.z.min(pos.z)),
+                GridPosition::new(max.x.max(pos.x), max.y.max(pos.y), max.z.max(pos.z)),
+            ))
+        })
+    }
+
+    /// Occupied cells in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (GridPosition, &T)> {
+        self.chunks.iter().flat_map(|(key, chunk)| {
+            chunk.cells.iter().enumerate().filter_map(move |(offset, cell)| Some((Self::join(*key, offset), cell.as_ref()?)))
+        })
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (GridPosition, &mut T)> {
+        self.chunks.iter_mut().flat_map(|(key, chunk)| {
+            let key = *key;
+            chunk.cells.iter_mut().enumerate().filter_map(move |(offset, cell)| Some((Self::join(key, offset), cell.as_mut()?)))
+        })
+    }
+
+    /// Chunk coordinates and offset within the chunk of `pos`.
+    fn split(pos: GridPosition) -> (GridPosition, usize) {
+        let key = GridPosition::new(
+            pos.x.div_euclid(CHUNK_SIZE),
+            pos.y.div_euclid(CHUNK_SIZE),
+            pos.z.div_euclid(CHUNK_SIZE),
+        );
+        let (x, y, z) = (
+            pos.x.rem_euclid(CHUNK_SIZE),
+            pos.y.rem_euclid(CHUNK_SIZE),
+            pos.z.rem_euclid(CHUNK_SIZE),
+        );
+        (key, (x + CHUNK_SIZE * (y + CHUNK_SIZE * z)) as usize)
+    }
+
+    fn join(key: GridPosition, offset: usize) -> GridPosition {
+        let offset = offset as i32;
+        GridPosition::new(
+            key.x * CHUNK_SIZE + offset % CHUNK_SIZE,
+            key.y * CHUNK_SIZE + offset / CHUNK_SIZE % CHUNK_SIZE,
+            key.z * CHUNK_SIZE + offset / (CHUNK_SIZE * CHUNK_SIZE),
+        )
+    }
+}
+
+impl<T> Grid3DLike for ChunkedGrid<T> {
+    type GridItem = T;
+
+    fn in_bounds(&self, _pos: GridPosition) -> bool {
+        true
+    }
+
+    fn get(&self, pos: GridPosition) -> Option<&T> {
+        let (key, offset) = Self::split(pos);
+        self.chunks.get(&key)?.cells[offset].as_ref()
+    }
+
+    fn get_mut(&mut self, pos: GridPosition) -> Option<&mut T> {
+        let (key, offset) = Self::split(pos);
+        self.chunks.get_mut(&key)?.cells[offset].as_mut()
+    }
+
+    fn set(&mut self, pos: GridPosition, item: T) -> Result<Option<T>, GridError> {
+        let (key, offset) = Self::split(pos);
+        let chunk = self.chunks.entry(key).or_insert_with(Chunk::new);
+        let old = chunk.cells[offset].replace(item);
+        if old.is_none() {
+            self.len += 1;
+            chunk.occupied += 1;
+        }
+        Ok(old)
+    }
+
+    fn iter(&self) -> impl Iterator<Item = (GridPosition, &T)> {
+        ChunkedGrid::iter(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunked_grid_allocates_and_frees_chunks() {
+        let mut grid = ChunkedGrid::new();
+        assert_eq!(grid.set((-1, 0, 0).into(), 'a'), Ok(None));
+        assert_eq!(grid.set((CHUNK_SIZE, 3, -40).into(), 'b'), Ok(None));
+        assert_eq!(grid.set((-1, 0, 0).into(), 'c'), Ok(Some('a')));
+        assert_eq!((grid.len(), grid.chunk_count()), (2, 2));
+        assert_eq!(grid.get((-1, 0, 0).into()), Some(&'c'));
+        assert_eq!(grid.bounds(), Some(((-1, 0, -40).into(), (CHUNK_SIZE, 3, 0).into())));
+
+        assert_eq!(grid.remove((-1, 0, 0).into()), Some('c'));
+        assert_eq!((grid.len(), grid.chunk_count()), (1, 1));
+        assert_eq!(grid.iter().collect::<Vec<_>>(), [((CHUNK_SIZE, 3, -40).into(), &'b')]);
+
+        // A chunk stays until its last cell goes.
+        assert_eq!(grid.set((CHUNK_SIZE + 1, 3, -40).into(), 'd'), Ok(None));
+        assert_eq!(grid.remove((CHUNK_SIZE, 3, -40).into()), Some('b'));
+        assert_eq!(grid.remove((CHUNK_SIZE, 3, -40).into()), None);
+        assert_eq!((grid.len(), grid.chunk_count()), (1, 1));
+        assert_eq!(grid.remove((CHUNK_SIZE + 1, 3, -40).into()), Some('d'));
+        assert_eq!((grid.len(), grid.chunk_count()), (0, 0));
+    }
+}
@@ -1,3 +1,6 @@
+use super::Neighbourhood;
+use super::vecdeque::iter::NeighboursIter;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Grid2DPosition {
     pub x: i32,
@@ -36,3 +39,42 @@ impl From<Grid2DPosition> for (i32, i32) {
         (pos.x, pos.y)
     }
 }
+
+/// A cell in a 3D grid; Y is up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct GridPosition {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+impl GridPosition {
+    pub fn new(x: i32, y: i32, z: i32) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn offset(self, dx: i32, dy: i32, dz: i32) -> Self {
+        Self::new(self.x + dx, self.y + dy, self.z + dz)
+    }
+
+    pub fn manhattan_distance(&self, other: &Self) -> i32 {
+        (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
+    }
+
+    /// Positions around this one, whether or not they lie in any grid.
+    pub fn neighbours(self, neighbourhood: Neighbourhood) -> NeighboursIter {
+        NeighboursIter::new(self, neighbourhood)
+    }
+}
+
+impl From<(i32, i32, i32)> for GridPosition {
+    fn from((x, y, z): (i32, i32, i32)) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl From<GridPosition> for (i32, i32, i32) {
+    fn from(pos: GridPosition) -> Self {
+        (pos.x, pos.y, pos.z)
+    }
+}
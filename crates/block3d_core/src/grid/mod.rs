@@ -1,72 +1,53 @@
-use grid_position::Grid2DPosition;
+use grid_position::GridPosition;
+use thiserror::Error;
+pub mod chunked;
 pub mod grid_position;
 pub mod vecdeque;
 
-pub trait Grid2DLike<'grid> {
-    /// The type of item stored in the grid.
-    type GridItem: Clone + Copy + 'grid;
-
-    /// The type representing a position in the grid.
-    /// Must be convertible to a 3D grid position.
-    type Position: Into<Grid2DPosition>;
-
-    /// Iterators for traversing the grid.
-    type Iter: Iterator<Item = Self::GridItem>;
-    type IterMut: Iterator<Item = &'grid mut Self::GridItem>;
-
-    type RowIter: Iterator<Item = Self::GridItem>;
-    type RowIterMut: Iterator<Item = &'grid mut Self::GridItem>;
-
-    type ColumnIter: Iterator<Item = Self::GridItem>;
-    type ColumnIterMut: Iterator<Item = &'grid mut Self::GridItem>;
-
+pub use chunked::ChunkedGrid;
+pub use vecdeque::Grid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum GridError {
+    #[error("grid dimensions must all be positive, got {0:?}")]
+    EmptyDimensions((i32, i32, i32)),
+    #[error("grid dimensions {0:?} hold more cells than can be addressed")]
+    TooLarge((i32, i32, i32)),
+    #[error("position {0:?} is outside the grid")]
+    OutOfBounds(GridPosition),
+}
 
-    type NeighborsIter: Iterator<Item = Self::GridItem>;
-    type NeighborsIterMut: Iterator<Item = &'grid mut Self::GridItem>;
+/// Which cells count as adjacent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Neighbourhood {
+    /// The 6 cells sharing a face.
+    VonNeumann,
+    /// The 26 cells sharing a face, edge or corner.
+    Moore,
+}
 
-    type BoundaryIter: Iterator<Item = Self::GridItem>;
-    type BoundaryIterMut: Iterator<Item = &'grid mut Self::GridItem>;
+/// Common interface of the dense and sparse 3D grids.
+pub trait Grid3DLike {
+    /// The type of item stored in the grid.
+    type GridItem;
 
-    /// Returns the dimensions of the grid as a tuple (width, height, depth).
-    /// This enforces the 3D nature of the grid.
-    fn dimensions(&self) -> (i32, i32, i32);
+    /// Whether `pos` can hold an item.
+    fn in_bounds(&self, pos: GridPosition) -> bool;
 
     /// Gets an item at the specified position.
-    fn get(&self, pos: Self::Position) -> Option<&Self::GridItem>;
+    fn get(&self, pos: GridPosition) -> Option<&Self::GridItem>;
 
     /// Gets a mutable reference to an item at the specified position.
-    fn get_mut(&mut self, pos: Self::Position) -> Option<&mut Self::GridItem>;
-
-    /// Sets an item at the specified position.
-    fn set(&mut self, pos: Self::Position, item: Self::GridItem);
-
-    /// Returns an iterator over the grid items.
-    fn iter(&self) -> Self::Iter;
-
-    /// Returns a mutable iterator over the grid items.
-    fn iter_mut(&mut self) -> Self::IterMut;
-
-    /// Returns an iterator over the neighbours of a specified position.
-    fn neighbours(&self, pos: Self::Position) -> Self::NeighborsIter;
-    fn neighbours_mut(&mut self, pos: Self::Position) -> Self::NeighborsIterMut;
-
-    // /// Returns an iterator over a row at the specified y and z coordinates.
-    // fn row(&self, y: i32, z: i32) -> Self::RowIter;
-
-    // /// Returns a mutable iterator over a row at the specified y and z coordinates.
-    // fn row_mut(&mut self, y: i32, z: i32) -> Self::RowIterMut;
-
-    // fn depth(&self, z: i32) -> Self::DepthIter;
-    // fn depth_mut(&mut self, z: i32) -> Self::DepthIterMut;
+    fn get_mut(&mut self, pos: GridPosition) -> Option<&mut Self::GridItem>;
 
-    // fn column(&self, x: i32, z: i32) -> Self::ColumnIter;
-    // fn column_mut(&mut self, x: i32, z: i32) -> Self::ColumnIterMut;
+    /// Sets an item at the specified position, returning the one it replaced.
+    fn set(&mut self, pos: GridPosition, item: Self::GridItem) -> Result<Option<Self::GridItem>, GridError>;
 
-    // /// Returns an iterator over a layer at the specified z coordinate.
-    // fn layer(&self, z: i32) -> Self::LayerIter;
-    // fn layer_mut(&mut self, z: i32) -> Self::LayerIterMut;
+    /// Returns an iterator over the stored items and their positions.
+    fn iter(&self) -> impl Iterator<Item = (GridPosition, &Self::GridItem)>;
 
-    // /// Returns an iterator over the boundary items of the grid.
-    // fn boundary(&self) -> Self::BoundaryIter;
-    // fn boundary_mut(&mut self) -> Self::BoundaryIterMut;
+    /// Returns an iterator over the items around a specified position.
+    fn neighbours(&self, pos: GridPosition, neighbourhood: Neighbourhood) -> impl Iterator<Item = (GridPosition, &Self::GridItem)> {
+        pos.neighbours(neighbourhood).filter_map(|p| Some((p, self.get(p)?)))
+    }
 }
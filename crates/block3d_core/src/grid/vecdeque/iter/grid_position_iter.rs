@@ -1,22 +1,39 @@
-use crate::grid::grid_position::Grid2DPosition;
+use crate::grid::grid_position::GridPosition;
 
-pub struct Grid2DPositionsIter {  
-    pub(crate) len: i32,
-    pub(crate) width: i32,
-    pub(crate) height: i32,
-    pub(crate) idx: i32,
+/// Every position in a `width × height × depth` box, X fastest then Y then Z:
+/// the storage order of `Grid`.
+pub struct GridPositionsIter {
+    pub(crate) len: usize,
+    pub(crate) width: usize,
+    pub(crate) height: usize,
+    pub(crate) idx: usize,
 }
 
-impl Iterator for Grid2DPositionsIter {
-    type Item = Grid2DPosition;
+impl GridPositionsIter {
+    /// Counts in `usize`: a box `Grid::new` accepts can hold more than `i32::MAX` cells.
+    pub fn new((width, height, depth): (i32, i32, i32)) -> Self {
+        let [width, height, depth] = [width, height, depth].map(|extent| extent.max(0) as usize);
+        Self { len: width.saturating_mul(height).saturating_mul(depth), width, height, idx: 0 }
+    }
+}
+
+impl Iterator for GridPositionsIter {
+    type Item = GridPosition;
     fn next(&mut self) -> Option<Self::Item> {
         if self.idx >= self.len {
             return None;
         }
-        let rem = self.idx % (self.width * self.height);
-        let y = rem / self.width;
-        let x = rem % self.width;
+        let layer = self.width * self.height;
+        let (rem, z) = (self.idx % layer, self.idx / layer);
+        let (x, y) = (rem % self.width, rem / self.width);
         self.idx += 1;
-        Some((x, y).into())
+        Some((x as i32, y as i32, z as i32).into())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.len - self.idx;
+        (remaining, Some(remaining))
     }
 }
+
+impl ExactSizeIterator for GridPositionsIter {}
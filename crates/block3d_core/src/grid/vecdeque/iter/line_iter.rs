@@ -0,0 +1,36 @@
+use std::iter::StepBy;
+
+use crate::grid::grid_position::GridPosition;
+
+/// Cells along one axis of a `Grid`: a row (X), column (Y) or depth line (Z).
+pub struct LineIter<'a, T> {
+    pub(crate) line_iter: StepBy<std::slice::Iter<'a, T>>,
+    pub(crate) position: GridPosition,
+    pub(crate) step: (i32, i32, i32),
+}
+
+impl<'a, T> Iterator for LineIter<'a, T> {
+    type Item = (GridPosition, &'a T);
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.line_iter.next()?;
+        let position = self.position;
+        self.position = position.offset(self.step.0, self.step.1, self.step.2);
+        Some((position, item))
+    }
+}
+
+pub struct LineIterMut<'a, T> {
+    pub(crate) line_iter: StepBy<std::slice::IterMut<'a, T>>,
+    pub(crate) position: GridPosition,
+    pub(crate) step: (i32, i32, i32),
+}
+
+impl<'a, T> Iterator for LineIterMut<'a, T> {
+    type Item = (GridPosition, &'a mut T);
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.line_iter.next()?;
+        let position = self.position;
+        self.position = position.offset(self.step.0, self.step.1, self.step.2);
+        Some((position, item))
+    }
+}
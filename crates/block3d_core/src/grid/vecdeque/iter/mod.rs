@@ -1,9 +1,9 @@
-pub mod grid_positions_enumerator;
 pub mod grid_position_iter;
-pub mod row_iter;
+pub mod line_iter;
 pub mod grid_iter;
 pub mod neighbours_iter;
 
-pub use grid_positions_enumerator::{Grid2DPositions, Grid2DPositionsEnumerator};
-pub use grid_position_iter::Grid2DPositionsIter;
-pub use row_iter::{RowIter, RowIterMut};
+pub use grid_position_iter::GridPositionsIter;
+pub use line_iter::{LineIter, LineIterMut};
+pub use grid_iter::{GridIter, GridIterMut};
+pub use neighbours_iter::NeighboursIter;
@@ -1,36 +1,37 @@
-use crate::grid::grid_position::Grid2DPosition;
+use crate::grid::grid_position::GridPosition;
+use crate::grid::Neighbourhood;
+
+const FACES: [(i32, i32, i32); 6] = [(1, 0, 0), (-1, 0, 0), (0, 1, 0), (0, -1, 0), (0, 0, 1), (0, 0, -1)];
 
 pub struct NeighboursIter {
-    position: Grid2DPosition,
+    position: GridPosition,
+    neighbourhood: Neighbourhood,
     index: usize,
 }
 
 impl NeighboursIter {
-    pub fn new(position: Grid2DPosition) -> Self {
-        NeighboursIter { position, index: 0 }
+    pub fn new(position: GridPosition, neighbourhood: Neighbourhood) -> Self {
+        NeighboursIter { position, neighbourhood, index: 0 }
     }
 }
 
 impl Iterator for NeighboursIter {
-    type Item = Grid2DPosition;
+    type Item = GridPosition;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let directions = [
-            (0, -1), // North
-            (1, 0),  // East
-            (0, 1),  // South
-            (-1, 0), // West
-        ];
-
-        if self.index < directions.len() {
-            let (dx, dy) = directions[self.index];
-            self.index += 1;
-            Some(Grid2DPosition {
-                x: self.position.x + dx,
-                y: self.position.y + dy,
-            })
-        } else {
-            None
-        }
+        let (dx, dy, dz) = match self.neighbourhood {
+            Neighbourhood::VonNeumann => *FACES.get(self.index)?,
+            Neighbourhood::Moore => {
+                // The 3×3×3 block around the position, skipping its centre (index 13).
+                let cell = if self.index < 13 { self.index } else { self.index + 1 };
+                if cell >= 27 {
+                    return None;
+                }
+                let cell = cell as i32;
+                (cell % 3 - 1, cell / 3 % 3 - 1, cell / 9 - 1)
+            }
+        };
+        self.index += 1;
+        Some(self.position.offset(dx, dy, dz))
     }
 }
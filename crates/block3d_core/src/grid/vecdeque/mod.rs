@@ -1,104 +1,262 @@
 pub mod iter;
 
+use iter::{GridIter, GridIterMut, GridPositionsIter, LineIter, LineIterMut};
 
-#[derive(Debug, PartialEq)]
-pub struct Grid<T: Clone + Copy> {
+use super::grid_position::GridPosition;
+use super::{Grid3DLike, GridError};
+
+/// A dense, fixed-size 3D grid stored X fastest, then Y, then Z.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Grid<T: Clone> {
     pub(crate) items: Vec<T>,
     pub(crate) width: i32,
     pub(crate) height: i32,
     pub(crate) depth: i32,
 }
 
-// impl Grid3DLike for Grid<T> {
-//     type GridItem = T;
-//     type Position = GridPosition;
-
-//     type Iter = GridIter<'_, T>;
-//     type IterMut = GridIterMut<'_, T>;  
-
-//     type RowIter = RowIter<'_, T>;
-//     type RowIterMut = RowIterMut<'_, T>;
-
-//     type ColumnIter = ColumnIter<'_, T>;
-//     type ColumnIterMut = ColumnIterMut<'_, T>;
-
-//     type DepthIter = DepthIter<'_, T>;
-//     type DepthIterMut = DepthIterMut<'_, T>;
-
-//     type LayerIter = LayerIter<'_, T>;
-//     type LayerIterMut = LayerIterMut<'_, T>;
-
-//     type NeighborsIter = NeighborsIter<'_, T>;
-//     type NeighborsIterMut = NeighborsIterMut<'_, T>;
-
-//     type BoundaryIter = BoundaryIter<'_, T>;
-//     type BoundaryIterMut = BoundaryIterMut<'_, T>;  
-
-//     fn dimensions(&self) -> (i32, i32, i32) {
-//         (self.width, self.height, self.depth)
-//     }
-
-//     fn get(&self, pos: Self::Position) -> Option<&Self::GridItem> {
-//         self.items.get(pos.into())
-//     }
-//     fn get_mut(&mut self, pos: Self::Position) -> Option<&mut Self::GridItem> {
-//         self.items.get_mut(pos.into())
-//     }
-//     fn set(&mut self, pos: Self::Position, item: Self::GridItem) {
-//         self.items[pos.into()] = item;
-//     }
-
-// }
-
-// impl<T: Clone + Copy> Grid<T> {
-//     pub fn new(width: i32, height: i32, depth: i32, default_value: T) -> Self {
-//         if width == 0 || height == 0 || depth == 0 {
-//             panic!("width and height can not be zero");
-//         }
-//         Self {
-//             width,
-//             height,
-//             depth,
-//             items: vec![default_value; (width * height * depth) as usize],
-//         }
-//     }
-//     pub fn is_bounds<P: Into<GridPosition>>(&self, pos: P) -> bool {
-//         let pos = pos.into();
-//         pos.x < self.width && pos.y < self.height && pos.z < self.depth
-//     }
-
-//     pub fn iter(&self) -> GridIter<'_, T> {
-//         GridIter {
-//             grid_iter: self.items.iter(),
-//             width: self.width,
-//             height: self.height,
-//         }
-//     }
-//     pub fn iter_mut(&mut self) -> GridIterMut<'_, T> {
-//         GridIterMut {
-//             grid_iter: self.items.iter_mut(),
-//             width: self.width,
-//             height: self.height,
-//         }
-//     }
-//     pub fn row(&self, y: i32, z: i32) -> RowIter<'_, T> {
-//         assert!(self.is_bounds((0, y, z)));
-//         let start_idx = (y as usize * self.width as usize);
-//         let end_idx = start_idx + self.width as usize;
-
-//         RowIter {
-//             row_iter: self.items[start_idx..end_idx].iter(),
-//             idx: y,
-//         }
-//     }
-//     pub fn row_mut(&mut self, y: i32, z: i32) -> RowIterMut<'_, T> {
-//         assert!(self.is_bounds((0, y, z)));
-//         let start_idx = y  * self.width;
-//         let end_idx = start_idx + self.width;
-
-//         RowIterMut {
-//             row_iter: self.items[start_idx as usize..end_idx as usize].iter_mut(),
-//             idx: y,
-//         }
-//     }
-// }
\ No newline at end of file
+impl<T: Clone> Grid3DLike for Grid<T> {
+    type GridItem = T;
+
+    fn in_bounds(&self, pos: GridPosition) -> bool {
+        (0..self.width).contains(&pos.x) && (0..self.height).contains(&pos.y) && (0..self.depth).contains(&pos.z)
+    }
+
+    fn get(&self, pos: GridPosition) -> Option<&T> {
+        self.index(pos).map(|idx| &self.items[idx])
+    }
+
+    fn get_mut(&mut self, pos: GridPosition) -> Option<&mut T> {
+        self.index(pos).map(|idx| &mut self.items[idx])
+    }
+
+    fn set(&mut self, pos: GridPosition, item: T) -> Result<Option<T>, GridError> {
+        let idx = self.index(pos).ok_or(GridError::OutOfBounds(pos))?;
+        Ok(Some(std::mem::replace(&mut self.items[idx], item)))
+    }
+
+    fn iter(&self) -> impl Iterator<Item = (GridPosition, &T)> {
+        Grid::iter(self)
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new((width, height, depth): (i32, i32, i32), fill: T) -> Result<Self, GridError> {
+        if width <= 0 || height <= 0 || depth <= 0 {
+            return Err(GridError::EmptyDimensions((width, height, depth)));
+        }
+        let volume = (width as usize)
+            .checked_mul(height as usize)
+            .and_then(|area| area.checked_mul(depth as usize))
+            .ok_or(GridError::TooLarge((width, height, depth)))?;
+        Ok(Self {
+            items: vec![fill; volume],
+            width,
+            height,
+            depth,
+        })
+    }
+
+    /// Returns the dimensions of the grid as a tuple (width, height, depth).
+    pub fn dimensions(&self) -> (i32, i32, i32) {
+        (self.width, self.height, self.depth)
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Storage index of `pos`, or `None` outside the grid.
+    pub fn index(&self, pos: GridPosition) -> Option<usize> {
+        let (width, height) = (self.width as usize, self.height as usize);
+        self.in_bounds(pos)
+            .then(|| pos.x as usize + width * (pos.y as usize + height * pos.z as usize))
+    }
+
+    /// Inverse of [`Grid::index`].
+    pub fn position(&self, idx: usize) -> Option<GridPosition> {
+        let (width, layer) = (self.width as usize, self.width as usize * self.height as usize);
+        (idx < self.items.len()).then(|| {
+            let rem = idx % layer;
+            GridPosition::new((rem % width) as i32, (rem / width) as i32, (idx / layer) as i32)
+        })
+    }
+
+    pub fn positions(&self) -> GridPositionsIter {
+        GridPositionsIter::new(self.dimensions())
+    }
+
+    pub fn iter(&self) -> GridIter<'_, T> {
+        GridIter {
+            grid_iter: self.items.iter(),
+            positions: self.positions(),
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> GridIterMut<'_, T> {
+        GridIterMut {
+            positions: self.positions(),
+            grid_iter: self.items.iter_mut(),
+        }
+    }
+
+    /// Cells along X at height `y` and depth `z`.
+    pub fn row(&self, y: i32, z: i32) -> Result<LineIter<'_, T>, GridError> {
+        self.line(GridPosition::new(0, y, z), (1, 0, 0), self.width)
+    }
+
+    pub fn row_mut(&mut self, y: i32, z: i32) -> Result<LineIterMut<'_, T>, GridError> {
+        self.line_mut(GridPosition::new(0, y, z), (1, 0, 0), self.width)
+    }
+
+    /// Cells along Y (bottom to top) at `x` and depth `z`.
+    pub fn column(&self, x: i32, z: i32) -> Result<LineIter<'_, T>, GridError> {
+        self.line(GridPosition::new(x, 0, z), (0, 1, 0), self.height)
+    }
+
+    pub fn column_mut(&mut self, x: i32, z: i32) -> Result<LineIterMut<'_, T>, GridError> {
+        self.line_mut(GridPosition::new(x, 0, z), (0, 1, 0), self.height)
+    }
+
+    /// Cells along Z at `x` and height `y`.
+    pub fn depth(&self, x: i32, y: i32) -> Result<LineIter<'_, T>, GridError> {
+        self.line(GridPosition::new(x, y, 0), (0, 0, 1), self.depth)
+    }
+
+    pub fn depth_mut(&mut self, x: i32, y: i32) -> Result<LineIterMut<'_, T>, GridError> {
+        self.line_mut(GridPosition::new(x, y, 0), (0, 0, 1), self.depth)
+    }
+
+    /// The horizontal slice at height `y`.
+    pub fn layer(&self, y: i32) -> Result<impl Iterator<Item = (GridPosition, &T)>, GridError> {
+        let start = GridPosition::new(0, y, 0);
+        self.index(start).ok_or(GridError::OutOfBounds(start))?;
+        let (width, height) = (self.width as usize, self.height as usize);
+        Ok(self
+            .items
+            .chunks(width)
+            .enumerate()
+            .filter(move |(chunk, _)| chunk % height == y as usize)
+            .flat_map(move |(chunk, row)| {
+                let z = (chunk / height) as i32;
+                row.iter().enumerate().map(move |(x, item)| (GridPosition::new(x as i32, y, z), item))
+            }))
+    }
+
+    pub fn layer_mut(&mut self, y: i32) -> Result<impl Iterator<Item = (GridPosition, &mut T)>, GridError> {
+        let start = GridPosition::new(0, y, 0);
+        self.index(start).ok_or(GridError::OutOfBounds(start))?;
+        let (width, height) = (self.width as usize, self.height as usize);
+        Ok(self
+            .items
+            .chunks_mut(width)
+            .enumerate()
+            .filter(move |(chunk, _)| chunk % height == y as usize)
+            .flat_map(move |(chunk, row)| {
+                let z = (chunk / height) as i32;
+                row.iter_mut().enumerate().map(move |(x, item)| (GridPosition::new(x as i32, y, z), item))
+            }))
+    }
+
+    /// Whether `pos` lies on the outer shell of the grid.
+    pub fn is_boundary(&self, pos: GridPosition) -> bool {
+        self.in_bounds(pos)
+            && (pos.x == 0
+                || pos.y == 0
+                || pos.z == 0
+                || pos.x == self.width - 1
+                || pos.y == self.height - 1
+                || pos.z == self.depth - 1)
+    }
+
+    /// Cells on the outer shell of the grid.
+    pub fn boundary(&self) -> impl Iterator<Item = (GridPosition, &T)> {
+        self.iter().filter(|(pos, _)| self.is_boundary(*pos))
+    }
+
+    pub fn boundary_mut(&mut self) -> impl Iterator<Item = (GridPosition, &mut T)> {
+        let (width, height, depth) = self.dimensions();
+        self.iter_mut().filter(move |(pos, _)| {
+            pos.x == 0 || pos.y == 0 || pos.z == 0 || pos.x == width - 1 || pos.y == height - 1 || pos.z == depth - 1
+        })
+    }
+
+    fn line(&self, start: GridPosition, step: (i32, i32, i32), len: i32) -> Result<LineIter<'_, T>, GridError> {
+        let (first, stride) = self.line_span(start, step)?;
+        Ok(LineIter {
+            line_iter: self.items[first..=first + stride * (len as usize - 1)].iter().step_by(stride),
+            position: start,
+            step,
+        })
+    }
+
+    fn line_mut(&mut self, start: GridPosition, step: (i32, i32, i32), len: i32) -> Result<LineIterMut<'_, T>, GridError> {
+        let (first, stride) = self.line_span(start, step)?;
+        Ok(LineIterMut {
+            line_iter: self.items[first..=first + stride * (len as usize - 1)].iter_mut().step_by(stride),
+            position: start,
+            step,
+        })
+    }
+
+    fn line_span(&self, start: GridPosition, (dx, dy, dz): (i32, i32, i32)) -> Result<(usize, usize), GridError> {
+        let first = self.index(start).ok_or(GridError::OutOfBounds(start))?;
+        let (width, height) = (self.width as usize, self.height as usize);
+        let stride = dx as usize + width * (dy as usize + height * dz as usize);
+        Ok((first, stride))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Neighbourhood;
+
+    #[test]
+    fn dense_grid_lines_layers_and_neighbours() {
+        let mut grid = Grid::new((3, 4, 5), 0).unwrap();
+        for (pos, cell) in grid.iter_mut() {
+            *cell = pos.x + 10 * pos.y + 100 * pos.z;
+        }
+        assert_eq!(grid.get((2, 3, 4).into()), Some(&432));
+        assert_eq!(grid.set((3, 0, 0).into(), 1), Err(GridError::OutOfBounds((3, 0, 0).into())));
+
+        let column: Vec<_> = grid.column(1, 2).unwrap().map(|(_, v)| *v).collect();
+        assert_eq!(column, [201, 211, 221, 231]);
+        let depth: Vec<_> = grid.depth(2, 1).unwrap().map(|(pos, v)| (pos.z, *v)).collect();
+        assert_eq!(depth, [(0, 12), (1, 112), (2, 212), (3, 312), (4, 412)]);
+        assert!(grid.layer(2).unwrap().all(|(pos, v)| pos.y == 2 && *v == pos.x + 20 + 100 * pos.z));
+        assert_eq!(grid.layer(2).unwrap().count(), 15);
+        // Everything but the 1×2×3 interior.
+        assert_eq!(grid.boundary().count(), 60 - 6);
+
+        assert_eq!(grid.neighbours((0, 0, 0).into(), Neighbourhood::VonNeumann).count(), 3);
+        assert_eq!(grid.neighbours((1, 1, 1).into(), Neighbourhood::Moore).count(), 26);
+    }
+
+    #[test]
+    fn volume_is_computed_without_overflow() {
+        // Wraps in i32 and still overflows usize.
+        let huge = (i32::MAX, i32::MAX, i32::MAX);
+        assert_eq!(Grid::new(huge, 0u8).err(), Some(GridError::TooLarge(huge)));
+        let grid = Grid::new((2, 3, 4), 0u8).unwrap();
+        assert_eq!(grid.position(grid.index((1, 2, 3).into()).unwrap()), Some((1, 2, 3).into()));
+    }
+
+    #[test]
+    fn zero_sized_cells_allow_more_than_i32_max() {
+        let grid = Grid::new((50_000, 50_000, 2), ()).unwrap();
+        assert_eq!(grid.len(), 5_000_000_000);
+        assert_eq!(grid.positions().len(), 5_000_000_000);
+        let last = GridPosition::new(49_999, 49_999, 1);
+        assert_eq!(grid.index(last), Some(4_999_999_999));
+        assert_eq!(grid.position(4_999_999_999), Some(last));
+        assert_eq!(grid.iter().next(), Some(((0, 0, 0).into(), &())));
+        let depth: Vec<_> = grid.depth(49_999, 49_999).unwrap().map(|(pos, _)| pos).collect();
+        assert_eq!(depth, [(49_999, 49_999, 0).into(), last]);
+        assert_eq!(grid.layer(49_999).unwrap().nth(50_000), Some(((0, 49_999, 1).into(), &())));
+    }
+}
@@ -1,49 +1,270 @@
+use bevy::prelude::{IVec3, Mat3, Quat, Vec3};
 use serde::{Serialize, Deserialize};
 use strum::EnumIter;
 
+/// A cube axis.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize, Hash, PartialOrd, Ord, EnumIter)]
+pub enum Axis {
+    X,
+    Y,
+    Z,
+}
+
+impl Axis {
+    pub const ALL: [Axis; 3] = [Axis::X, Axis::Y, Axis::Z];
 
+    pub fn index(self) -> usize {
+        self as usize
+    }
 
-/// A partial orientation group with 4 discrete states for demonstration.
-#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize, Default, Hash, EnumIter)]
-pub enum Orientation {
-    #[default]
-    O0,
-    O90,
-    O180,
-    O270,
+    pub fn unit(self) -> IVec3 {
+        match self {
+            Axis::X => IVec3::X,
+            Axis::Y => IVec3::Y,
+            Axis::Z => IVec3::Z,
+        }
+    }
+}
+
+/// The six permutations of (X, Y, Z), even ones first.
+const PERMUTATIONS: [[Axis; 3]; 6] = [
+    [Axis::X, Axis::Y, Axis::Z],
+    [Axis::Y, Axis::Z, Axis::X],
+    [Axis::Z, Axis::X, Axis::Y],
+    [Axis::X, Axis::Z, Axis::Y],
+    [Axis::Y, Axis::X, Axis::Z],
+    [Axis::Z, Axis::Y, Axis::X],
+];
+
+/// One of the 48 symmetries of the cube, stored as a signed permutation of the
+/// axes. The 24 with determinant +1 are the rotations a block can be placed in;
+/// the other 24 also mirror it.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize, Hash)]
+pub struct Orientation {
+    /// World axis each local axis (X, Y, Z) ends up along.
+    axes: [Axis; 3],
+    /// Whether each local axis ends up pointing along the negative world axis.
+    flips: [bool; 3],
+}
+
+impl Default for Orientation {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
 }
 
 impl Orientation {
+    pub const IDENTITY: Orientation = Orientation { axes: [Axis::X, Axis::Y, Axis::Z], flips: [false; 3] };
+    /// Number of cube symmetries, including reflections.
+    pub const COUNT: usize = 48;
+    /// Number of proper rotations.
+    pub const ROTATIONS: usize = 24;
+
+    /// `None` unless `axes` is a permutation of X, Y and Z.
+    pub fn new(axes: [Axis; 3], flips: [bool; 3]) -> Option<Self> {
+        PERMUTATIONS.contains(&axes).then_some(Self { axes, flips })
+    }
+
+    /// `quarter_turns` right-handed quarter turns about `axis`; negative turns go the other way.
+    pub fn about(axis: Axis, quarter_turns: i32) -> Self {
+        // One quarter turn sends the next axis to the one after it and that one back, flipped.
+        let (a, b) = match axis {
+            Axis::X => (Axis::Y, Axis::Z),
+            Axis::Y => (Axis::Z, Axis::X),
+            Axis::Z => (Axis::X, Axis::Y),
+        };
+        let mut turn = Self::IDENTITY;
+        turn.axes[a.index()] = b;
+        turn.axes[b.index()] = a;
+        turn.flips[b.index()] = true;
+        (0..quarter_turns.rem_euclid(4)).fold(Self::IDENTITY, |orientation, _| turn.compose(orientation))
+    }
+
+    /// Mirror across the plane normal to `axis`.
+    pub fn reflection(axis: Axis) -> Self {
+        let mut flips = [false; 3];
+        flips[axis.index()] = true;
+        Self { flips, ..Self::IDENTITY }
+    }
+
+    /// The 24 rotations, identity first.
+    pub fn rotations() -> impl Iterator<Item = Orientation> {
+        Self::all().filter(|o| o.is_rotation())
+    }
+
+    /// All 48 symmetries in `index` order.
+    pub fn all() -> impl Iterator<Item = Orientation> {
+        (0..Self::COUNT).filter_map(Self::from_index)
+    }
+
+    /// Dense index in `0..COUNT`, the identity being 0.
+    pub fn index(self) -> usize {
+        let permutation = PERMUTATIONS.iter().position(|p| *p == self.axes).unwrap_or_default();
+        let flips = self.flips.iter().enumerate().fold(0, |bits, (i, &f)| bits | usize::from(f) << i);
+        permutation * 8 + flips
+    }
+
+    pub fn from_index(index: usize) -> Option<Self> {
+        let axes = *PERMUTATIONS.get(index / 8)?;
+        let flips = [0, 1, 2].map(|i| index & (1 << i) != 0);
+        Some(Self { axes, flips })
+    }
+
+    /// World axis each local axis ends up along.
+    pub fn axes(self) -> [Axis; 3] {
+        self.axes
+    }
+
+    /// Whether each local axis ends up reversed.
+    pub fn flips(self) -> [bool; 3] {
+        self.flips
+    }
+
+    pub fn determinant(self) -> i32 {
+        let parity = if PERMUTATIONS[..3].contains(&self.axes) { 1 } else { -1 };
+        self.flips.iter().fold(parity, |d, &f| if f { -d } else { d })
+    }
+
+    pub fn is_rotation(self) -> bool {
+        self.determinant() > 0
+    }
+
+    /// `other` followed by `self`.
     pub fn compose(self, other: Orientation) -> Orientation {
-        // Just an example of group-like composition
-        let sum = (self.as_degrees() + other.as_degrees()) % 360;
-        Self::from_degrees(sum)
+        let axes = other.axes.map(|a| self.axes[a.index()]);
+        let flips = [0, 1, 2].map(|i| other.flips[i] != self.flips[other.axes[i].index()]);
+        Orientation { axes, flips }
     }
 
-    fn as_degrees(self) -> u16 {
-        match self {
-            Orientation::O0 => 0,
-            Orientation::O90 => 90,
-            Orientation::O180 => 180,
-            Orientation::O270 => 270,
+    pub fn inverse(self) -> Orientation {
+        let mut inverse = self;
+        for (local, world) in Axis::ALL.into_iter().zip(self.axes) {
+            inverse.axes[world.index()] = local;
+            inverse.flips[world.index()] = self.flips[local.index()];
         }
+        inverse
+    }
+
+    /// Columns are the images of the local X, Y and Z unit vectors.
+    pub fn matrix(self) -> Mat3 {
+        let [x, y, z] = [0, 1, 2].map(|i| {
+            let column = self.axes[i].unit().as_vec3();
+            if self.flips[i] { -column } else { column }
+        });
+        Mat3::from_cols(x, y, z)
     }
 
-    fn from_degrees(d: u16) -> Orientation {
-        match d {
-            0   => Orientation::O0,
-            90  => Orientation::O90,
-            180 => Orientation::O180,
-            270 => Orientation::O270,
-            _   => panic!("Invalid discrete orientation"),
+    /// Nearest cube symmetry to `matrix`; `None` if it isn't within rounding of a
+    /// signed permutation.
+    pub fn from_matrix(matrix: Mat3) -> Option<Self> {
+        let mut axes = [Axis::X; 3];
+        let mut flips = [false; 3];
+        for i in 0..3 {
+            let column = matrix.col(i);
+            let axis = Axis::ALL.into_iter().find(|a| (column[a.index()].abs() - 1.0).abs() < 1e-3)?;
+            if Axis::ALL.into_iter().any(|a| a != axis && column[a.index()].abs() > 1e-3) {
+                return None;
+            }
+            axes[i] = axis;
+            flips[i] = column[axis.index()] < 0.0;
         }
+        Self::new(axes, flips)
     }
 
-    
+    /// `None` for reflections, which have no quaternion.
+    pub fn to_quat(self) -> Option<Quat> {
+        self.is_rotation().then(|| Quat::from_mat3(&self.matrix()))
+    }
+
+    /// Snap `rotation` to a cube rotation; `None` if it isn't within rounding of one.
+    pub fn from_quat(rotation: Quat) -> Option<Self> {
+        Self::from_matrix(Mat3::from_quat(rotation.normalize()))
+    }
 
-   
+    pub fn apply(self, v: IVec3) -> IVec3 {
+        let mut out = IVec3::ZERO;
+        for i in 0..3 {
+            out[self.axes[i].index()] = if self.flips[i] { -v[i] } else { v[i] };
+        }
+        out
+    }
+
+    pub fn apply_vec3(self, v: Vec3) -> Vec3 {
+        self.matrix() * v
+    }
+
+    /// Extent along the world axes of a block with local extent `size`.
+    pub fn apply_size(self, size: (u32, u32, u32)) -> (u32, u32, u32) {
+        let local = [size.0, size.1, size.2];
+        let mut world = [0; 3];
+        for i in 0..3 {
+            world[self.axes[i].index()] = local[i];
+        }
+        (world[0], world[1], world[2])
+    }
+
+    /// Cell of an oriented block of local `size` that local `cell` ends up in,
+    /// counted from the oriented block's minimum corner.
+    pub fn apply_cell(self, cell: (u32, u32, u32), size: (u32, u32, u32)) -> (u32, u32, u32) {
+        let (local, extent) = ([cell.0, cell.1, cell.2], [size.0, size.1, size.2]);
+        let mut world = [0; 3];
+        for i in 0..3 {
+            world[self.axes[i].index()] = if self.flips[i] { extent[i].saturating_sub(local[i] + 1) } else { local[i] };
+        }
+        (world[0], world[1], world[2])
+    }
+
+    /// Map `point` in the local box `[0, size]` into the oriented box
+    /// `[0, apply_size(size)]`, so blocks keep their minimum corner at their position.
+    pub fn apply_in_box(self, point: Vec3, size: (u32, u32, u32)) -> Vec3 {
+        let local = Vec3::new(size.0 as f32, size.1 as f32, size.2 as f32);
+        let mut out = self.apply_vec3(point);
+        for i in 0..3 {
+            if self.flips[i] {
+                out[self.axes[i].index()] += local[i];
+            }
+        }
+        out
+    }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
 
+    #[test]
+    fn rotations_form_a_group() {
+        let rotations: Vec<_> = Orientation::rotations().collect();
+        assert_eq!(rotations.len(), Orientation::ROTATIONS);
+        assert_eq!(rotations[0], Orientation::IDENTITY);
+        for &a in &rotations {
+            assert_eq!(a.compose(a.inverse()), Orientation::IDENTITY);
+            assert_eq!(Orientation::from_index(a.index()), Some(a));
+            for &b in &rotations {
+                assert!(rotations.contains(&a.compose(b)));
+                assert_eq!(a.compose(b).matrix(), a.matrix() * b.matrix());
+            }
+        }
+        assert_eq!(Orientation::all().filter(|o| !o.is_rotation()).count(), 24);
+    }
 
+    #[test]
+    fn quarter_turns_match_quaternions() {
+        let turn = Orientation::about(Axis::Y, 1);
+        assert_eq!(turn.apply(IVec3::X), IVec3::NEG_Z);
+        assert_eq!(Orientation::about(Axis::Y, 4), Orientation::IDENTITY);
+        assert_eq!(Orientation::about(Axis::Z, -1), Orientation::about(Axis::Z, 3));
+        let q = Quat::from_rotation_y(std::f32::consts::FRAC_PI_2);
+        assert_eq!(Orientation::from_quat(q), Some(turn));
+        assert!(turn.to_quat().unwrap().abs_diff_eq(q, 1e-6) || turn.to_quat().unwrap().abs_diff_eq(-q, 1e-6));
+        assert_eq!(Orientation::reflection(Axis::X).to_quat(), None);
+    }
 
+    #[test]
+    fn oriented_box_keeps_its_corner() {
+        let turn = Orientation::about(Axis::Z, 1);
+        assert_eq!(turn.apply_size((3, 1, 2)), (1, 3, 2));
+        assert_eq!(turn.apply_in_box(Vec3::new(3.0, 1.0, 2.0), (3, 1, 2)), Vec3::new(0.0, 3.0, 2.0));
+        assert_eq!(turn.apply_in_box(Vec3::ZERO, (3, 1, 2)), Vec3::new(1.0, 0.0, 0.0));
+    }
+}
@@ -1,6 +1,10 @@
 use serde::{Deserialize, Serialize};
 
-// Define a new enum for symmetry operations, including reflections
+use crate::face::Face;
+use crate::orientation::{Axis, Orientation};
+
+/// Symmetry operations on blocks. Rotations turn about the vertical (Y) axis and
+/// reflections mirror across the plane normal to the named axis.
 #[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 pub enum SymmetryOperation {
     Identity,
@@ -10,21 +14,128 @@ pub enum SymmetryOperation {
     ReflectX,
     ReflectY,
     ReflectZ,
-    // Add more operations as needed
 }
 
-// Implement a method to apply a symmetry operation to a block
 impl SymmetryOperation {
-    // pub fn apply(&self, orientation: Orientation) -> Orientation {
-    //     match self {
-    //         SymmetryOperation::Identity => orientation,
-    //         SymmetryOperation::Rotate90 => orientation.rotate_90(),
-    //         SymmetryOperation::Rotate180 => orientation.rotate_180(),
-    //         SymmetryOperation::Rotate270 => orientation.rotate_270(),
-    //         SymmetryOperation::ReflectX => orientation.reflect_x(),
-    //         SymmetryOperation::ReflectY => orientation.reflect_y(),
-    //         SymmetryOperation::ReflectZ => orientation.reflect_z(),
-    //         // Implement the actual logic for each operation
-    //     }
-    // }
-}
\ No newline at end of file
+    /// The cube symmetry this operation performs.
+    pub fn orientation(self) -> Orientation {
+        match self {
+            SymmetryOperation::Identity => Orientation::IDENTITY,
+            SymmetryOperation::Rotate90 => Orientation::about(Axis::Y, 1),
+            SymmetryOperation::Rotate180 => Orientation::about(Axis::Y, 2),
+            SymmetryOperation::Rotate270 => Orientation::about(Axis::Y, 3),
+            SymmetryOperation::ReflectX => Orientation::reflection(Axis::X),
+            SymmetryOperation::ReflectY => Orientation::reflection(Axis::Y),
+            SymmetryOperation::ReflectZ => Orientation::reflection(Axis::Z),
+        }
+    }
+
+    /// Orientation of a block in `orientation` after this operation.
+    pub fn apply(self, orientation: Orientation) -> Orientation {
+        self.orientation().compose(orientation)
+    }
+
+    pub fn apply_size(self, size: (u32, u32, u32)) -> (u32, u32, u32) {
+        self.orientation().apply_size(size)
+    }
+
+    /// The faces of a block of local `size` after this operation.
+    pub fn apply_faces(self, faces: impl IntoIterator<Item = Face>, size: (u32, u32, u32)) -> Vec<Face> {
+        let orientation = self.orientation();
+        faces.into_iter().map(|face| face.oriented(orientation, size)).collect()
+    }
+}
+
+/// The orientations that leave a block looking the same, as a subgroup of the
+/// cube symmetries. Orientations that differ only by one of these give identical
+/// placements, so the solver only needs to try one of each.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct SymmetryClass {
+    /// Bit `Orientation::index` is set for each member.
+    members: u64,
+}
+
+impl Default for SymmetryClass {
+    fn default() -> Self {
+        Self::NONE
+    }
+}
+
+impl SymmetryClass {
+    /// No symmetry: all 24 rotations are distinct.
+    pub const NONE: SymmetryClass = SymmetryClass { members: 1 };
+
+    /// Unchanged by any rotation, like a uniform cube of material.
+    pub fn cubic() -> Self {
+        Self::generated_by(&[Orientation::about(Axis::X, 1), Orientation::about(Axis::Y, 1)])
+    }
+
+    /// Unchanged by `1 / order` turns about `axis` (`order` 2 or 4).
+    pub fn about(axis: Axis, order: u8) -> Self {
+        Self::generated_by(&[Orientation::about(axis, 4 / i32::from(order.clamp(1, 4)))])
+    }
+
+    /// The smallest class containing `generators`.
+    pub fn generated_by(generators: &[Orientation]) -> Self {
+        let mut class = Self::NONE;
+        loop {
+            let mut grown = class;
+            for member in class.members() {
+                for &generator in generators {
+                    grown.members |= 1 << generator.compose(member).index();
+                }
+            }
+            if grown == class {
+                return class;
+            }
+            class = grown;
+        }
+    }
+
+    pub fn contains(self, orientation: Orientation) -> bool {
+        self.members & (1 << orientation.index()) != 0
+    }
+
+    pub fn members(self) -> impl Iterator<Item = Orientation> {
+        Orientation::all().filter(move |&o| self.contains(o))
+    }
+
+    /// Number of member orientations.
+    pub fn order(self) -> usize {
+        self.members.count_ones() as usize
+    }
+
+    /// One rotation for each distinct placement of the block, the lowest-index one
+    /// of each set of equivalent rotations.
+    pub fn distinct_orientations(self) -> Vec<Orientation> {
+        let mut distinct: Vec<Orientation> = Vec::new();
+        for rotation in Orientation::rotations() {
+            if !distinct.iter().any(|&kept| self.contains(kept.inverse().compose(rotation))) {
+                distinct.push(rotation);
+            }
+        }
+        distinct
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn symmetry_classes_shrink_candidate_orientations() {
+        assert_eq!(SymmetryClass::NONE.distinct_orientations().len(), 24);
+        assert_eq!(SymmetryClass::about(Axis::Y, 2).distinct_orientations().len(), 12);
+        assert_eq!(SymmetryClass::about(Axis::Y, 4).distinct_orientations().len(), 6);
+        assert_eq!(SymmetryClass::cubic().order(), 24);
+        assert_eq!(SymmetryClass::cubic().distinct_orientations(), vec![Orientation::IDENTITY]);
+    }
+
+    #[test]
+    fn operations_compose_like_orientations() {
+        let turned = SymmetryOperation::Rotate90.apply(SymmetryOperation::Rotate270.orientation());
+        assert_eq!(turned, Orientation::IDENTITY);
+        assert_eq!(SymmetryOperation::Rotate90.apply_size((2, 1, 3)), (3, 1, 2));
+        assert!(!SymmetryOperation::ReflectZ.orientation().is_rotation());
+    }
+}
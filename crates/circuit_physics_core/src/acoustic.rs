@@ -0,0 +1,81 @@
+use std::f64::consts::PI;
+
+use uom::si::f64::{Area, ElectricPotential, Frequency, Length, Pressure};
+use uom::si::{area::square_meter, electric_potential::volt, frequency::hertz, length::meter, pressure::pascal};
+
+// ============================================================================
+// ACOUSTIC AND MICROPHONIC TRAITS
+// ============================================================================
+
+/// Density of air at 20 °C (kg/m³)
+pub const AIR_DENSITY: f64 = 1.204;
+
+/// 0 dB SPL (Pa)
+pub const REFERENCE_SOUND_PRESSURE: f64 = 20e-6;
+
+/// Components that convert between electrical and acoustic energy, such as
+/// piezoelectric ceramic capacitors that "sing" under ripple and pick up sound
+pub trait AcousticBehavior {
+    /// Effective piezoelectric coefficient of the whole part (m/V, equivalently C/N);
+    /// zero for parts that aren't piezoelectric
+    fn piezoelectric_coefficient(&self) -> f64;
+
+    /// Surface that moves with the part and radiates sound
+    fn radiating_area(&self) -> Area;
+
+    /// Peak surface displacement under a sinusoidal voltage of peak `ripple`
+    fn vibration_amplitude(&self, ripple: ElectricPotential, _frequency: Frequency) -> Length {
+        Length::new::<meter>(self.piezoelectric_coefficient() * ripple.get::<volt>().abs())
+    }
+
+    /// RMS sound pressure at `distance` under a sinusoidal `ripple`, radiating as
+    /// a small piston in the board baffle
+    fn acoustic_emission(&self, ripple: ElectricPotential, frequency: Frequency, distance: Length) -> Pressure {
+        let omega = 2.0 * PI * frequency.get::<hertz>();
+        let displacement = self.vibration_amplitude(ripple, frequency).get::<meter>();
+        let peak = AIR_DENSITY * omega * omega * displacement * self.radiating_area().get::<square_meter>()
+            / (2.0 * PI * distance.get::<meter>());
+        Pressure::new::<pascal>(peak / 2f64.sqrt())
+    }
+
+    /// Open-circuit voltage per pascal of sound pressure on the part (V/Pa)
+    fn microphonic_sensitivity(&self) -> f64;
+
+    fn is_piezoelectric(&self) -> bool {
+        self.piezoelectric_coefficient() > 0.0
+    }
+}
+
+/// Displacement gain of a mechanical resonance driven at `frequency`
+pub fn resonant_gain(frequency: Frequency, resonance: Frequency, q: f64) -> f64 {
+    let r = (frequency / resonance).value;
+    1.0 / ((1.0 - r * r).powi(2) + (r / q).powi(2)).sqrt()
+}
+
+/// Sound pressure level (dB re 20 µPa)
+pub fn sound_pressure_level(pressure: Pressure) -> f64 {
+    20.0 * (pressure.get::<pascal>() / REFERENCE_SOUND_PRESSURE).log10()
+}
+
+/// IEC 61672 A-weighting (dB) at `frequency`, 0 dB at 1 kHz
+pub fn a_weighting(frequency: Frequency) -> f64 {
+    let f2 = frequency.get::<hertz>().powi(2);
+    let response = 12194.0_f64.powi(2) * f2 * f2
+        / ((f2 + 20.6_f64.powi(2))
+            * ((f2 + 107.7_f64.powi(2)) * (f2 + 737.9_f64.powi(2))).sqrt()
+            * (f2 + 12194.0_f64.powi(2)));
+    20.0 * response.log10() + 2.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_weighting_matches_iec_table() {
+        let at = |hz: f64| a_weighting(Frequency::new::<hertz>(hz));
+        assert!(at(1000.0).abs() < 0.01);
+        assert!((at(100.0) + 19.1).abs() < 0.1);
+        assert!((at(10000.0) + 2.5).abs() < 0.1);
+    }
+}
@@ -1,14 +1,5 @@
 use serde::{Serialize, Deserialize};
-// use bevy::prelude::*;
-// use uom::si::f64::*;
-use uom::si::{
-    acceleration::meter_per_second_squared, electric_current::ampere, electric_potential::volt, electrical_resistance::ohm, f32::{Acceleration, ElectricCurrent, ElectricField, ElectricalResistance, Frequency, MagneticFluxDensity, Power, ThermodynamicTemperature, Time}, frequency::hertz, luminous_intensity::candela, magnetic_flux_density::tesla, power::watt, pressure::pascal, thermodynamic_temperature::kelvin, time::second
-};
-
-
-
-
-
+use uom::si::f64::Ratio;
 
 // ============================================================================
 // CHEMICAL AND ENVIRONMENTAL TRAITS
@@ -22,8 +13,8 @@ pub trait EnvironmentalDegradation {
     /// Corrosion resistance rating
     fn corrosion_resistance(&self) -> CorrosionResistance;
     
-    /// Outgassing characteristics (TML, CVCM percentages)
-    fn outgassing_properties(&self) -> (f32, f32); // (TML, CVCM)
+    /// Outgassing characteristics as mass loss fractions
+    fn outgassing_properties(&self) -> (Ratio, Ratio); // (TML, CVCM)
     
     /// Chemical compatibility with common materials
     fn chemical_compatibility(&self, material: ChemicalMaterial) -> bool;
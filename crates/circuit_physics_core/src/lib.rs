@@ -1,10 +1,13 @@
 pub mod material_properties;
+pub mod units;
 
 pub mod thermal;
 pub mod electromagnetic;
 pub mod mechanical;
 pub mod radiation;
 pub mod optical;
+pub mod acoustic;
 pub mod lifetime;
 pub mod environment_degradation;
-pub mod physical;
\ No newline at end of file
+pub mod physical;
+pub mod overrides;
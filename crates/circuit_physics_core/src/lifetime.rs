@@ -1,12 +1,9 @@
-use serde::{Serialize, Deserialize};
-// use bevy::prelude::*;
-// use uom::si::f64::*;
-use uom::si::{
-    acceleration::meter_per_second_squared, electric_current::ampere, electric_potential::volt, electrical_resistance::ohm, f32::{Acceleration, ElectricCurrent, ElectricField, ElectricalResistance, Frequency, MagneticFluxDensity, Power, ThermodynamicTemperature, Time}, frequency::hertz, luminous_intensity::candela, magnetic_flux_density::tesla, power::watt, pressure::pascal, thermodynamic_temperature::kelvin, time::second
-};
-
-
+use uom::si::energy::electronvolt;
+use uom::si::f64::{Energy, ThermodynamicTemperature, Time};
+use uom::si::thermodynamic_temperature::kelvin;
+use uom::si::time::year;
 
+use crate::units::REFERENCE_TEMPERATURE_KELVIN;
 
 // ============================================================================
 // AGING AND RELIABILITY TRAITS
@@ -14,45 +11,98 @@ use uom::si::{
 
 /// Components that exhibit aging mechanisms
 pub trait AgingMechanisms {
-    /// Activation energy for primary aging mechanism (eV)
-    fn activation_energy(&self) -> f32;
+    /// Activation energy for primary aging mechanism
+    fn activation_energy(&self) -> Energy;
     
     /// Stress acceleration factor
-    fn stress_acceleration_factor(&self, stress_level: f32, reference_level: f32) -> f32;
+    fn stress_acceleration_factor(&self, stress_level: f64, reference_level: f64) -> f64;
     
     /// Time to failure based on Arrhenius model
-    fn time_to_failure_arrhenius(&self, temperature: ThermodynamicTemperature, stress: f32) -> Time {
+    fn time_to_failure_arrhenius(&self, temperature: ThermodynamicTemperature, stress: f64) -> Time {
         let k_b = 8.617e-5; // Boltzmann constant in eV/K
         let temp_k = temperature.get::<kelvin>();
-        let reference_temp = 298.15; // 25°C
+        let reference_temp = REFERENCE_TEMPERATURE_KELVIN;
         
-        let acceleration = ((self.activation_energy() / k_b) * 
+        let acceleration = ((self.activation_energy().get::<electronvolt>() / k_b) * 
                           (1.0/reference_temp - 1.0/temp_k)).exp();
         let stress_factor = self.stress_acceleration_factor(stress, 1.0) ;
         
         // Base lifetime at reference conditions (assume 10 years)
-        let base_lifetime: f32 = 10.0 * 365.0 * 24.0 * 3600.0; // seconds
+        let base_lifetime = Time::new::<year>(10.0);
         
-        Time::new::<second>(base_lifetime / (acceleration * stress_factor))
+        base_lifetime / (acceleration * stress_factor)
     }
 }
 
 /// Components with wear-out mechanisms
 pub trait WearOut {
     /// Wear-out distribution parameters (Weibull shape and scale)
-    fn weibull_parameters(&self) -> (f32, f32); // (beta, eta)
+    fn weibull_parameters(&self) -> (f64, Time); // (beta, eta)
     
     /// Calculate reliability at given time
-    fn reliability_at_time(&self, time: Time) -> f32 {
+    fn reliability_at_time(&self, time: Time) -> f64 {
         let (beta, eta) = self.weibull_parameters();
-        let t = time.get::<second>() as f32;
-        (-((t / eta).powf(beta))).exp()
+        (-((time / eta).value.powf(beta))).exp()
     }
     
-    // Mean time to failure
-    // fn mean_time_to_failure(&self) -> Time {
-    //     let (beta, eta) = self.weibull_parameters();
-    //     let gamma_factor = gamma::gamma(1.0 + 1.0/beta);
-    //     Time::new::<second>((eta * gamma_factor))
-    // }
+    /// Mean time to failure
+    fn mean_time_to_failure(&self) -> Time {
+        let (beta, eta) = self.weibull_parameters();
+        weibull_mean(beta, eta)
+    }
+}
+
+/// Mean of a Weibull distribution, `η·Γ(1 + 1/β)`.
+pub fn weibull_mean(beta: f64, eta: Time) -> Time {
+    eta * gamma(1.0 + 1.0 / beta)
+}
+
+/// Gamma function via the Lanczos approximation (g = 7, n = 9), accurate to ~1e-15
+/// for positive arguments; the reflection formula covers `x < 0.5`.
+pub fn gamma(x: f64) -> f64 {
+    const G: f64 = 7.0;
+    const COEFFICIENTS: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+
+    if x < 0.5 {
+        return std::f64::consts::PI / ((std::f64::consts::PI * x).sin() * gamma(1.0 - x));
+    }
+    let x = x - 1.0;
+    let t = x + G + 0.5;
+    let series = COEFFICIENTS[1..]
+        .iter()
+        .enumerate()
+        .fold(COEFFICIENTS[0], |sum, (i, c)| sum + c / (x + i as f64 + 1.0));
+    (2.0 * std::f64::consts::PI).sqrt() * t.powf(x + 0.5) * (-t).exp() * series
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uom::si::time::second;
+
+    #[test]
+    fn gamma_matches_known_values() {
+        assert!((gamma(1.0) - 1.0).abs() < 1e-12);
+        assert!((gamma(5.0) - 24.0).abs() < 1e-9);
+        assert!((gamma(0.5) - std::f64::consts::PI.sqrt()).abs() < 1e-12);
+        assert!((gamma(1.5) - 0.5 * std::f64::consts::PI.sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn weibull_mean_reduces_to_exponential_for_unit_shape() {
+        let seconds = |s| Time::new::<second>(s);
+        assert!((weibull_mean(1.0, seconds(1000.0)).get::<second>() - 1000.0).abs() < 1e-9);
+        // Rayleigh: η·√π/2
+        assert!((weibull_mean(2.0, seconds(2.0)).get::<second>() - std::f64::consts::PI.sqrt()).abs() < 1e-12);
+    }
 }
@@ -1,11 +1,14 @@
+use uom::si::f64::{ElectricalResistivity, MassDensity, Pressure, SpecificHeatCapacity, ThermalConductivity};
+
 /// Separate trait for material/physical properties needed for physics simulation
 pub trait MaterialProperties {
-    fn thermal_conductivity(&self) -> f32;
-    fn electrical_resistivity(&self) -> f32;
-    fn youngs_modulus(&self) -> f32;
-    fn poisson_ratio(&self) -> f32;
-    fn density(&self) -> f32;
-    fn specific_heat(&self) -> f32;
+    fn thermal_conductivity(&self) -> ThermalConductivity;
+    fn electrical_resistivity(&self) -> ElectricalResistivity;
+    fn youngs_modulus(&self) -> Pressure;
+    /// Dimensionless, typically 0.2–0.4
+    fn poisson_ratio(&self) -> f64;
+    fn density(&self) -> MassDensity;
+    fn specific_heat(&self) -> SpecificHeatCapacity;
 }
 
 /// Optional trait for blocks that have a specific material/construction type
@@ -1,10 +1,9 @@
-use serde::{Serialize, Deserialize};
-// use bevy::prelude::*;
-// use uom::si::f64::*;
-use uom::si::{
-    acceleration::meter_per_second_squared, electric_current::ampere, electric_potential::volt, electrical_resistance::ohm, f32::{Acceleration, ElectricCurrent, ElectricField, ElectricalResistance, Frequency, MagneticFluxDensity, Power, ThermodynamicTemperature, Time}, frequency::hertz, luminous_intensity::candela, magnetic_flux_density::tesla, power::watt, pressure::pascal, thermodynamic_temperature::kelvin, time::second
+use uom::si::f64::{
+    Acceleration, Frequency, Length, TemperatureCoefficient, TemperatureInterval, ThermodynamicTemperature, Time,
 };
+use uom::si::{temperature_coefficient::per_kelvin, temperature_interval::kelvin, time::minute};
 
+use crate::units::to_celsius;
 
 // ============================================================================
 // MECHANICAL STRESS AND VIBRATION TRAITS
@@ -12,16 +11,16 @@ use uom::si::{
 
 /// Components that can experience mechanical stress and vibration
 pub trait MechanicalStress {
-    /// Maximum acceleration the component can withstand (g's)
+    /// Maximum acceleration the component can withstand
     fn max_acceleration(&self) -> Acceleration;
     
-    /// Resonant frequency for vibration analysis (Hz)
+    /// Resonant frequency for vibration analysis
     fn resonant_frequency(&self) -> Option<Frequency>;
     
     /// Mechanical quality factor (Q-factor)
-    fn mechanical_q_factor(&self) -> f32;
+    fn mechanical_q_factor(&self) -> f64;
     
-    /// Shock resistance (g's for specified duration)
+    /// Shock resistance (peak acceleration for specified duration)
     fn shock_resistance(&self) -> (Acceleration, Time);
     
     /// Check if component can survive given acceleration
@@ -30,19 +29,87 @@ pub trait MechanicalStress {
     }
 }
 
+/// Fatigue ductility coefficient ε'f of eutectic `SnPb` solder (Engelmaier)
+pub const FATIGUE_DUCTILITY_COEFFICIENT: f64 = 0.325;
+
 /// Components susceptible to solder joint fatigue and mechanical failure
 pub trait SolderJointReliability {
-    /// Coefficient of thermal expansion mismatch (ppm/°C)
-    fn cte_mismatch(&self) -> f32;
-    
-    /// Solder joint geometry factor
-    fn joint_geometry_factor(&self) -> f32;
-    
-    /// Predicted cycles to failure for thermal cycling
-    fn thermal_cycles_to_failure(&self, temp_range: f32, cycle_time: Time) -> u32 {
-        // Simplified Coffin-Manson model
-        let delta_t = temp_range;
-        let n_f = 1000.0 * (delta_t / 100.0).powf(-2.0) * self.joint_geometry_factor();
-        n_f as u32
+    /// Coefficient of thermal expansion mismatch
+    fn cte_mismatch(&self) -> TemperatureCoefficient;
+    
+    /// Joint compliance relative to a chip resistor fillet; leads and underfill
+    /// raise it and the shear strain is divided by it
+    fn joint_geometry_factor(&self) -> f64;
+    
+    /// Distance from the package's neutral point to the farthest joint
+    fn neutral_point_distance(&self) -> Length;
+    
+    /// Solder joint height (stand-off)
+    fn joint_height(&self) -> Length;
+    
+    /// Cyclic shear strain range Δγ of the farthest joint for a temperature swing
+    fn cyclic_strain_range(&self, temp_range: TemperatureInterval) -> f64 {
+        let expansion = self.cte_mismatch().get::<per_kelvin>() * temp_range.get::<kelvin>().abs();
+        let lever = (self.neutral_point_distance() / self.joint_height()).value;
+        expansion * lever / self.joint_geometry_factor()
+    }
+    
+    /// Engelmaier cycles to failure `Nf = ½ (Δγ / 2ε'f)^(1/c)` for a swing of
+    /// `temp_range` around `mean_temperature` with period `cycle_time`
+    fn thermal_cycles_to_failure(
+        &self,
+        temp_range: TemperatureInterval,
+        mean_temperature: ThermodynamicTemperature,
+        cycle_time: Time,
+    ) -> f64 {
+        let strain = self.cyclic_strain_range(temp_range);
+        if strain <= 0.0 {
+            return f64::INFINITY;
+        }
+        let c = fatigue_ductility_exponent(mean_temperature, cycle_time);
+        0.5 * (strain / (2.0 * FATIGUE_DUCTILITY_COEFFICIENT)).powf(1.0 / c)
+    }
+}
+
+/// Engelmaier fatigue ductility exponent c, from the mean joint temperature and
+/// a half-cycle dwell of `cycle_time / 2`
+pub fn fatigue_ductility_exponent(mean_temperature: ThermodynamicTemperature, cycle_time: Time) -> f64 {
+    // Below a second of dwell the log term stops mattering; keep it finite.
+    let dwell_minutes = (cycle_time.get::<minute>() / 2.0).max(1.0 / 60.0);
+    -0.442 - 6e-4 * to_celsius(mean_temperature) + 1.74e-2 * (1.0 + 360.0 / dwell_minutes).ln()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::units::celsius;
+    use uom::si::length::millimeter;
+    use uom::si::temperature_coefficient::ppm_per_kelvin;
+    use uom::si::time::hour;
+
+    /// 0805-sized chip on FR-4.
+    struct Chip;
+
+    impl SolderJointReliability for Chip {
+        fn cte_mismatch(&self) -> TemperatureCoefficient { TemperatureCoefficient::new::<ppm_per_kelvin>(10.0) }
+        fn joint_geometry_factor(&self) -> f64 { 1.0 }
+        fn neutral_point_distance(&self) -> Length { Length::new::<millimeter>(1.0) }
+        fn joint_height(&self) -> Length { Length::new::<millimeter>(0.05) }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn engelmaier_life_scales_with_swing_and_dwell() {
+        let swing = |k: f64| TemperatureInterval::new::<kelvin>(k);
+        let mean = celsius(25.0);
+        let hourly = Time::new::<hour>(1.0);
+        let life = Chip.thermal_cycles_to_failure(swing(100.0), mean, hourly);
+        assert!((1000.0..5000.0).contains(&life), "{life}");
+
+        // Nf ∝ Δγ^(1/c), so doubling the swing costs a factor 2^(-1/c).
+        let c = fatigue_ductility_exponent(mean, hourly);
+        let doubled = Chip.thermal_cycles_to_failure(swing(200.0), mean, hourly);
+        assert!((life / doubled - 2f64.powf(-1.0 / c)).abs() < 1e-9);
+        // Longer dwells let the solder creep further each cycle.
+        assert!(Chip.thermal_cycles_to_failure(swing(100.0), mean, hourly * 24.0) < life);
+    }
+}
@@ -0,0 +1,253 @@
+use serde::{Serialize, Deserialize};
+use uom::si::f64::{
+    Acceleration, Area, ElectricField, ElectricPotential, Energy, Frequency, HeatCapacity, Length, Ratio,
+    TemperatureCoefficient, ThermalResistance, ThermodynamicTemperature, Time,
+};
+use uom::si::{electric_potential::volt, length::meter};
+
+use crate::acoustic::AcousticBehavior;
+use crate::electromagnetic::ElectromagneticCompatibility;
+use crate::environment_degradation::{ChemicalMaterial, CorrosionResistance, EnvironmentalDegradation};
+use crate::lifetime::{AgingMechanisms, WearOut};
+use crate::mechanical::{MechanicalStress, SolderJointReliability};
+use crate::radiation::RadiationHardness;
+use crate::thermal::{TemperatureDependent, ThermalBehavior};
+
+// ============================================================================
+// PARAMETER OVERRIDES
+// ============================================================================
+
+/// Datasheet or measured values that replace a component's built-in defaults.
+/// Every field is optional; unset fields fall through to the component.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ParameterOverrides {
+    // Thermal
+    pub thermal_resistance_jc: Option<ThermalResistance>,
+    pub thermal_resistance_ca: Option<ThermalResistance>,
+    pub thermal_capacitance: Option<HeatCapacity>,
+    pub max_junction_temperature: Option<ThermodynamicTemperature>,
+    pub temperature_coefficient: Option<TemperatureCoefficient>,
+    // Mechanical
+    pub max_acceleration: Option<Acceleration>,
+    pub resonant_frequency: Option<Frequency>,
+    pub mechanical_q_factor: Option<f64>,
+    pub shock_resistance: Option<(Acceleration, Time)>,
+    pub cte_mismatch: Option<TemperatureCoefficient>,
+    pub joint_geometry_factor: Option<f64>,
+    pub neutral_point_distance: Option<Length>,
+    pub joint_height: Option<Length>,
+    // Electromagnetic
+    /// Flat emission level (dBµV/m) used at every frequency
+    pub emission_level: Option<f64>,
+    pub susceptibility_threshold: Option<ElectricField>,
+    pub shielding_effectiveness: Option<f64>,
+    // Radiation
+    pub total_dose_tolerance: Option<f64>,
+    pub seu_cross_section: Option<Area>,
+    pub displacement_damage_threshold: Option<f64>,
+    /// Onset LET (MeV·cm²/mg)
+    pub seu_threshold_let: Option<f64>,
+    // Acoustic
+    /// Whole-part piezoelectric coefficient (m/V)
+    pub piezoelectric_coefficient: Option<f64>,
+    /// Microphonic pickup (V/Pa)
+    pub microphonic_sensitivity: Option<f64>,
+    // Environmental
+    pub moisture_sensitivity_level: Option<u8>,
+    pub corrosion_resistance: Option<CorrosionResistance>,
+    pub outgassing_properties: Option<(Ratio, Ratio)>,
+    /// Materials known to be incompatible, on top of the component's own list
+    pub incompatible_materials: Vec<ChemicalMaterial>,
+    // Aging
+    pub activation_energy: Option<Energy>,
+    /// Inverse power law exponent for `stress_acceleration_factor`
+    pub stress_exponent: Option<f64>,
+    pub weibull_parameters: Option<(f64, Time)>,
+}
+
+impl ParameterOverrides {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// A component viewed through a set of overrides. Implements every physics trait
+/// the component implements, answering from the overrides first.
+#[derive(Clone, Copy, Debug)]
+pub struct WithOverrides<'a, T> {
+    pub component: &'a T,
+    pub overrides: &'a ParameterOverrides,
+}
+
+pub trait Overridable: Sized {
+    fn with_overrides<'a>(&'a self, overrides: &'a ParameterOverrides) -> WithOverrides<'a, Self> {
+        WithOverrides { component: self, overrides }
+    }
+}
+
+impl<T> Overridable for T {}
+
+impl<T: ThermalBehavior> ThermalBehavior for WithOverrides<'_, T> {
+    fn thermal_resistance_jc(&self) -> ThermalResistance {
+        self.overrides.thermal_resistance_jc.unwrap_or_else(|| self.component.thermal_resistance_jc())
+    }
+
+    fn thermal_resistance_ca(&self) -> ThermalResistance {
+        self.overrides.thermal_resistance_ca.unwrap_or_else(|| self.component.thermal_resistance_ca())
+    }
+
+    fn thermal_capacitance(&self) -> HeatCapacity {
+        self.overrides.thermal_capacitance.unwrap_or_else(|| self.component.thermal_capacitance())
+    }
+
+    fn max_junction_temperature(&self) -> ThermodynamicTemperature {
+        self.overrides.max_junction_temperature.unwrap_or_else(|| self.component.max_junction_temperature())
+    }
+}
+
+impl<T: TemperatureDependent> TemperatureDependent for WithOverrides<'_, T> {
+    fn temperature_coefficient(&self) -> TemperatureCoefficient {
+        self.overrides.temperature_coefficient.unwrap_or_else(|| self.component.temperature_coefficient())
+    }
+
+    fn reference_temperature(&self) -> ThermodynamicTemperature {
+        self.component.reference_temperature()
+    }
+}
+
+impl<T: MechanicalStress> MechanicalStress for WithOverrides<'_, T> {
+    fn max_acceleration(&self) -> Acceleration {
+        self.overrides.max_acceleration.unwrap_or_else(|| self.component.max_acceleration())
+    }
+
+    fn resonant_frequency(&self) -> Option<Frequency> {
+        self.overrides.resonant_frequency.or_else(|| self.component.resonant_frequency())
+    }
+
+    fn mechanical_q_factor(&self) -> f64 {
+        self.overrides.mechanical_q_factor.unwrap_or_else(|| self.component.mechanical_q_factor())
+    }
+
+    fn shock_resistance(&self) -> (Acceleration, Time) {
+        self.overrides.shock_resistance.unwrap_or_else(|| self.component.shock_resistance())
+    }
+}
+
+impl<T: SolderJointReliability> SolderJointReliability for WithOverrides<'_, T> {
+    fn cte_mismatch(&self) -> TemperatureCoefficient {
+        self.overrides.cte_mismatch.unwrap_or_else(|| self.component.cte_mismatch())
+    }
+
+    fn joint_geometry_factor(&self) -> f64 {
+        self.overrides.joint_geometry_factor.unwrap_or_else(|| self.component.joint_geometry_factor())
+    }
+
+    fn neutral_point_distance(&self) -> Length {
+        self.overrides.neutral_point_distance.unwrap_or_else(|| self.component.neutral_point_distance())
+    }
+
+    fn joint_height(&self) -> Length {
+        self.overrides.joint_height.unwrap_or_else(|| self.component.joint_height())
+    }
+}
+
+impl<T: ElectromagneticCompatibility> ElectromagneticCompatibility for WithOverrides<'_, T> {
+    fn emission_level(&self, frequency: Frequency) -> f64 {
+        self.overrides.emission_level.unwrap_or_else(|| self.component.emission_level(frequency))
+    }
+
+    fn susceptibility_threshold(&self, frequency: Frequency) -> ElectricField {
+        self.overrides.susceptibility_threshold.unwrap_or_else(|| self.component.susceptibility_threshold(frequency))
+    }
+
+    fn shielding_effectiveness(&self) -> f64 {
+        self.overrides.shielding_effectiveness.unwrap_or_else(|| self.component.shielding_effectiveness())
+    }
+}
+
+impl<T: RadiationHardness> RadiationHardness for WithOverrides<'_, T> {
+    fn total_dose_tolerance(&self) -> f64 {
+        self.overrides.total_dose_tolerance.unwrap_or_else(|| self.component.total_dose_tolerance())
+    }
+
+    fn seu_cross_section(&self) -> Area {
+        self.overrides.seu_cross_section.unwrap_or_else(|| self.component.seu_cross_section())
+    }
+
+    fn displacement_damage_threshold(&self) -> f64 {
+        self.overrides.displacement_damage_threshold.unwrap_or_else(|| self.component.displacement_damage_threshold())
+    }
+
+    fn seu_threshold_let(&self) -> f64 {
+        self.overrides.seu_threshold_let.unwrap_or_else(|| self.component.seu_threshold_let())
+    }
+}
+
+impl<T: AcousticBehavior> AcousticBehavior for WithOverrides<'_, T> {
+    fn piezoelectric_coefficient(&self) -> f64 {
+        self.overrides.piezoelectric_coefficient.unwrap_or_else(|| self.component.piezoelectric_coefficient())
+    }
+
+    fn radiating_area(&self) -> Area {
+        self.component.radiating_area()
+    }
+
+    /// The component's own response (resonance included), rescaled to an
+    /// overridden coefficient
+    fn vibration_amplitude(&self, ripple: ElectricPotential, frequency: Frequency) -> Length {
+        let amplitude = self.component.vibration_amplitude(ripple, frequency);
+        match self.overrides.piezoelectric_coefficient {
+            Some(coefficient) => {
+                let own = self.component.piezoelectric_coefficient();
+                if own > 0.0 {
+                    amplitude * (coefficient / own)
+                } else {
+                    Length::new::<meter>(coefficient * ripple.get::<volt>().abs())
+                }
+            }
+            None => amplitude,
+        }
+    }
+
+    fn microphonic_sensitivity(&self) -> f64 {
+        self.overrides.microphonic_sensitivity.unwrap_or_else(|| self.component.microphonic_sensitivity())
+    }
+}
+
+impl<T: EnvironmentalDegradation> EnvironmentalDegradation for WithOverrides<'_, T> {
+    fn moisture_sensitivity_level(&self) -> u8 {
+        self.overrides.moisture_sensitivity_level.unwrap_or_else(|| self.component.moisture_sensitivity_level())
+    }
+
+    fn corrosion_resistance(&self) -> CorrosionResistance {
+        self.overrides.corrosion_resistance.unwrap_or_else(|| self.component.corrosion_resistance())
+    }
+
+    fn outgassing_properties(&self) -> (Ratio, Ratio) {
+        self.overrides.outgassing_properties.unwrap_or_else(|| self.component.outgassing_properties())
+    }
+
+    fn chemical_compatibility(&self, material: ChemicalMaterial) -> bool {
+        !self.overrides.incompatible_materials.contains(&material) && self.component.chemical_compatibility(material)
+    }
+}
+
+impl<T: AgingMechanisms> AgingMechanisms for WithOverrides<'_, T> {
+    fn activation_energy(&self) -> Energy {
+        self.overrides.activation_energy.unwrap_or_else(|| self.component.activation_energy())
+    }
+
+    fn stress_acceleration_factor(&self, stress_level: f64, reference_level: f64) -> f64 {
+        match self.overrides.stress_exponent {
+            Some(exponent) => (stress_level / reference_level).powf(exponent),
+            None => self.component.stress_acceleration_factor(stress_level, reference_level),
+        }
+    }
+}
+
+impl<T: WearOut> WearOut for WithOverrides<'_, T> {
+    fn weibull_parameters(&self) -> (f64, Time) {
+        self.overrides.weibull_parameters.unwrap_or_else(|| self.component.weibull_parameters())
+    }
+}
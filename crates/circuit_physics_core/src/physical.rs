@@ -1,7 +1,17 @@
 
 use serde::{Serialize, Deserialize};
 
+use crate::environment_degradation::{ChemicalMaterial, CorrosionResistance};
+
+use uom::si::acceleration::standard_gravity;
 use uom::si::f64::*;
+use uom::si::frequency::hertz;
+use uom::si::heat_capacity::joule_per_kelvin;
+use uom::si::length::millimeter;
+use uom::si::ratio::percent;
+use uom::si::temperature_coefficient::ppm_per_kelvin;
+use uom::si::thermal_resistance::kelvin_per_watt;
+use uom::si::time::second;
 
 
 // ============================================================================
@@ -91,5 +101,162 @@ pub enum PackageType {
     BareChip,
 }
 
+impl PackageType {
+    /// Typical junction-to-case thermal resistance for small parts in this package
+    pub fn typical_thermal_resistance_jc(self) -> ThermalResistance {
+        let k_per_w = match self {
+            PackageType::ThroughHole => 40.0,
+            PackageType::SurfaceMount => 60.0,
+            PackageType::ChipOnBoard => 15.0,
+            PackageType::BareChip => 5.0,
+        };
+        ThermalResistance::new::<kelvin_per_watt>(k_per_w)
+    }
+
+    /// Typical case-to-ambient thermal resistance, still air on FR-4
+    pub fn typical_thermal_resistance_ca(self) -> ThermalResistance {
+        let k_per_w = match self {
+            PackageType::ThroughHole => 120.0,
+            PackageType::SurfaceMount => 220.0,
+            PackageType::ChipOnBoard => 60.0,
+            PackageType::BareChip => 40.0,
+        };
+        ThermalResistance::new::<kelvin_per_watt>(k_per_w)
+    }
+
+    /// Typical lumped thermal capacitance of the package body
+    pub fn typical_thermal_capacitance(self) -> HeatCapacity {
+        let j_per_k = match self {
+            PackageType::ThroughHole => 0.2,
+            PackageType::SurfaceMount => 0.02,
+            PackageType::ChipOnBoard => 0.05,
+            PackageType::BareChip => 0.005,
+        };
+        HeatCapacity::new::<joule_per_kelvin>(j_per_k)
+    }
+
+    /// Sustained acceleration the mounted part survives; long leads are the weak point
+    pub fn typical_max_acceleration(self) -> Acceleration {
+        let g = match self {
+            PackageType::ThroughHole => 20.0,
+            PackageType::SurfaceMount => 50.0,
+            PackageType::ChipOnBoard => 100.0,
+            PackageType::BareChip => 200.0,
+        };
+        Acceleration::new::<standard_gravity>(g)
+    }
+
+    /// First resonance of the part on its leads or joints
+    pub fn typical_resonant_frequency(self) -> Frequency {
+        let hz = match self {
+            PackageType::ThroughHole => 300.0,
+            PackageType::SurfaceMount => 5000.0,
+            PackageType::ChipOnBoard => 8000.0,
+            PackageType::BareChip => 20000.0,
+        };
+        Frequency::new::<hertz>(hz)
+    }
+
+    /// Mechanical Q of the first mode
+    pub fn typical_mechanical_q_factor(self) -> f64 {
+        match self {
+            PackageType::ThroughHole => 20.0,
+            PackageType::SurfaceMount => 30.0,
+            PackageType::ChipOnBoard => 40.0,
+            PackageType::BareChip => 50.0,
+        }
+    }
+
+    /// Half-sine shock rating as (peak acceleration, duration), per MIL-STD-202 style tests
+    pub fn typical_shock_resistance(self) -> (Acceleration, Time) {
+        let (g, seconds) = match self {
+            PackageType::ThroughHole => (100.0, 6e-3),
+            PackageType::SurfaceMount => (1500.0, 0.5e-3),
+            PackageType::ChipOnBoard => (1000.0, 0.5e-3),
+            PackageType::BareChip => (3000.0, 0.2e-3),
+        };
+        (Acceleration::new::<standard_gravity>(g), Time::new::<second>(seconds))
+    }
+
+    /// CTE mismatch between part body and FR-4 seen by the joints;
+    /// compliant leads absorb most of it
+    pub fn typical_cte_mismatch(self) -> TemperatureCoefficient {
+        let ppm = match self {
+            PackageType::ThroughHole => 3.0,
+            PackageType::SurfaceMount => 10.0,
+            PackageType::ChipOnBoard | PackageType::BareChip => 14.0,
+        };
+        TemperatureCoefficient::new::<ppm_per_kelvin>(ppm)
+    }
+
+    /// Relative solder joint fatigue resistance (1.0 = chip resistor fillet)
+    pub fn typical_joint_geometry_factor(self) -> f64 {
+        match self {
+            PackageType::ThroughHole => 5.0,
+            PackageType::SurfaceMount => 1.0,
+            PackageType::ChipOnBoard => 0.5,
+            PackageType::BareChip => 0.3,
+        }
+    }
+
+    /// Distance from the package centre to its outermost joint
+    pub fn typical_neutral_point_distance(self) -> Length {
+        let mm = match self {
+            PackageType::ThroughHole | PackageType::BareChip => 2.5,
+            PackageType::SurfaceMount => 1.0,
+            PackageType::ChipOnBoard => 3.0,
+        };
+        Length::new::<millimeter>(mm)
+    }
+
+    /// Solder stand-off height of the joints
+    pub fn typical_joint_height(self) -> Length {
+        let mm = match self {
+            PackageType::ThroughHole => 0.5,
+            PackageType::SurfaceMount => 0.05,
+            PackageType::ChipOnBoard => 0.03,
+            PackageType::BareChip => 0.075,
+        };
+        Length::new::<millimeter>(mm)
+    }
+
+    /// J-STD-020 moisture sensitivity level of a plastic body in this package
+    pub fn typical_moisture_sensitivity_level(self) -> u8 {
+        match self {
+            PackageType::ThroughHole => 1,
+            PackageType::SurfaceMount => 3,
+            PackageType::ChipOnBoard => 5,
+            PackageType::BareChip => 6,
+        }
+    }
+
+    pub fn typical_corrosion_resistance(self) -> CorrosionResistance {
+        match self {
+            PackageType::ThroughHole | PackageType::SurfaceMount => CorrosionResistance::Good,
+            PackageType::ChipOnBoard => CorrosionResistance::Fair,
+            PackageType::BareChip => CorrosionResistance::Poor,
+        }
+    }
+
+    /// ASTM E595 outgassing as (TML, CVCM)
+    pub fn typical_outgassing(self) -> (Ratio, Ratio) {
+        let (tml, cvcm) = match self {
+            PackageType::ThroughHole => (0.5, 0.05),
+            PackageType::SurfaceMount => (0.3, 0.03),
+            PackageType::ChipOnBoard => (0.8, 0.1),
+            PackageType::BareChip => (0.0, 0.0),
+        };
+        (Ratio::new::<percent>(tml), Ratio::new::<percent>(cvcm))
+    }
+
+    /// Whether the package body tolerates exposure to `material` (cleaning, potting, conformal coat)
+    pub fn typical_chemical_compatibility(self, material: ChemicalMaterial) -> bool {
+        match self {
+            PackageType::BareChip => matches!(material, ChemicalMaterial::Silicone | ChemicalMaterial::Fluorocarbon),
+            PackageType::ThroughHole | PackageType::SurfaceMount | PackageType::ChipOnBoard => material != ChemicalMaterial::Acetone,
+        }
+    }
+}
+
 
 
@@ -1,14 +1,5 @@
-use serde::{Serialize, Deserialize};
-// use bevy::prelude::*;
-// use uom::si::f64::*;
-use uom::si::{
-    acceleration::meter_per_second_squared, electric_current::ampere, electric_potential::volt, electrical_resistance::ohm, f32::{Acceleration, ElectricCurrent, ElectricField, ElectricalResistance, Frequency, MagneticFluxDensity, Power, ThermodynamicTemperature, Time}, frequency::hertz, luminous_intensity::candela, magnetic_flux_density::tesla, power::watt, pressure::pascal, thermodynamic_temperature::kelvin, time::second
-};
-
-
-
-
-
+use uom::si::f64::{Area, Frequency};
+use uom::si::{area::square_centimeter, frequency::hertz};
 
 // ============================================================================
 // RADIATION EFFECTS TRAITS
@@ -16,17 +7,66 @@ use uom::si::{
 
 /// Components susceptible to radiation effects
 pub trait RadiationHardness {
-    /// Total ionizing dose tolerance (rad or Gy)
-    fn total_dose_tolerance(&self) -> f32;
+    /// Total ionizing dose tolerance (rad(Si))
+    fn total_dose_tolerance(&self) -> f64;
     
-    /// Single event upset cross-section (cm²)
-    fn seu_cross_section(&self) -> f32;
+    /// Single event upset cross-section
+    fn seu_cross_section(&self) -> Area;
     
     /// Displacement damage threshold (MeV·cm²/g)
-    fn displacement_damage_threshold(&self) -> f32;
+    fn displacement_damage_threshold(&self) -> f64;
+    
+    /// Onset LET (MeV·cm²/mg) below which ions don't cause upsets; unhardened
+    /// silicon upsets from about 1
+    fn seu_threshold_let(&self) -> f64 {
+        1.0
+    }
+    
+    /// Upsets per second in an environment given as an integral LET spectrum:
+    /// `(LET, flux of ions at or above that LET per cm²·s)`, ascending in LET
+    fn seu_rate(&self, let_spectrum: &[(f64, f64)]) -> Frequency {
+        let sigma = self.seu_cross_section().get::<square_centimeter>();
+        Frequency::new::<hertz>(sigma * integral_flux(let_spectrum, self.seu_threshold_let()))
+    }
     
     /// Check if component can survive radiation environment
-    fn radiation_survivability(&self, total_dose: f32, particle_flux: f32) -> bool {
+    fn radiation_survivability(&self, total_dose: f64, particle_flux: f64) -> bool {
         total_dose <= self.total_dose_tolerance()
     }
-}
\ No newline at end of file
+}
+
+/// Flux of ions with LET at or above `threshold`, interpolated log-log in an
+/// integral LET spectrum; below the first point the first flux applies and
+/// above the last there are none
+pub fn integral_flux(let_spectrum: &[(f64, f64)], threshold: f64) -> f64 {
+    let Some(&(first_let, first_flux)) = let_spectrum.first() else {
+        return 0.0;
+    };
+    if threshold <= first_let {
+        return first_flux;
+    }
+    let upper = let_spectrum.partition_point(|&(l, _)| l < threshold);
+    let Some(&(l1, f1)) = let_spectrum.get(upper) else {
+        return 0.0;
+    };
+    let (l0, f0) = let_spectrum[upper - 1];
+    if f0 <= 0.0 || f1 <= 0.0 {
+        return f0 + (f1 - f0) * (threshold - l0) / (l1 - l0);
+    }
+    f0 * (f1 / f0).powf((threshold / l0).ln() / (l1 / l0).ln())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integral_flux_interpolates_log_log() {
+        let spectrum = [(1.0, 1e-3), (10.0, 1e-5), (100.0, 1e-9)];
+        assert_eq!(integral_flux(&spectrum, 0.5), 1e-3);
+        assert!((integral_flux(&spectrum, 10.0) - 1e-5).abs() < 1e-18);
+        // Halfway in log LET is halfway in log flux.
+        assert!((integral_flux(&spectrum, 10f64.sqrt()) - 1e-4).abs() < 1e-12);
+        assert_eq!(integral_flux(&spectrum, 200.0), 0.0);
+    }
+}
@@ -1,9 +1,6 @@
-use serde::{Serialize, Deserialize};
-// use bevy::prelude::*;
-// use uom::si::f64::*;
-use uom::si::{
-    acceleration::meter_per_second_squared, electric_current::ampere, electric_potential::volt, electrical_resistance::ohm, f32::{Acceleration, ElectricCurrent, ElectricField, ElectricalResistance, Frequency, MagneticFluxDensity, Power, ThermodynamicTemperature, Time}, frequency::hertz, luminous_intensity::candela, magnetic_flux_density::tesla, power::watt, pressure::pascal, thermodynamic_temperature::kelvin, time::second
-};
+use uom::si::f64::{HeatCapacity, Power, TemperatureCoefficient, ThermalResistance, ThermodynamicTemperature};
+
+use crate::units::{offset, reference_temperature, temperature_rise};
 
 // ============================================================================
 // THERMAL SIMULATION TRAITS
@@ -11,22 +8,21 @@ use uom::si::{
 
 /// Components that exhibit thermal behavior and can be thermally simulated
 pub trait ThermalBehavior {
-    /// Thermal resistance from junction to case (K/W)
-    fn thermal_resistance_jc(&self) -> f32;
+    /// Thermal resistance from junction to case
+    fn thermal_resistance_jc(&self) -> ThermalResistance;
     
-    /// Thermal resistance from case to ambient (K/W)
-    fn thermal_resistance_ca(&self) -> f32;
+    /// Thermal resistance from case to ambient
+    fn thermal_resistance_ca(&self) -> ThermalResistance;
     
-    /// Thermal capacitance for transient analysis (J/K)
-    fn thermal_capacitance(&self) -> f32;
+    /// Thermal capacitance for transient analysis
+    fn thermal_capacitance(&self) -> HeatCapacity;
     
-    /// Maximum junction temperature (K)
+    /// Maximum junction temperature
     fn max_junction_temperature(&self) -> ThermodynamicTemperature;
     
     /// Current junction temperature based on power dissipation
     fn junction_temperature(&self, power: Power, ambient_temp: ThermodynamicTemperature) -> ThermodynamicTemperature {
-        let temp_rise = power.get::<watt>() * (self.thermal_resistance_jc() + self.thermal_resistance_ca());
-        ThermodynamicTemperature::new::<kelvin>(ambient_temp.get::<kelvin>() + temp_rise)
+        offset(ambient_temp, power * (self.thermal_resistance_jc() + self.thermal_resistance_ca()))
     }
     
     /// Check if component is within thermal limits
@@ -37,17 +33,16 @@ pub trait ThermalBehavior {
 
 /// Components that have temperature-dependent electrical characteristics
 pub trait TemperatureDependent {
-    /// Temperature coefficient of primary parameter (ppm/°C or %/°C)
-    fn temperature_coefficient(&self) -> f32;
+    /// Temperature coefficient of primary parameter
+    fn temperature_coefficient(&self) -> TemperatureCoefficient;
     
     /// Reference temperature for specifications (typically 25°C)
     fn reference_temperature(&self) -> ThermodynamicTemperature {
-        ThermodynamicTemperature::new::<kelvin>(298.15)
+        reference_temperature()
     }
     
-    /// Calculate parameter drift due to temperature
-    fn parameter_drift(&self, current_temp: ThermodynamicTemperature) -> f32 {
-        let temp_diff = current_temp.get::<kelvin>() - self.reference_temperature().get::<kelvin>();
-        self.temperature_coefficient() * temp_diff as f32 / 1e6 // Convert ppm to fraction
+    /// Calculate parameter drift due to temperature, as a fraction of the nominal value
+    fn parameter_drift(&self, current_temp: ThermodynamicTemperature) -> f64 {
+        (self.temperature_coefficient() * temperature_rise(self.reference_temperature(), current_temp)).value
     }
 }
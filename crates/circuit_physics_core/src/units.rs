@@ -0,0 +1,69 @@
+//! Helpers for building and converting the `uom::si::f64` quantities used
+//! throughout this crate.
+
+use uom::si::f64::{TemperatureInterval, ThermodynamicTemperature};
+use uom::si::{temperature_interval, thermodynamic_temperature};
+use uom::si::{Dimension, Quantity, SI};
+
+/// 25 °C, the usual datasheet reference temperature
+pub const REFERENCE_TEMPERATURE_KELVIN: f64 = 298.15;
+
+pub fn reference_temperature() -> ThermodynamicTemperature {
+    ThermodynamicTemperature::new::<thermodynamic_temperature::kelvin>(REFERENCE_TEMPERATURE_KELVIN)
+}
+
+pub fn celsius(degrees: f64) -> ThermodynamicTemperature {
+    ThermodynamicTemperature::new::<thermodynamic_temperature::degree_celsius>(degrees)
+}
+
+pub fn to_celsius(temperature: ThermodynamicTemperature) -> f64 {
+    temperature.get::<thermodynamic_temperature::degree_celsius>()
+}
+
+/// `to - from` as an interval; absolute temperatures can't be subtracted directly
+pub fn temperature_rise(from: ThermodynamicTemperature, to: ThermodynamicTemperature) -> TemperatureInterval {
+    TemperatureInterval::new::<temperature_interval::kelvin>(
+        to.get::<thermodynamic_temperature::kelvin>() - from.get::<thermodynamic_temperature::kelvin>(),
+    )
+}
+
+/// `temperature` shifted by `interval`
+pub fn offset(temperature: ThermodynamicTemperature, interval: TemperatureInterval) -> ThermodynamicTemperature {
+    ThermodynamicTemperature::new::<thermodynamic_temperature::kelvin>(
+        temperature.get::<thermodynamic_temperature::kelvin>() + interval.get::<temperature_interval::kelvin>(),
+    )
+}
+
+/// Convert an `f32` quantity from other crates to this crate's `f64` representation
+pub fn widen<D: Dimension + ?Sized>(quantity: Quantity<D, SI<f32>, f32>) -> Quantity<D, SI<f64>, f64> {
+    Quantity { dimension: quantity.dimension, units: std::marker::PhantomData, value: f64::from(quantity.value) }
+}
+
+/// Convert to `f32` for rendering or storage where precision doesn't matter
+pub fn narrow<D: Dimension + ?Sized>(quantity: Quantity<D, SI<f64>, f64>) -> Quantity<D, SI<f32>, f32> {
+    Quantity { dimension: quantity.dimension, units: std::marker::PhantomData, value: quantity.value as f32 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uom::si::f32;
+    use uom::si::length::millimeter;
+
+    #[test]
+    fn celsius_round_trips_through_kelvin() {
+        let t = celsius(85.0);
+        assert!((t.get::<thermodynamic_temperature::kelvin>() - 358.15).abs() < 1e-9);
+        assert!((to_celsius(t) - 85.0).abs() < 1e-9);
+        let rise = temperature_rise(reference_temperature(), t);
+        assert!((rise.get::<temperature_interval::kelvin>() - 60.0).abs() < 1e-9);
+        assert!((to_celsius(offset(reference_temperature(), rise)) - 85.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn widen_preserves_value_in_any_unit() {
+        let length = widen(f32::Length::new::<millimeter>(2.5));
+        assert!((length.get::<millimeter>() - 2.5).abs() < 1e-6);
+        assert!((narrow(length).get::<millimeter>() - 2.5).abs() < 1e-6);
+    }
+}